@@ -5,6 +5,7 @@ use log::error;
 use select::node::Node;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
+use std::collections::BTreeMap;
 
 pub struct AocPage {
     low: Low,
@@ -67,16 +68,19 @@ impl AocPage {
                 part1: TestCase {
                     input,
                     output: part1_out,
+                    params: BTreeMap::new(),
                 },
             },
             ProblemStage::Part2 | ProblemStage::Complete => TestCases::Part2 {
                 part1: TestCase {
                     input: input.clone(),
                     output: part1_out,
+                    params: BTreeMap::new(),
                 },
                 part2: TestCase {
                     input,
                     output: part2_out,
+                    params: BTreeMap::new(),
                 },
             },
         })
@@ -185,6 +189,10 @@ pub enum TestCases {
 pub struct TestCase {
     pub input: Option<String>,
     pub output: Option<String>,
+    /// Parameters this example needs that differ from the day's usual example values, like a
+    /// smaller grid. These are never scraped, only added by hand to the saved data
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, String>,
 }
 
 impl TestCases {
@@ -274,10 +282,12 @@ mod tests {
                 part1: TestCase {
                     input: Some(input.to_owned()),
                     output: Some(p1_out.to_owned()),
+                    params: BTreeMap::new(),
                 },
                 part2: TestCase {
                     input: Some(input.to_owned()),
                     output: Some(p2_out.to_owned()),
+                    params: BTreeMap::new(),
                 },
             }
         );
@@ -340,6 +350,7 @@ mod tests {
                     // We should be able to ignore the last <em> in each part because this is the
                     // question
                     output: Some("X,Y".to_owned()),
+                    params: BTreeMap::new(),
                 },
             }
         );
@@ -0,0 +1,506 @@
+//! Records snapshots of a [`Matrix`] while a simulation runs, so they can be played back in the
+//! terminal or exported as an animated GIF or a sequence of PNGs.
+//!
+//! ```no_run
+//! # use util::{animation::Recorder, Matrix};
+//! let mut grid = Matrix::new_with_value(3, 3, b'.');
+//! let mut recorder = Recorder::new();
+//! for i in 0..3 {
+//!     grid.set(i, i, b'#');
+//!     recorder
+//!         .record(&grid, |c| *c as char)
+//!         .highlight(i, i, termcolor::Color::Red)
+//!         .caption(format!("step {}", i));
+//! }
+//! recorder.play().unwrap();
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use termcolor::Color;
+
+use crate::image::{Image, Rgb};
+use crate::Matrix;
+
+/// One snapshot of a grid, with optional highlighted cells and a caption shown below it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    rows: usize,
+    cols: usize,
+    cells: Vec<char>,
+    highlights: Vec<Option<Color>>,
+    caption: String,
+}
+
+impl Frame {
+    /// Captures `matrix`, using `f` to choose the character shown for each cell
+    pub fn new<T>(matrix: &Matrix<T>, f: impl Fn(&T) -> char) -> Self {
+        let cells: Vec<char> = matrix.iter().map(f).collect();
+        Self {
+            rows: matrix.rows(),
+            cols: matrix.cols(),
+            highlights: vec![None; cells.len()],
+            cells,
+            caption: String::new(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> char {
+        self.cells[row * self.cols + col]
+    }
+
+    /// Returns the color `row`, `col` is highlighted with, if any
+    pub fn highlight_at(&self, row: usize, col: usize) -> Option<Color> {
+        self.highlights[row * self.cols + col]
+    }
+
+    pub fn caption_text(&self) -> &str {
+        &self.caption
+    }
+
+    /// Draws the cell at `row`, `col` in `color`. Cells outside the frame are ignored, so
+    /// positions that wander off the grid don't need to be filtered first
+    pub fn highlight(&mut self, row: usize, col: usize, color: Color) -> &mut Self {
+        if row < self.rows && col < self.cols {
+            self.highlights[row * self.cols + col] = Some(color);
+        }
+        self
+    }
+
+    /// Highlights every cell in `cells` with `color`
+    pub fn highlight_all(
+        &mut self,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        color: Color,
+    ) -> &mut Self {
+        for (row, col) in cells {
+            self.highlight(row, col, color);
+        }
+        self
+    }
+
+    pub fn caption(&mut self, caption: impl Into<String>) -> &mut Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// Draws each cell as a `scale` by `scale` square. Highlighted cells use their highlight
+    /// color, everything else uses `palette`
+    pub fn to_image(&self, scale: usize, palette: impl Fn(char) -> Rgb) -> Image {
+        let mut image = Image::new(self.cols * scale, self.rows * scale, [0, 0, 0]);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let color = match self.highlight_at(row, col) {
+                    Some(color) => color_to_rgb(color),
+                    None => palette(self.get(row, col)),
+                };
+                image.fill_rect(col * scale, row * scale, scale, scale, color);
+            }
+        }
+        image
+    }
+}
+
+/// Returns the RGB value a terminal typically uses for `color`
+pub fn color_to_rgb(color: Color) -> Rgb {
+    const ANSI: [Rgb; 16] = [
+        [0, 0, 0],
+        [205, 0, 0],
+        [0, 205, 0],
+        [205, 205, 0],
+        [0, 0, 238],
+        [205, 0, 205],
+        [0, 205, 205],
+        [229, 229, 229],
+        [127, 127, 127],
+        [255, 0, 0],
+        [0, 255, 0],
+        [255, 255, 0],
+        [92, 92, 255],
+        [255, 0, 255],
+        [0, 255, 255],
+        [255, 255, 255],
+    ];
+    match color {
+        Color::Black => ANSI[0],
+        Color::Red => ANSI[1],
+        Color::Green => ANSI[2],
+        Color::Yellow => ANSI[3],
+        Color::Blue => ANSI[4],
+        Color::Magenta => ANSI[5],
+        Color::Cyan => ANSI[6],
+        Color::White => ANSI[7],
+        Color::Ansi256(n @ 0..=15) => ANSI[n as usize],
+        Color::Ansi256(n @ 16..=231) => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let n = n - 16;
+            [level(n / 36), level(n / 6 % 6), level(n % 6)]
+        }
+        Color::Ansi256(n) => {
+            let grey = 8 + (n - 232) * 10;
+            [grey, grey, grey]
+        }
+        Color::Rgb(r, g, b) => [r, g, b],
+        _ => ANSI[7],
+    }
+}
+
+/// The default palette for exports: `#` is white, `.` and spaces are black, and any other
+/// character gets a distinct color from its code point
+pub fn default_palette(c: char) -> Rgb {
+    match c {
+        '#' => [255, 255, 255],
+        '.' | ' ' => [0, 0, 0],
+        c => {
+            let n = c as u32;
+            let channel = |shift: u32| (n.wrapping_mul(2654435761) >> shift) as u8 | 0x40;
+            [channel(0), channel(8), channel(16)]
+        }
+    }
+}
+
+/// Collects [`Frame`]s for playback and export
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    frames: Vec<Frame>,
+    delay: Duration,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            delay: Duration::from_millis(100),
+        }
+    }
+
+    /// Sets how long each frame is shown for, both in the terminal and in exported GIFs
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn push(&mut self, frame: Frame) -> &mut Frame {
+        self.frames.push(frame);
+        self.frames.last_mut().unwrap()
+    }
+
+    /// Captures `matrix` as a new frame, returning it so highlights and a caption can be added
+    pub fn record<T>(&mut self, matrix: &Matrix<T>, f: impl Fn(&T) -> char) -> &mut Frame {
+        self.push(Frame::new(matrix, f))
+    }
+
+    /// Plays the frames in the terminal.
+    ///
+    /// Space pauses, the left and right arrows step while paused, `+` and `-` change the speed,
+    /// and `q` or escape quits. When stdout isn't a terminal, every frame is printed once instead
+    pub fn play(&self) -> io::Result<()> {
+        use crossterm::tty::IsTty;
+
+        if io::stdout().is_tty() {
+            player::play(&self.frames, self.delay)
+        } else {
+            let mut out = io::stdout().lock();
+            for frame in &self.frames {
+                player::draw(&mut out, frame, "\n")?;
+                writeln!(out)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Writes every frame as an endlessly looping GIF, drawing each cell as a `scale` pixel square
+    pub fn write_gif(
+        &self,
+        out: impl Write,
+        scale: usize,
+        palette: impl Fn(char) -> Rgb,
+    ) -> io::Result<()> {
+        use gif::{Encoder, Repeat};
+
+        let Some(first) = self.frames.first() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no frames to write",
+            ));
+        };
+        let too_big = |_| io::Error::new(io::ErrorKind::InvalidInput, "frame is too big for a GIF");
+        let width = u16::try_from(first.cols * scale).map_err(too_big)?;
+        let height = u16::try_from(first.rows * scale).map_err(too_big)?;
+        let to_io = |e: gif::EncodingError| io::Error::other(e);
+
+        let mut encoder = Encoder::new(out, width, height, &[]).map_err(to_io)?;
+        encoder.set_repeat(Repeat::Infinite).map_err(to_io)?;
+        let delay = (self.delay.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+        for frame in &self.frames {
+            if (frame.rows, frame.cols) != (first.rows, first.cols) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "every frame in a GIF must be the same size",
+                ));
+            }
+            let image = frame.to_image(scale, &palette);
+            let mut gif_frame = gif::Frame::from_rgb_speed(width, height, image.pixels(), 10);
+            gif_frame.delay = delay;
+            encoder.write_frame(&gif_frame).map_err(to_io)?;
+        }
+        Ok(())
+    }
+
+    /// Saves every frame to the GIF at `path`, see [`Recorder::write_gif`]
+    pub fn save_gif(
+        &self,
+        path: impl AsRef<Path>,
+        scale: usize,
+        palette: impl Fn(char) -> Rgb,
+    ) -> io::Result<()> {
+        self.write_gif(BufWriter::new(File::create(path)?), scale, palette)
+    }
+
+    /// Saves each frame as `frame_00000.png`, `frame_00001.png`... in `dir`, creating it if
+    /// needed. Returns the paths written
+    pub fn save_png_sequence(
+        &self,
+        dir: impl AsRef<Path>,
+        scale: usize,
+        palette: impl Fn(char) -> Rgb,
+    ) -> io::Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let mut paths = Vec::new();
+        for (i, frame) in self.frames.iter().enumerate() {
+            let path = dir.join(format!("frame_{:05}.png", i));
+            frame.to_image(scale, &palette).save_png(&path)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod player {
+    use std::io::{self, Write};
+    use std::time::{Duration, Instant};
+
+    use crossterm::event::{self, Event, KeyCode, KeyEvent};
+    use crossterm::style::{self, Print, ResetColor, SetForegroundColor};
+    use crossterm::{cursor, queue, terminal};
+
+    use super::Frame;
+
+    fn to_crossterm(color: termcolor::Color) -> style::Color {
+        use termcolor::Color::*;
+        match color {
+            Black => style::Color::Black,
+            Blue => style::Color::DarkBlue,
+            Green => style::Color::DarkGreen,
+            Red => style::Color::DarkRed,
+            Cyan => style::Color::DarkCyan,
+            Magenta => style::Color::DarkMagenta,
+            Yellow => style::Color::DarkYellow,
+            White => style::Color::Grey,
+            Ansi256(n) => style::Color::AnsiValue(n),
+            Rgb(r, g, b) => style::Color::Rgb { r, g, b },
+            _ => style::Color::Reset,
+        }
+    }
+
+    /// Draws `frame` and its caption, ending lines with `newline` since raw mode needs `\r\n`
+    pub(super) fn draw(out: &mut impl Write, frame: &Frame, newline: &str) -> io::Result<()> {
+        for row in 0..frame.rows() {
+            for col in 0..frame.cols() {
+                let c = frame.get(row, col);
+                match frame.highlight_at(row, col) {
+                    Some(color) => queue!(
+                        out,
+                        SetForegroundColor(to_crossterm(color)),
+                        Print(c),
+                        ResetColor
+                    )?,
+                    None => queue!(out, Print(c))?,
+                }
+            }
+            queue!(out, Print(newline))?;
+        }
+        if !frame.caption_text().is_empty() {
+            queue!(out, Print(frame.caption_text()), Print(newline))?;
+        }
+        out.flush()
+    }
+
+    /// Leaves raw mode and the alternate screen even if playback fails
+    struct RawTerminal;
+
+    impl RawTerminal {
+        fn enter(out: &mut impl Write) -> io::Result<Self> {
+            terminal::enable_raw_mode()?;
+            crossterm::execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+            Ok(Self)
+        }
+    }
+
+    impl Drop for RawTerminal {
+        fn drop(&mut self) {
+            let _ = crossterm::execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+            let _ = terminal::disable_raw_mode();
+        }
+    }
+
+    pub(super) fn play(frames: &[Frame], mut delay: Duration) -> io::Result<()> {
+        if frames.is_empty() {
+            return Ok(());
+        }
+        let mut out = io::stdout().lock();
+        let _terminal = RawTerminal::enter(&mut out)?;
+
+        let mut current = 0;
+        let mut paused = false;
+        let mut next_frame = Instant::now() + delay;
+        loop {
+            queue!(
+                out,
+                cursor::MoveTo(0, 0),
+                terminal::Clear(terminal::ClearType::All)
+            )?;
+            draw(&mut out, &frames[current], "\r\n")?;
+            let state = if paused { "paused" } else { "playing" };
+            queue!(
+                out,
+                Print(format!(
+                    "frame {}/{}, {}ms per frame, {}  [space] pause  [<-/->] step  [+/-] speed  [q] quit",
+                    current + 1,
+                    frames.len(),
+                    delay.as_millis(),
+                    state
+                ))
+            )?;
+            out.flush()?;
+
+            let timeout = if paused {
+                Duration::from_secs(3600)
+            } else {
+                next_frame.saturating_duration_since(Instant::now())
+            };
+            if event::poll(timeout)? {
+                if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+                    match code {
+                        KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                        KeyCode::Char(' ') => paused = !paused,
+                        KeyCode::Right => {
+                            paused = true;
+                            current = (current + 1).min(frames.len() - 1);
+                        }
+                        KeyCode::Left => {
+                            paused = true;
+                            current = current.saturating_sub(1);
+                        }
+                        KeyCode::Char('+') | KeyCode::Char('=') => {
+                            delay = (delay / 2).max(Duration::from_millis(1));
+                        }
+                        KeyCode::Char('-') => delay *= 2,
+                        _ => {}
+                    }
+                }
+                next_frame = Instant::now() + delay;
+            } else if !paused {
+                if current + 1 == frames.len() {
+                    paused = true;
+                } else {
+                    current += 1;
+                }
+                next_frame = Instant::now() + delay;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording() -> Recorder {
+        let mut grid = Matrix::new_with_value(2, 3, b'.');
+        let mut recorder = Recorder::new().with_delay(Duration::from_millis(50));
+        for col in 0..3 {
+            grid.set(1, col, b'#');
+            recorder
+                .record(&grid, |c| *c as char)
+                .highlight(0, col, Color::Red)
+                .highlight(5, 5, Color::Red)
+                .caption(format!("column {}", col));
+        }
+        recorder
+    }
+
+    #[test]
+    fn frames() {
+        let recorder = recording();
+        assert_eq!(recorder.len(), 3);
+        let frame = &recorder.frames()[1];
+        assert_eq!((frame.rows(), frame.cols()), (2, 3));
+        assert_eq!(frame.get(1, 1), '#');
+        assert_eq!(frame.get(1, 2), '.');
+        assert_eq!(frame.highlight_at(0, 1), Some(Color::Red));
+        assert_eq!(frame.highlight_at(0, 0), None);
+        assert_eq!(frame.caption_text(), "column 1");
+
+        let image = frame.to_image(2, default_palette);
+        assert_eq!((image.width(), image.height()), (6, 4));
+        assert_eq!(image.get(3, 1), [205, 0, 0]);
+        assert_eq!(image.get(0, 0), [0, 0, 0]);
+        assert_eq!(image.get(1, 2), [255, 255, 255]);
+    }
+
+    #[test]
+    fn export() {
+        let recorder = recording();
+        let mut gif = Vec::new();
+        recorder.write_gif(&mut gif, 4, default_palette).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        assert!(Recorder::new()
+            .write_gif(Vec::new(), 1, default_palette)
+            .is_err());
+
+        let mut text = Vec::new();
+        player::draw(&mut text, &recorder.frames()[0], "\n").unwrap();
+        let text = String::from_utf8(text).unwrap();
+        assert!(text.ends_with("#..\ncolumn 0\n"), "{}", text);
+    }
+
+    #[test]
+    fn terminal_colors() {
+        assert_eq!(color_to_rgb(Color::Ansi256(9)), [255, 0, 0]);
+        assert_eq!(color_to_rgb(Color::Ansi256(16)), [0, 0, 0]);
+        assert_eq!(color_to_rgb(Color::Ansi256(231)), [255, 255, 255]);
+        assert_eq!(color_to_rgb(Color::Ansi256(255)), [238, 238, 238]);
+        assert_eq!(color_to_rgb(Color::Rgb(1, 2, 3)), [1, 2, 3]);
+    }
+}
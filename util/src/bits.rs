@@ -0,0 +1,569 @@
+//! Reading values bit by bit out of hex or binary strings, and fixed size sets of bits.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, Not, Shl, Shr};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BitError {
+    /// A read needed more bits than the reader had left
+    UnexpectedEnd {
+        /// The position of the read, relative to the start of the reader
+        position: usize,
+        wanted: usize,
+        available: usize,
+    },
+    /// More than 128 bits were read at once
+    TooWide(u32),
+    /// The bits that were read don't fit in the requested type
+    Overflow { value: u128, ty: &'static str },
+    /// The input string contained a character that isn't a hex or binary digit
+    InvalidDigit { index: usize, found: char },
+}
+
+impl fmt::Display for BitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitError::UnexpectedEnd {
+                position,
+                wanted,
+                available,
+            } => write!(
+                f,
+                "wanted {} bits at bit {}, but only {} are left",
+                wanted, position, available
+            ),
+            BitError::TooWide(bits) => write!(f, "can't read {} bits at once", bits),
+            BitError::Overflow { value, ty } => write!(f, "{} doesn't fit in `{}`", value, ty),
+            BitError::InvalidDigit { index, found } => {
+                write!(f, "invalid digit `{}` at index {}", found, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BitError {}
+
+/// Reads big endian values of any width from a sequence of bits, like the packets from 2021
+/// day 16.
+///
+/// ```
+/// # use util::bits::BitReader;
+/// let mut reader = BitReader::from_hex("D2FE28").unwrap();
+/// assert_eq!(reader.read::<u8>(3), Ok(6));
+/// assert_eq!(reader.read::<u8>(3), Ok(4));
+/// assert_eq!(reader.position(), 6);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitReader<'a> {
+    bytes: Cow<'a, [u8]>,
+    /// The absolute bit index this reader starts at, positions are reported relative to this
+    start: usize,
+    /// The absolute index of the next bit to read
+    pos: usize,
+    /// The absolute index one past the last bit this reader may read
+    end: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Reads every bit of `bytes`, most significant bit first
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            end: bytes.len() * 8,
+            bytes: Cow::Borrowed(bytes),
+            start: 0,
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bits read so far
+    pub fn position(&self) -> usize {
+        self.pos - self.start
+    }
+
+    /// Returns the total number of bits this reader covers
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bits left to read
+    pub fn remaining(&self) -> usize {
+        self.end - self.pos
+    }
+
+    fn check(&self, bits: usize) -> Result<(), BitError> {
+        if bits > self.remaining() {
+            Err(BitError::UnexpectedEnd {
+                position: self.position(),
+                wanted: bits,
+                available: self.remaining(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn bit_at(&self, index: usize) -> bool {
+        self.bytes[index / 8] & (0x80 >> (index % 8)) != 0
+    }
+
+    pub fn read_bit(&mut self) -> Result<bool, BitError> {
+        self.check(1)?;
+        let bit = self.bit_at(self.pos);
+        self.pos += 1;
+        Ok(bit)
+    }
+
+    /// Returns the next `bits` bits as an unsigned number without consuming them
+    pub fn peek<T: TryFrom<u128>>(&self, bits: u32) -> Result<T, BitError> {
+        if bits > 128 {
+            return Err(BitError::TooWide(bits));
+        }
+        self.check(bits as usize)?;
+        let value = (self.pos..self.pos + bits as usize)
+            .fold(0u128, |acc, i| acc << 1 | self.bit_at(i) as u128);
+        T::try_from(value).map_err(|_| BitError::Overflow {
+            value,
+            ty: std::any::type_name::<T>(),
+        })
+    }
+
+    /// Reads the next `bits` bits as an unsigned number, failing if it doesn't fit in `T`.
+    /// Nothing is consumed on failure
+    pub fn read<T: TryFrom<u128>>(&mut self, bits: u32) -> Result<T, BitError> {
+        let value = self.peek(bits)?;
+        self.pos += bits as usize;
+        Ok(value)
+    }
+
+    pub fn skip(&mut self, bits: usize) -> Result<(), BitError> {
+        self.check(bits)?;
+        self.pos += bits;
+        Ok(())
+    }
+
+    /// Skips to the next multiple of `bits` from the start of this reader, returning the number
+    /// of bits skipped. Fails without skipping if that's past the end
+    ///
+    /// # Panics
+    /// This function panics if `bits` is zero
+    #[track_caller]
+    pub fn align(&mut self, bits: usize) -> Result<usize, BitError> {
+        assert!(bits != 0, "can't align to a multiple of 0 bits");
+        let skipped = (bits - self.position() % bits) % bits;
+        self.skip(skipped)?;
+        Ok(skipped)
+    }
+
+    /// Returns true if every remaining bit is zero, like the padding after the last packet
+    pub fn rest_is_zero(&self) -> bool {
+        (self.pos..self.end).all(|i| !self.bit_at(i))
+    }
+
+    /// Splits off the next `bits` bits as their own reader, for a nested value whose length is
+    /// known ahead of time. This reader continues after them
+    pub fn sub_reader(&mut self, bits: usize) -> Result<BitReader<'_>, BitError> {
+        self.check(bits)?;
+        let start = self.pos;
+        self.pos += bits;
+        Ok(BitReader {
+            bytes: Cow::Borrowed(&self.bytes),
+            start,
+            pos: start,
+            end: start + bits,
+        })
+    }
+}
+
+impl BitReader<'static> {
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self {
+            end: bytes.len() * 8,
+            bytes: Cow::Owned(bytes),
+            start: 0,
+            pos: 0,
+        }
+    }
+
+    /// Reads the bits of the hex digits in `text`, ignoring surrounding whitespace
+    pub fn from_hex(text: &str) -> Result<Self, BitError> {
+        Self::from_digits(text.trim(), 16)
+    }
+
+    /// Reads a string of `0`s and `1`s, ignoring surrounding whitespace
+    pub fn from_binary(text: &str) -> Result<Self, BitError> {
+        Self::from_digits(text.trim(), 2)
+    }
+
+    fn from_digits(text: &str, radix: u32) -> Result<Self, BitError> {
+        let width = radix.trailing_zeros() as usize;
+        let mut bytes = vec![0; (text.len() * width).div_ceil(8)];
+        for (index, c) in text.chars().enumerate() {
+            let digit = c
+                .to_digit(radix)
+                .ok_or(BitError::InvalidDigit { index, found: c })?;
+            for bit in 0..width {
+                if digit & (1 << (width - 1 - bit)) != 0 {
+                    let i = index * width + bit;
+                    bytes[i / 8] |= 0x80 >> (i % 8);
+                }
+            }
+        }
+        let mut reader = Self::from_bytes(bytes);
+        reader.end = text.len() * width;
+        Ok(reader)
+    }
+}
+
+/// A fixed number of bits which can be shifted and combined like an integer of any width.
+///
+/// Bit `i` is the `i`th least significant bit, so shifting left moves bits to higher indices.
+/// This makes rows of a grid easy to move around all at once, like the blizzards in 2022 day 24
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    /// Creates a set of `len` bits which are all unset
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// Creates a set of `len` bits which are all set
+    pub fn full(len: usize) -> Self {
+        let mut set = Self {
+            words: vec![u64::MAX; len.div_ceil(64)],
+            len,
+        };
+        set.clear_unused();
+        set
+    }
+
+    /// Creates a set of `len` bits with the bits at `indices` set
+    pub fn from_indices(len: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut set = Self::new(len);
+        for i in indices {
+            set.insert(i);
+        }
+        set
+    }
+
+    /// Unsets the bits past `len` in the last word, which operations like `!` would set
+    fn clear_unused(&mut self) {
+        if !self.len.is_multiple_of(64) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (self.len % 64)) - 1;
+            }
+        }
+    }
+
+    /// Returns the number of bits in this set, including unset bits
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no bits are set
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Returns the number of set bits
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[track_caller]
+    pub fn contains(&self, i: usize) -> bool {
+        assert!(i < self.len, "bit {} out of range for {} bits", i, self.len);
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Sets bit `i`, returning true if it wasn't already set
+    #[track_caller]
+    pub fn insert(&mut self, i: usize) -> bool {
+        let was_set = self.contains(i);
+        self.words[i / 64] |= 1 << (i % 64);
+        !was_set
+    }
+
+    /// Unsets bit `i`, returning true if it was set
+    #[track_caller]
+    pub fn remove(&mut self, i: usize) -> bool {
+        let was_set = self.contains(i);
+        self.words[i / 64] &= !(1 << (i % 64));
+        was_set
+    }
+
+    #[track_caller]
+    pub fn set(&mut self, i: usize, value: bool) {
+        if value {
+            self.insert(i);
+        } else {
+            self.remove(i);
+        }
+    }
+
+    /// Returns the indices of the set bits in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(w, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(w * 64 + bit)
+            })
+        })
+    }
+
+    /// Moves every bit `n` places towards the end, wrapping bits past the end around to the start
+    pub fn rotate_left(&self, n: usize) -> Self {
+        if self.len == 0 {
+            return self.clone();
+        }
+        let n = n % self.len;
+        &(self << n) | &(self >> (self.len - n))
+    }
+
+    /// Moves every bit `n` places towards the start, wrapping bits before the start around to the
+    /// end
+    pub fn rotate_right(&self, n: usize) -> Self {
+        if self.len == 0 {
+            return self.clone();
+        }
+        self.rotate_left(self.len - n % self.len)
+    }
+
+    #[track_caller]
+    fn zip_with(&self, other: &BitSet, f: impl Fn(u64, u64) -> u64) -> BitSet {
+        assert_eq!(self.len, other.len, "bit sets have different lengths");
+        BitSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            len: self.len,
+        }
+    }
+}
+
+impl fmt::Debug for BitSet {
+    /// Prints the bits from index 0 to the end, so it reads like a row of a grid
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits: String = (0..self.len)
+            .map(|i| if self.contains(i) { '1' } else { '0' })
+            .collect();
+        write!(f, "BitSet({})", bits)
+    }
+}
+
+impl BitOr for &BitSet {
+    type Output = BitSet;
+
+    fn bitor(self, rhs: &BitSet) -> BitSet {
+        self.zip_with(rhs, |a, b| a | b)
+    }
+}
+
+impl BitAnd for &BitSet {
+    type Output = BitSet;
+
+    fn bitand(self, rhs: &BitSet) -> BitSet {
+        self.zip_with(rhs, |a, b| a & b)
+    }
+}
+
+impl BitXor for &BitSet {
+    type Output = BitSet;
+
+    fn bitxor(self, rhs: &BitSet) -> BitSet {
+        self.zip_with(rhs, |a, b| a ^ b)
+    }
+}
+
+impl BitOrAssign<&BitSet> for BitSet {
+    fn bitor_assign(&mut self, rhs: &BitSet) {
+        *self = &*self | rhs;
+    }
+}
+
+impl BitAndAssign<&BitSet> for BitSet {
+    fn bitand_assign(&mut self, rhs: &BitSet) {
+        *self = &*self & rhs;
+    }
+}
+
+impl Not for &BitSet {
+    type Output = BitSet;
+
+    fn not(self) -> BitSet {
+        let mut set = BitSet {
+            words: self.words.iter().map(|w| !w).collect(),
+            len: self.len,
+        };
+        set.clear_unused();
+        set
+    }
+}
+
+impl Shl<usize> for &BitSet {
+    type Output = BitSet;
+
+    /// Moves every bit `n` places towards the end, dropping bits shifted past it
+    fn shl(self, n: usize) -> BitSet {
+        let mut set = BitSet::new(self.len);
+        let (words, bits) = (n / 64, n % 64);
+        for i in (words..set.words.len()).rev() {
+            let mut word = self.words[i - words] << bits;
+            if bits != 0 && i > words {
+                word |= self.words[i - words - 1] >> (64 - bits);
+            }
+            set.words[i] = word;
+        }
+        set.clear_unused();
+        set
+    }
+}
+
+impl Shr<usize> for &BitSet {
+    type Output = BitSet;
+
+    /// Moves every bit `n` places towards the start, dropping bits shifted before it
+    fn shr(self, n: usize) -> BitSet {
+        let mut set = BitSet::new(self.len);
+        let (words, bits) = (n / 64, n % 64);
+        for i in 0..set.words.len().saturating_sub(words) {
+            let mut word = self.words[i + words] >> bits;
+            if bits != 0 && i + words + 1 < self.words.len() {
+                word |= self.words[i + words + 1] << (64 - bits);
+            }
+            set.words[i] = word;
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reader() {
+        let mut reader = BitReader::from_hex("38006F45291200").unwrap();
+        assert_eq!(reader.len(), 56);
+        assert_eq!(reader.read::<u8>(3), Ok(1));
+        assert_eq!(reader.read::<u8>(3), Ok(6));
+        assert_eq!(reader.read_bit(), Ok(false));
+        let length: usize = reader.read(15).unwrap();
+        assert_eq!(length, 27);
+        {
+            let mut packets = reader.sub_reader(length).unwrap();
+            assert_eq!(packets.read::<u16>(11), Ok(0b11010001010));
+            assert_eq!(packets.position(), 11);
+            assert_eq!(packets.remaining(), 16);
+            packets.skip(16).unwrap();
+            assert_eq!(
+                packets.read_bit(),
+                Err(BitError::UnexpectedEnd {
+                    position: 27,
+                    wanted: 1,
+                    available: 0
+                })
+            );
+        }
+        assert_eq!(reader.position(), 49);
+        assert_eq!(reader.align(4), Ok(3));
+        assert_eq!(reader.align(4), Ok(0));
+        assert!(reader.rest_is_zero());
+
+        let mut reader = BitReader::from_binary("1011 ").unwrap();
+        assert_eq!(reader.len(), 4);
+        assert_eq!(reader.peek::<u8>(4), Ok(11));
+        assert_eq!(
+            reader.read::<u8>(5).unwrap_err().to_string(),
+            "wanted 5 bits at bit 0, but only 4 are left"
+        );
+        assert_eq!(reader.read::<u8>(2), Ok(2));
+        assert_eq!(
+            reader.align(8),
+            Err(BitError::UnexpectedEnd {
+                position: 2,
+                wanted: 6,
+                available: 2
+            })
+        );
+    }
+
+    #[test]
+    fn reader_errors() {
+        assert_eq!(
+            BitReader::from_hex("1G"),
+            Err(BitError::InvalidDigit {
+                index: 1,
+                found: 'G'
+            })
+        );
+        let mut reader = BitReader::new(&[0xFF, 0xFF, 0xFF]);
+        assert_eq!(
+            reader.read::<u8>(9),
+            Err(BitError::Overflow {
+                value: 511,
+                ty: "u8"
+            })
+        );
+        assert_eq!(reader.read::<u16>(9), Ok(511));
+        assert_eq!(reader.read::<u8>(200), Err(BitError::TooWide(200)));
+    }
+
+    #[test]
+    fn bit_set() {
+        let mut set = BitSet::from_indices(70, [0, 3, 63, 64, 69]);
+        assert_eq!(set.count_ones(), 5);
+        assert!(set.contains(64));
+        assert!(!set.insert(3));
+        assert!(set.remove(3));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 63, 64, 69]);
+
+        assert_eq!((&set << 1).iter().collect::<Vec<_>>(), vec![1, 64, 65]);
+        assert_eq!((&set >> 64).iter().collect::<Vec<_>>(), vec![0, 5]);
+        assert_eq!(
+            set.rotate_left(1).iter().collect::<Vec<_>>(),
+            vec![0, 1, 64, 65]
+        );
+        assert_eq!(
+            set.rotate_right(6).iter().collect::<Vec<_>>(),
+            vec![57, 58, 63, 64]
+        );
+        assert_eq!(set.rotate_right(76), set.rotate_right(6));
+
+        let inverse = !&set;
+        assert_eq!(inverse.count_ones(), 66);
+        assert!((&inverse & &set).is_empty());
+        assert_eq!(&inverse | &set, BitSet::full(70));
+        assert_eq!((&inverse ^ &BitSet::full(70)), set);
+
+        let row = BitSet::from_indices(5, [0, 2]);
+        assert_eq!(format!("{:?}", row), "BitSet(10100)");
+        assert_eq!(format!("{:?}", row.rotate_right(1)), "BitSet(01001)");
+    }
+
+    #[test]
+    #[should_panic(expected = "can't align to a multiple of 0 bits")]
+    fn align_zero() {
+        let mut reader = BitReader::from_hex("FF").unwrap();
+        let _ = reader.align(0);
+    }
+}
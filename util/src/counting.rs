@@ -0,0 +1,425 @@
+//! Counting things in buckets instead of simulating them one by one, like the lanternfish from
+//! 2021 day 6 or the polymer pairs from 2021 day 14.
+
+use std::collections::{hash_map, HashMap};
+use std::hash::Hash;
+
+/// A multiset that stores how many times each key was added.
+///
+/// ```
+/// # use util::counting::Counter;
+/// let counter: Counter<char> = "NNCB".chars().collect();
+/// assert_eq!(counter.get(&'N'), 2);
+/// assert_eq!(counter.most_common(), Some((&'N', 2)));
+/// assert_eq!(counter.total(), 4);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counter<K: Hash + Eq> {
+    counts: HashMap<K, u64>,
+}
+
+impl<K: Hash + Eq> Default for Counter<K> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `count` copies of `key`
+    pub fn add(&mut self, key: K, count: u64) {
+        if count != 0 {
+            *self.counts.entry(key).or_default() += count;
+        }
+    }
+
+    /// Returns how many copies of `key` there are
+    pub fn get(&self, key: &K) -> u64 {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// Removes every copy of `key`, returning how many there were
+    pub fn remove(&mut self, key: &K) -> u64 {
+        self.counts.remove(key).unwrap_or(0)
+    }
+
+    /// Returns the number of distinct keys
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the sum of all counts
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Returns each key with its count, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u64)> {
+        self.counts.iter().map(|(k, &c)| (k, c))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.counts.keys()
+    }
+
+    /// Returns the key with the highest count. Ties are broken arbitrarily
+    pub fn most_common(&self) -> Option<(&K, u64)> {
+        self.iter().max_by_key(|&(_, c)| c)
+    }
+
+    /// Returns the key with the lowest count. Ties are broken arbitrarily
+    pub fn least_common(&self) -> Option<(&K, u64)> {
+        self.iter().min_by_key(|&(_, c)| c)
+    }
+
+    /// Returns every key and count, from most to least common
+    pub fn sorted(&self) -> Vec<(&K, u64)> {
+        let mut sorted: Vec<_> = self.iter().collect();
+        sorted.sort_by_key(|&(_, c)| std::cmp::Reverse(c));
+        sorted
+    }
+
+    /// Multiplies every count by `factor`
+    ///
+    /// # Panics
+    /// This function panics if a count no longer fits in a `u64`
+    #[track_caller]
+    pub fn scale(&mut self, factor: u64) {
+        if factor == 0 {
+            self.counts.clear();
+        }
+        for count in self.counts.values_mut() {
+            *count = count.checked_mul(factor).expect("count overflowed a u64");
+        }
+    }
+
+    /// Returns the counts keyed by `f` of each key, adding together keys that map to the same
+    /// thing
+    pub fn map_keys<K2: Hash + Eq>(&self, mut f: impl FnMut(&K) -> K2) -> Counter<K2> {
+        let mut mapped = Counter::new();
+        for (key, count) in self.iter() {
+            mapped.add(f(key), count);
+        }
+        mapped
+    }
+
+    /// Moves every bucket one step forward. Each copy of a key turns into one copy of every key
+    /// returned by `next`, which may return the same key more than once or nothing at all
+    pub fn step<I: IntoIterator<Item = K>>(&self, mut next: impl FnMut(&K) -> I) -> Counter<K> {
+        let mut stepped = Counter::new();
+        for (key, count) in self.iter() {
+            for successor in next(key) {
+                stepped.add(successor, count);
+            }
+        }
+        stepped
+    }
+
+    /// Applies [`Counter::step`] `steps` times
+    pub fn simulate<I: IntoIterator<Item = K>>(
+        self,
+        steps: usize,
+        mut next: impl FnMut(&K) -> I,
+    ) -> Counter<K> {
+        (0..steps).fold(self, |counter, _| counter.step(&mut next))
+    }
+}
+
+impl<K: Hash + Eq + Clone> Counter<K> {
+    /// Adds every count from `other` to this counter
+    pub fn merge(&mut self, other: &Counter<K>) {
+        for (key, count) in other.iter() {
+            self.add(key.clone(), count);
+        }
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Hash + Eq> Extend<K> for Counter<K> {
+    fn extend<T: IntoIterator<Item = K>>(&mut self, iter: T) {
+        for key in iter {
+            self.add(key, 1);
+        }
+    }
+}
+
+impl<K: Hash + Eq> Extend<(K, u64)> for Counter<K> {
+    fn extend<T: IntoIterator<Item = (K, u64)>>(&mut self, iter: T) {
+        for (key, count) in iter {
+            self.add(key, count);
+        }
+    }
+}
+
+impl<K: Hash + Eq> IntoIterator for Counter<K> {
+    type Item = (K, u64);
+    type IntoIter = hash_map::IntoIter<K, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+type SquareMatrix = Vec<Vec<u64>>;
+
+fn identity(n: usize) -> SquareMatrix {
+    (0..n)
+        .map(|i| (0..n).map(|j| (i == j) as u64).collect())
+        .collect()
+}
+
+#[track_caller]
+fn multiply(a: &SquareMatrix, b: &SquareMatrix) -> SquareMatrix {
+    let n = a.len();
+    let mut product = vec![vec![0; n]; n];
+    for i in 0..n {
+        for k in 0..n {
+            if a[i][k] == 0 {
+                continue;
+            }
+            for j in 0..n {
+                product[i][j] = a[i][k]
+                    .checked_mul(b[k][j])
+                    .and_then(|x| x.checked_add(product[i][j]))
+                    .expect("count overflowed a u64");
+            }
+        }
+    }
+    product
+}
+
+#[track_caller]
+fn power(mut base: SquareMatrix, mut exp: u64) -> SquareMatrix {
+    let mut result = identity(base.len());
+    while exp != 0 {
+        if exp & 1 == 1 {
+            result = multiply(&result, &base);
+        }
+        exp >>= 1;
+        if exp != 0 {
+            base = multiply(&base, &base);
+        }
+    }
+    result
+}
+
+/// A step of [`Counter::step`] over a fixed set of keys, stored as a matrix so that it can be
+/// repeated a huge number of times in `O(keys³ log steps)`.
+///
+/// ```
+/// # use util::counting::{Counter, Transition};
+/// // rabbits: young pairs grow up, and each adult pair has a young pair every month
+/// let month = Transition::new(["young", "adult"], |&k| match k {
+///     "young" => vec!["adult"],
+///     _ => vec!["adult", "young"],
+/// });
+/// let start: Counter<&str> = ["young"].into_iter().collect();
+/// assert_eq!(month.pow(11).apply(&start).total(), 144);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transition<K: Hash + Eq> {
+    keys: Vec<K>,
+    index: HashMap<K, usize>,
+    /// `matrix[to][from]` is how many copies of `to` each copy of `from` turns into
+    matrix: SquareMatrix,
+}
+
+impl<K: Hash + Eq + Clone> Transition<K> {
+    /// Builds the step where each copy of a key turns into one copy of every key returned by
+    /// `next`.
+    ///
+    /// # Panics
+    /// This function panics if `next` returns a key that isn't in `keys`
+    #[track_caller]
+    pub fn new<I: IntoIterator<Item = K>>(
+        keys: impl IntoIterator<Item = K>,
+        mut next: impl FnMut(&K) -> I,
+    ) -> Self {
+        let mut keys: Vec<K> = keys.into_iter().collect();
+        let mut index = HashMap::new();
+        keys.retain(|key| {
+            let next_index = index.len();
+            match index.entry(key.clone()) {
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert(next_index);
+                    true
+                }
+                hash_map::Entry::Occupied(_) => false,
+            }
+        });
+        let mut matrix = vec![vec![0; keys.len()]; keys.len()];
+        for (from, key) in keys.iter().enumerate() {
+            for to in next(key) {
+                let to = *index.get(&to).expect("transition leads to an unknown key");
+                matrix[to][from] += 1;
+            }
+        }
+        Self {
+            keys,
+            index,
+            matrix,
+        }
+    }
+
+    /// Returns the keys this transition is defined on, without duplicates
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Returns this transition repeated `steps` times
+    ///
+    /// # Panics
+    /// This function panics if any count could overflow a `u64`
+    #[track_caller]
+    pub fn pow(&self, steps: u64) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            index: self.index.clone(),
+            matrix: power(self.matrix.clone(), steps),
+        }
+    }
+
+    /// Moves every bucket of `counts` forward by this transition
+    ///
+    /// # Panics
+    /// This function panics if `counts` contains a key that isn't part of this transition, or if
+    /// a count overflows a `u64`
+    #[track_caller]
+    pub fn apply(&self, counts: &Counter<K>) -> Counter<K> {
+        let mut before = vec![0; self.keys.len()];
+        for (key, count) in counts.iter() {
+            let i = *self
+                .index
+                .get(key)
+                .expect("key isn't part of the transition");
+            before[i] = count;
+        }
+        let mut after = Counter::new();
+        for (to, row) in self.matrix.iter().enumerate() {
+            let count = row
+                .iter()
+                .zip(&before)
+                .try_fold(0u64, |sum, (&a, &b)| sum.checked_add(a.checked_mul(b)?))
+                .expect("count overflowed a u64");
+            after.add(self.keys[to].clone(), count);
+        }
+        after
+    }
+}
+
+/// Returns term `n` of the sequence where the first terms are `initial` and each later term is
+/// `coefficients[0]` times the previous term plus `coefficients[1]` times the one before it and
+/// so on.
+///
+/// ```
+/// # use util::counting::linear_recurrence;
+/// let fibonacci = |n| linear_recurrence(&[1, 1], &[0, 1], n);
+/// assert_eq!(fibonacci(10), 55);
+/// assert_eq!(fibonacci(90), 2880067194370816120);
+/// ```
+///
+/// # Panics
+/// This function panics if `coefficients` and `initial` have different lengths or are empty,
+/// or if a term overflows a `u64`
+#[track_caller]
+pub fn linear_recurrence(coefficients: &[u64], initial: &[u64], n: u64) -> u64 {
+    let k = coefficients.len();
+    assert!(k != 0, "a recurrence needs at least one coefficient");
+    assert_eq!(k, initial.len(), "need one initial term per coefficient");
+    if n < k as u64 {
+        return initial[n as usize];
+    }
+    // the companion matrix maps the terms (a[i + k - 1], ..., a[i]) to (a[i + k], ..., a[i + 1])
+    let mut companion = vec![vec![0; k]; k];
+    companion[0] = coefficients.to_vec();
+    for i in 1..k {
+        companion[i][i - 1] = 1;
+    }
+    let m = power(companion, n - (k as u64 - 1));
+    m[0].iter()
+        .zip(initial.iter().rev())
+        .try_fold(0u64, |sum, (&a, &b)| sum.checked_add(a.checked_mul(b)?))
+        .expect("term overflowed a u64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lanternfish(age: &u8) -> Vec<u8> {
+        match age {
+            0 => vec![6, 8],
+            n => vec![n - 1],
+        }
+    }
+
+    #[test]
+    fn counter() {
+        let mut counter: Counter<char> = "NBCCNBBBCBHCB".chars().collect();
+        assert_eq!(counter.get(&'B'), 6);
+        assert_eq!(counter.get(&'X'), 0);
+        assert_eq!(counter.least_common(), Some((&'H', 1)));
+        assert_eq!(counter.sorted()[0], (&'B', 6));
+
+        counter.scale(2);
+        let other: Counter<char> = "HX".chars().collect();
+        counter.merge(&other);
+        assert_eq!(counter.get(&'H'), 3);
+        assert_eq!(counter.total(), 28);
+        assert_eq!(counter.remove(&'X'), 1);
+        assert_eq!(counter.len(), 4);
+
+        let vowels = counter.map_keys(|c| "AEIOU".contains(*c));
+        assert_eq!(vowels.get(&false), 27);
+        assert!(Counter::<u8>::new().most_common().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "count overflowed a u64")]
+    fn scale_overflow() {
+        let mut counter: Counter<char> = "aa".chars().collect();
+        counter.scale(u64::MAX);
+    }
+
+    #[test]
+    fn simulate() {
+        let fish: Counter<u8> = [3, 4, 3, 1, 2].into_iter().collect();
+        assert_eq!(fish.clone().simulate(18, lanternfish).total(), 26);
+        assert_eq!(fish.clone().simulate(80, lanternfish).total(), 5934);
+
+        let day = Transition::new(0..=8, lanternfish);
+        assert_eq!(
+            day.pow(80).apply(&fish),
+            fish.clone().simulate(80, lanternfish)
+        );
+        assert_eq!(day.pow(256).apply(&fish).total(), 26984457539);
+        assert_eq!(day.pow(0).apply(&fish), fish);
+        let repeated = Transition::new([1, 0, 1, 2, 0], |&k: &u8| [k]);
+        assert_eq!(repeated.keys(), [1, 0, 2]);
+    }
+
+    #[test]
+    fn recurrence() {
+        assert_eq!(linear_recurrence(&[2], &[3], 10), 3 * 1024);
+        assert_eq!(linear_recurrence(&[0, 1], &[4, 5], 0), 4);
+        assert_eq!(linear_recurrence(&[0, 1], &[4, 5], 7), 5);
+        // tribonacci
+        assert_eq!(linear_recurrence(&[1, 1, 1], &[0, 0, 1], 10), 81);
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Describes the shape of a sequence that eventually repeats: `prefix_len` states that never
+/// repeat, followed by a loop of `period` states that repeats forever
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CycleInfo {
+    /// The index of the first state that is part of the loop
+    pub prefix_len: usize,
+    /// The number of states in the loop
+    pub period: usize,
+}
+
+impl CycleInfo {
+    /// Returns the smallest step that has the same state as step `n`.
+    /// The result is always less than `prefix_len + period`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix_len {
+            n
+        } else {
+            self.prefix_len + (n - self.prefix_len) % self.period
+        }
+    }
+
+    /// Returns how many times the loop has been fully completed by step `n`
+    pub fn repeats_before(&self, n: usize) -> usize {
+        n.saturating_sub(self.prefix_len) / self.period
+    }
+}
+
+/// The result of simulating a sequence until it repeats.
+///
+/// `states[i]` is the state after `i` steps, for every step before the first repeat
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<S> {
+    pub info: CycleInfo,
+    pub states: Vec<S>,
+    /// The state after `prefix_len + period` steps, which was found to match
+    /// `states[prefix_len]`
+    pub repeated: S,
+}
+
+impl<S> Cycle<S> {
+    /// Returns the state after `n` steps without simulating them
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.info.reduce(n)]
+    }
+
+    /// Computes a value derived from the state after `n` steps, assuming that `value` grows by the
+    /// same amount every time the loop is completed.
+    ///
+    /// This is the usual trick for "how tall is the tower after 10^12 rocks" puzzles, where the
+    /// state is keyed on everything except the accumulated height
+    pub fn extrapolate(&self, n: usize, value: impl Fn(&S) -> i64) -> i64 {
+        let base = value(self.state_at(n));
+        if n < self.info.prefix_len {
+            return base;
+        }
+        let per_loop = value(&self.repeated) - value(&self.states[self.info.prefix_len]);
+        base + per_loop * self.info.repeats_before(n) as i64
+    }
+}
+
+/// Runs `step` starting from `initial` until a state repeats, remembering every state along the
+/// way
+pub fn find_cycle<S>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle<S>
+where
+    S: Hash + Eq + Clone,
+{
+    find_cycle_by_key(initial, &mut step, |s| s.clone())
+}
+
+/// Like [`find_cycle`], but two states are considered the same if `key` returns the same value
+/// for both. Useful when the state carries a counter that grows forever
+pub fn find_cycle_by_key<S, K>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle<S>
+where
+    K: Hash + Eq,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    loop {
+        let index = states.len();
+        if let Some(&prefix_len) = seen.get(&key(&state)) {
+            return Cycle {
+                info: CycleInfo {
+                    prefix_len,
+                    period: index - prefix_len,
+                },
+                states,
+                repeated: state,
+            };
+        }
+        seen.insert(key(&state), index);
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Finds the cycle in the sequence produced by repeatedly calling `step` on `initial` using
+/// Brent's algorithm, which only keeps two states in memory at a time
+pub fn brent<S>(initial: S, mut step: impl FnMut(&S) -> S) -> CycleInfo
+where
+    S: PartialEq + Clone,
+{
+    // find the period by moving the tortoise to the hare at each power of two
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // start the hare `period` steps ahead, then they meet at the start of the loop
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut prefix_len = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_len += 1;
+    }
+
+    CycleInfo { prefix_len, period }
+}
+
+/// Returns the state after `n` steps, skipping over full trips around the loop. Uses [`brent`]
+/// so it works even when states are too large to store
+pub fn nth_state<S>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S
+where
+    S: PartialEq + Clone,
+{
+    let info = brent(initial.clone(), &mut step);
+    let mut state = initial;
+    for _ in 0..info.reduce(n) {
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn brent_matches_hash_map() {
+        for start in 0..50 {
+            let cycle = find_cycle(start, next);
+            assert_eq!(brent(start, next), cycle.info);
+            for n in [0, 1, 10, 100, 12345] {
+                let mut state = start;
+                for _ in 0..n {
+                    state = next(&state);
+                }
+                assert_eq!(*cycle.state_at(n), state);
+                assert_eq!(nth_state(start, next, n), state);
+            }
+        }
+    }
+
+    #[test]
+    fn steady_state() {
+        let cycle = find_cycle(10u32, |&x| x.saturating_sub(3));
+        assert_eq!(
+            cycle.info,
+            CycleInfo {
+                prefix_len: 4,
+                period: 1
+            }
+        );
+        assert_eq!(*cycle.state_at(1_000_000), 0);
+    }
+
+    #[test]
+    fn extrapolate_by_key() {
+        // (position, height) with a loop of 3 positions after a prefix of 2, height grows 9 per loop
+        let step = |&(pos, height): &(i64, i64)| {
+            if pos < 2 {
+                (pos + 1, height + 100)
+            } else {
+                (2 + (pos - 1) % 3, height + pos)
+            }
+        };
+        let cycle = find_cycle_by_key((0, 0), step, |s| s.0);
+        assert_eq!(cycle.info.prefix_len, 2);
+        assert_eq!(cycle.info.period, 3);
+
+        let mut state = (0, 0);
+        for n in 0..100 {
+            assert_eq!(cycle.extrapolate(n, |s| s.1), state.1, "step {n}");
+            state = step(&state);
+        }
+    }
+}
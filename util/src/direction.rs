@@ -0,0 +1,298 @@
+use crate::{Direction, SignedPoint};
+use std::str::FromStr;
+
+/// One of the eight directions around a cell, including diagonals.
+///
+/// Like [`Direction`], up is towards row zero
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Returns all eight directions in clockwise order starting from [`Direction8::Up`]
+    pub fn all() -> [Direction8; 8] {
+        use Direction8::*;
+        [Up, UpRight, Right, DownRight, Down, DownLeft, Left, UpLeft]
+    }
+
+    /// Returns the four diagonal directions in clockwise order
+    pub fn diagonals() -> [Direction8; 4] {
+        use Direction8::*;
+        [UpRight, DownRight, DownLeft, UpLeft]
+    }
+
+    pub fn to_unit_offsets(self) -> (isize, isize) {
+        match self {
+            Direction8::Up => (-1, 0),
+            Direction8::UpRight => (-1, 1),
+            Direction8::Right => (0, 1),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (1, 0),
+            Direction8::DownLeft => (1, -1),
+            Direction8::Left => (0, -1),
+            Direction8::UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        Direction::try_from(self).is_err()
+    }
+
+    /// Rotates clockwise by `eighth_turns` (45 degrees each).
+    /// Negative values rotate counter-clockwise
+    pub fn rotate(self, eighth_turns: isize) -> Direction8 {
+        let index = Self::all().iter().position(|&d| d == self).unwrap();
+        Self::all()[(index as isize + eighth_turns).rem_euclid(8) as usize]
+    }
+
+    /// Rotates clockwise by `degrees`. Negative values rotate counter-clockwise.
+    /// Returns `None` if `degrees` is not a multiple of 45
+    pub fn rotate_degrees(self, degrees: isize) -> Option<Direction8> {
+        if degrees % 45 != 0 {
+            return None;
+        }
+        Some(self.rotate(degrees / 45))
+    }
+
+    /// Returns the direction 90 degrees clockwise from this one
+    pub fn turn_right(self) -> Direction8 {
+        self.rotate(2)
+    }
+
+    /// Returns the direction 90 degrees counter-clockwise from this one
+    pub fn turn_left(self) -> Direction8 {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Direction8 {
+        self.rotate(4)
+    }
+
+    /// Returns the direction that points exactly towards `delta`, if `delta` is non-zero and lies on
+    /// a row, column or 45 degree diagonal
+    pub fn try_from_delta(delta: SignedPoint) -> Option<Direction8> {
+        let aligned = delta.row == 0 || delta.col == 0 || delta.row.abs() == delta.col.abs();
+        if delta == SignedPoint::new(0, 0) || !aligned {
+            return None;
+        }
+        let unit = (delta.row.signum(), delta.col.signum());
+        Self::all()
+            .into_iter()
+            .find(|d| d.to_unit_offsets() == unit)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
+/// Fails for diagonal directions
+impl TryFrom<Direction8> for Direction {
+    type Error = ();
+
+    fn try_from(value: Direction8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Direction8::Up => Direction::Up,
+            Direction8::Down => Direction::Down,
+            Direction8::Left => Direction::Left,
+            Direction8::Right => Direction::Right,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// Parses compass directions (`N`, `NE`, `E`, ... `NW`), `U`/`D`/`L`/`R` (with two letter forms
+/// like `UL`), and the arrows `^`, `v`, `<`, `>`. Matching is case insensitive
+impl FromStr for Direction8 {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(dir) = s.parse::<Direction>() {
+            return Ok(dir.into());
+        }
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "NE" | "UR" | "RU" => Direction8::UpRight,
+            "SE" | "DR" | "RD" => Direction8::DownRight,
+            "SW" | "DL" | "LD" => Direction8::DownLeft,
+            "NW" | "UL" | "LU" => Direction8::UpLeft,
+            _ => return Err(()),
+        })
+    }
+}
+
+impl SignedPoint {
+    /// Moves this point one cell in `direction`, which may be diagonal
+    pub fn offset8(self, direction: Direction8) -> Self {
+        let (row, col) = direction.to_unit_offsets();
+        Self::new(self.row + row, self.col + col)
+    }
+
+    /// Returns the eight points surrounding this point, in the order of [`Direction8::all`]
+    pub fn neighbors8(self) -> [SignedPoint; 8] {
+        Direction8::all().map(|d| self.offset8(d))
+    }
+}
+
+/// Tracks a position and facing direction while following movement instructions, such as those
+/// in 2020 day 12.
+///
+/// Positions use the same coordinate system as [`SignedPoint`], so north is towards negative rows
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Heading {
+    pub position: SignedPoint,
+    pub facing: Direction,
+}
+
+impl Heading {
+    pub fn new(position: SignedPoint, facing: Direction) -> Self {
+        Self { position, facing }
+    }
+
+    /// Moves `count` cells in the direction currently being faced
+    pub fn forward(&mut self, count: isize) {
+        self.position = self.position.offset_by(self.facing, count);
+    }
+
+    /// Moves `count` cells in `direction` without changing the facing direction
+    pub fn shift(&mut self, direction: Direction, count: isize) {
+        self.position = self.position.offset_by(direction, count);
+    }
+
+    pub fn turn_left(&mut self) {
+        self.facing = self.facing.turn_left();
+    }
+
+    pub fn turn_right(&mut self) {
+        self.facing = self.facing.turn_right();
+    }
+
+    /// Turns clockwise by `degrees`, which must be a multiple of 90
+    ///
+    /// # Panics
+    /// This function panics if `degrees` is not a multiple of 90
+    #[track_caller]
+    pub fn rotate_degrees(&mut self, degrees: isize) {
+        self.facing = self
+            .facing
+            .rotate_degrees(degrees)
+            .unwrap_or_else(|| panic!("cannot rotate by {degrees} degrees"));
+    }
+
+    /// Applies a single instruction such as `F10`, `R90`, `L270` or `N3`.
+    ///
+    /// `F` moves forward, `L` and `R` turn by the given number of degrees, and the compass letters
+    /// `N`, `E`, `S`, `W` (see [`Direction::from_compass`]) or `U` and `D` shift the position
+    /// without turning
+    pub fn apply(&mut self, instruction: &str) -> Result<(), String> {
+        let instruction = instruction.trim();
+        let Some(action) = instruction.bytes().next() else {
+            return Err("empty instruction".to_owned());
+        };
+        // a multi-byte first character can't be an action, and can't be sliced off at byte 1
+        let value: isize = instruction
+            .get(1..)
+            .ok_or_else(|| format!("unknown action in `{instruction}`"))?
+            .parse()
+            .map_err(|e| format!("bad value in `{instruction}`: {e}"))?;
+
+        let not_a_multiple = || format!("`{instruction}` does not turn by a multiple of 90");
+        match action {
+            b'F' | b'f' => self.forward(value),
+            b'R' | b'r' => {
+                self.facing = self
+                    .facing
+                    .rotate_degrees(value)
+                    .ok_or_else(not_a_multiple)?
+            }
+            b'L' | b'l' => {
+                self.facing = self
+                    .facing
+                    .rotate_degrees(-value)
+                    .ok_or_else(not_a_multiple)?
+            }
+            _ => {
+                let direction = Direction::from_compass(action)
+                    .or_else(|| Direction::try_from(action).ok())
+                    .ok_or_else(|| format!("unknown action in `{instruction}`"))?;
+                self.shift(direction, value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the manhattan distance between the current position and the origin
+    pub fn manhattan_distance(&self) -> usize {
+        self.position.manhattan_distance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate8() {
+        for dir in Direction8::all() {
+            assert_eq!(dir.rotate(8), dir);
+            assert_eq!(dir.reverse().reverse(), dir);
+            assert_eq!(dir.rotate_degrees(-90), Some(dir.turn_left()));
+        }
+        assert_eq!(Direction8::Up.rotate(1), Direction8::UpRight);
+        assert_eq!(Direction8::Up.rotate(-1), Direction8::UpLeft);
+        assert!(Direction8::DownLeft.is_diagonal());
+        assert!(!Direction8::Left.is_diagonal());
+    }
+
+    #[test]
+    fn parse8() {
+        assert_eq!("ne".parse(), Ok(Direction8::UpRight));
+        assert_eq!("SW".parse(), Ok(Direction8::DownLeft));
+        assert_eq!("^".parse(), Ok(Direction8::Up));
+        assert_eq!("W".parse(), Ok(Direction8::Left));
+        assert!("NN".parse::<Direction8>().is_err());
+    }
+
+    #[test]
+    fn from_delta() {
+        let p = |row, col| SignedPoint::new(row, col);
+        assert_eq!(
+            Direction8::try_from_delta(p(-3, 3)),
+            Some(Direction8::UpRight)
+        );
+        assert_eq!(Direction8::try_from_delta(p(0, -2)), Some(Direction8::Left));
+        assert_eq!(Direction8::try_from_delta(p(1, 2)), None);
+        assert_eq!(Direction8::try_from_delta(p(0, 0)), None);
+    }
+
+    #[test]
+    fn heading_example() {
+        // example from 2020 day 12
+        let mut ship = Heading::new(SignedPoint::new(0, 0), Direction::Right);
+        for line in ["F10", "N3", "F7", "R90", "F11"] {
+            ship.apply(line).unwrap();
+        }
+        assert_eq!(ship.position, SignedPoint::new(8, 17));
+        assert_eq!(ship.facing, Direction::Down);
+        assert_eq!(ship.manhattan_distance(), 25);
+
+        assert!(ship.apply("R45").is_err());
+        assert!(ship.apply("X1").is_err());
+        assert!(ship.apply("é5").is_err());
+    }
+}
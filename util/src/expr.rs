@@ -0,0 +1,733 @@
+//! Arithmetic expressions with configurable operator precedence, like the homework from 2020
+//! day 18 or the monkey math from 2022 day 21.
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::math::{ext_gcd_wide, ModInt};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprError {
+    /// The parser found a token that can't appear at this point
+    UnexpectedToken {
+        index: usize,
+        found: String,
+    },
+    /// The input ended in the middle of an expression
+    UnexpectedEnd,
+    /// A number literal doesn't fit in an `i64`
+    LiteralTooLarge {
+        index: usize,
+    },
+    /// Evaluation needed a variable that has no value
+    UnknownVariable(String),
+    DivisionByZero,
+    /// Solving failed because the unknown is multiplied by itself or divided by
+    NotLinear,
+    /// The equation is true for every value of the unknown or for none of them
+    NoUniqueSolution,
+    /// The only solution is a fraction
+    NotInteger {
+        numerator: i128,
+        denominator: i128,
+    },
+    Overflow,
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprError::UnexpectedToken { index, found } => {
+                write!(f, "unexpected `{}` at index {}", found, index)
+            }
+            ExprError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            ExprError::LiteralTooLarge { index } => {
+                write!(f, "number at index {} doesn't fit in an i64", index)
+            }
+            ExprError::UnknownVariable(name) => write!(f, "no value for variable `{}`", name),
+            ExprError::DivisionByZero => write!(f, "division by zero"),
+            ExprError::NotLinear => write!(f, "equation is not linear in the unknown"),
+            ExprError::NoUniqueSolution => write!(f, "equation has no unique solution"),
+            ExprError::NotInteger {
+                numerator,
+                denominator,
+            } => write!(
+                f,
+                "solution {}/{} is not an integer",
+                numerator, denominator
+            ),
+            ExprError::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl BinOp {
+    const ALL: [BinOp; 4] = [BinOp::Add, BinOp::Sub, BinOp::Mul, BinOp::Div];
+
+    pub fn symbol(self) -> char {
+        match self {
+            BinOp::Add => '+',
+            BinOp::Sub => '-',
+            BinOp::Mul => '*',
+            BinOp::Div => '/',
+        }
+    }
+
+    pub fn from_symbol(c: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.symbol() == c)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Assoc {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a - b - c` is `a - (b - c)`
+    Right,
+}
+
+/// The precedence and associativity of each binary operator. Higher precedences bind tighter,
+/// and an operator with precedence 0 isn't allowed at all.
+///
+/// ```
+/// # use util::expr::Precedence;
+/// let expr = Precedence::addition_first().parse("2 * 3 + (4 * 5)").unwrap();
+/// assert_eq!(expr.to_string(), "(2 * (3 + (4 * 5)))");
+/// assert_eq!(expr.eval::<i64>(), Ok(46));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Precedence {
+    levels: [(u8, Assoc); 4],
+}
+
+impl Precedence {
+    /// Every operator has the same precedence and is left associative, so expressions are
+    /// evaluated left to right
+    pub fn same() -> Self {
+        Self {
+            levels: [(1, Assoc::Left); 4],
+        }
+    }
+
+    /// The usual rules: `*` and `/` before `+` and `-`, all left associative
+    pub fn standard() -> Self {
+        Self::same()
+            .with(BinOp::Mul, 2, Assoc::Left)
+            .with(BinOp::Div, 2, Assoc::Left)
+    }
+
+    /// `+` and `-` before `*` and `/`, all left associative
+    pub fn addition_first() -> Self {
+        Self::same()
+            .with(BinOp::Add, 2, Assoc::Left)
+            .with(BinOp::Sub, 2, Assoc::Left)
+    }
+
+    /// Sets the precedence and associativity of `op`
+    pub fn with(mut self, op: BinOp, precedence: u8, assoc: Assoc) -> Self {
+        self.levels[op as usize] = (precedence, assoc);
+        self
+    }
+
+    pub fn get(&self, op: BinOp) -> (u8, Assoc) {
+        self.levels[op as usize]
+    }
+
+    /// Parses `text` into an expression tree.
+    ///
+    /// Numbers are non-negative integer literals, variables are runs of letters, digits and `_`
+    /// starting with a letter or `_`, and a `-` in front of an operand negates it
+    pub fn parse(&self, text: &str) -> Result<Expr, ExprError> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            pos: 0,
+            precedence: self,
+        };
+        let expr = parser.expr(0)?;
+        match parser.tokens.get(parser.pos) {
+            Some((index, token)) => Err(ExprError::UnexpectedToken {
+                index: *index,
+                found: token.to_string(),
+            }),
+            None => Ok(expr),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Expr {
+    Num(i64),
+    Var(String),
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+/// A type that expressions can be evaluated in.
+///
+/// The `checked_*` methods return `None` when the result can't be represented, which
+/// evaluation reports as [`ExprError::Overflow`]. By default they use the operators and never
+/// fail, which suits floats. [`ModInt`] can't overflow either, but its division fails when the
+/// divisor has no inverse modulo `M`
+pub trait Number:
+    Clone
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// Converts a literal, or returns `None` if it doesn't fit in this type
+    fn from_literal(value: i64) -> Option<Self>;
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(self - rhs)
+    }
+
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Some(self * rhs)
+    }
+
+    /// Divides by a non-zero `rhs`
+    fn checked_div(self, rhs: Self) -> Option<Self> {
+        Some(self / rhs)
+    }
+}
+
+macro_rules! impl_number_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Number for $ty {
+                fn from_literal(value: i64) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_div(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_number_int!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+impl Number for f32 {
+    fn from_literal(value: i64) -> Option<Self> {
+        Some(value as f32)
+    }
+}
+
+impl Number for f64 {
+    fn from_literal(value: i64) -> Option<Self> {
+        Some(value as f64)
+    }
+}
+
+impl<const M: u64> Number for ModInt<M> {
+    fn from_literal(value: i64) -> Option<Self> {
+        Some(ModInt::new(value))
+    }
+
+    fn checked_div(self, rhs: Self) -> Option<Self> {
+        rhs.inv().map(|inv| self * inv)
+    }
+}
+
+impl Expr {
+    /// Evaluates an expression without variables
+    pub fn eval<T: Number>(&self) -> Result<T, ExprError> {
+        self.eval_with(|_| None)
+    }
+
+    /// Evaluates this expression, looking up variables with `vars`
+    pub fn eval_with<T: Number>(&self, vars: impl Fn(&str) -> Option<T>) -> Result<T, ExprError> {
+        self.eval_inner(&vars)
+    }
+
+    fn eval_inner<T: Number>(&self, vars: &impl Fn(&str) -> Option<T>) -> Result<T, ExprError> {
+        let zero = || T::from_literal(0).ok_or(ExprError::Overflow);
+        let result = match self {
+            Expr::Num(n) => T::from_literal(*n),
+            Expr::Var(name) => {
+                return vars(name).ok_or_else(|| ExprError::UnknownVariable(name.clone()))
+            }
+            Expr::Neg(inner) => zero()?.checked_sub(inner.eval_inner(vars)?),
+            Expr::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.eval_inner(vars)?, rhs.eval_inner(vars)?);
+                match op {
+                    BinOp::Add => lhs.checked_add(rhs),
+                    BinOp::Sub => lhs.checked_sub(rhs),
+                    BinOp::Mul => lhs.checked_mul(rhs),
+                    BinOp::Div if rhs == zero()? => return Err(ExprError::DivisionByZero),
+                    BinOp::Div => lhs.checked_div(rhs),
+                }
+            }
+        };
+        result.ok_or(ExprError::Overflow)
+    }
+
+    /// Calls `f` on the name of every variable in this expression, left to right
+    pub fn visit_variables<'a>(&'a self, f: &mut impl FnMut(&'a str)) {
+        match self {
+            Expr::Num(_) => {}
+            Expr::Var(name) => f(name),
+            Expr::Neg(inner) => inner.visit_variables(f),
+            Expr::Binary(_, lhs, rhs) => {
+                lhs.visit_variables(f);
+                rhs.visit_variables(f);
+            }
+        }
+    }
+
+    /// Finds the integer value of `unknown` that makes `self` equal to `other`, looking up every
+    /// other variable with `vars`.
+    ///
+    /// Division is exact while solving, so `x / 2 = 3` gives 6 even though integer evaluation
+    /// would also accept 7.
+    ///
+    /// ```
+    /// # use util::expr::Precedence;
+    /// let rules = Precedence::standard();
+    /// let lhs = rules.parse("4 + 2 * (humn - 3)").unwrap();
+    /// let rhs = rules.parse("pppw / 2").unwrap();
+    /// assert_eq!(lhs.solve(&rhs, "humn", |_| Some(300)), Ok(76));
+    /// ```
+    pub fn solve(
+        &self,
+        other: &Expr,
+        unknown: &str,
+        vars: impl Fn(&str) -> Option<i64>,
+    ) -> Result<i64, ExprError> {
+        let lhs = self.linear(unknown, &vars)?;
+        let rhs = other.linear(unknown, &vars)?;
+        // lhs - rhs = 0 is a * x + b = 0 after clearing the denominator
+        let diff = lhs.sub(rhs)?;
+        if diff.a == 0 {
+            return Err(ExprError::NoUniqueSolution);
+        }
+        let (numerator, denominator) =
+            reduce(diff.b.checked_neg().ok_or(ExprError::Overflow)?, diff.a);
+        if denominator != 1 {
+            return Err(ExprError::NotInteger {
+                numerator,
+                denominator,
+            });
+        }
+        i64::try_from(numerator).map_err(|_| ExprError::Overflow)
+    }
+
+    fn linear(
+        &self,
+        unknown: &str,
+        vars: &impl Fn(&str) -> Option<i64>,
+    ) -> Result<Linear, ExprError> {
+        match self {
+            Expr::Num(n) => Ok(Linear::constant(*n as i128)),
+            Expr::Var(name) if name == unknown => Ok(Linear { a: 1, b: 0, d: 1 }),
+            Expr::Var(name) => vars(name)
+                .map(|v| Linear::constant(v as i128))
+                .ok_or_else(|| ExprError::UnknownVariable(name.clone())),
+            Expr::Neg(inner) => Linear::constant(0).sub(inner.linear(unknown, vars)?),
+            Expr::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.linear(unknown, vars)?, rhs.linear(unknown, vars)?);
+                match op {
+                    BinOp::Add => lhs.add(rhs),
+                    BinOp::Sub => lhs.sub(rhs),
+                    BinOp::Mul => lhs.mul(rhs),
+                    BinOp::Div => lhs.div(rhs),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Writes the expression with every binary operation in parentheses
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Num(n) => write!(f, "{}", n),
+            Expr::Var(name) => write!(f, "{}", name),
+            Expr::Neg(inner) => write!(f, "-{}", inner),
+            Expr::Binary(op, lhs, rhs) => write!(f, "({} {} {})", lhs, op.symbol(), rhs),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Num(i64),
+    Ident(String),
+    Op(BinOp),
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Num(n) => write!(f, "{}", n),
+            Token::Ident(name) => write!(f, "{}", name),
+            Token::Op(op) => write!(f, "{}", op.symbol()),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+/// Splits `text` into tokens, each paired with its byte index
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(index, c)) = chars.peek() {
+        let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let token = if c.is_whitespace() {
+            chars.next();
+            continue;
+        } else if c.is_ascii_digit() || is_ident(c) {
+            let mut word = String::new();
+            while let Some((_, c)) = chars.next_if(|&(_, c)| is_ident(c)) {
+                word.push(c);
+            }
+            if c.is_ascii_digit() {
+                let n =
+                    word.parse()
+                        .map_err(|_| match word.bytes().all(|b| b.is_ascii_digit()) {
+                            true => ExprError::LiteralTooLarge { index },
+                            false => ExprError::UnexpectedToken {
+                                index,
+                                found: word.clone(),
+                            },
+                        })?;
+                Token::Num(n)
+            } else {
+                Token::Ident(word)
+            }
+        } else {
+            chars.next();
+            match c {
+                '(' => Token::Open,
+                ')' => Token::Close,
+                _ => Token::Op(BinOp::from_symbol(c).ok_or(ExprError::UnexpectedToken {
+                    index,
+                    found: c.to_string(),
+                })?),
+            }
+        };
+        tokens.push((index, token));
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    precedence: &'a Precedence,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Result<(usize, Token), ExprError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or(ExprError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Parses an expression whose binary operators all have at least `min_precedence`
+    fn expr(&mut self, min_precedence: u16) -> Result<Expr, ExprError> {
+        let mut lhs = self.operand()?;
+        while let Some((index, Token::Op(op))) = self.tokens.get(self.pos) {
+            let (precedence, assoc) = self.precedence.get(*op);
+            if precedence == 0 {
+                return Err(ExprError::UnexpectedToken {
+                    index: *index,
+                    found: op.symbol().to_string(),
+                });
+            }
+            let precedence = precedence as u16;
+            if precedence < min_precedence {
+                break;
+            }
+            let op = *op;
+            self.pos += 1;
+            let next_min = match assoc {
+                Assoc::Left => precedence + 1,
+                Assoc::Right => precedence,
+            };
+            let rhs = self.expr(next_min)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn operand(&mut self) -> Result<Expr, ExprError> {
+        let (index, token) = self.next()?;
+        match token {
+            Token::Num(n) => Ok(Expr::Num(n)),
+            Token::Ident(name) => Ok(Expr::Var(name)),
+            Token::Op(BinOp::Sub) => Ok(Expr::Neg(Box::new(self.operand()?))),
+            Token::Open => {
+                let inner = self.expr(0)?;
+                match self.next()? {
+                    (_, Token::Close) => Ok(inner),
+                    (index, token) => Err(ExprError::UnexpectedToken {
+                        index,
+                        found: token.to_string(),
+                    }),
+                }
+            }
+            token => Err(ExprError::UnexpectedToken {
+                index,
+                found: token.to_string(),
+            }),
+        }
+    }
+}
+
+/// Divides `n` and `d` by their gcd and makes `d` positive
+fn reduce(n: i128, d: i128) -> (i128, i128) {
+    let g = ext_gcd_wide(n, d).0;
+    let sign = d.signum();
+    (n / g * sign, d / g * sign)
+}
+
+/// The linear function `(a * x + b) / d`, kept in lowest terms with `d` positive
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Linear {
+    a: i128,
+    b: i128,
+    d: i128,
+}
+
+impl Linear {
+    fn constant(value: i128) -> Self {
+        Self {
+            a: 0,
+            b: value,
+            d: 1,
+        }
+    }
+
+    fn new(a: Option<i128>, b: Option<i128>, d: Option<i128>) -> Result<Self, ExprError> {
+        let (a, b, d) = (
+            a.ok_or(ExprError::Overflow)?,
+            b.ok_or(ExprError::Overflow)?,
+            d.ok_or(ExprError::Overflow)?,
+        );
+        let g = ext_gcd_wide(ext_gcd_wide(a, b).0, d).0;
+        let g = g * d.signum();
+        Ok(Self {
+            a: a / g,
+            b: b / g,
+            d: d / g,
+        })
+    }
+
+    fn add(self, rhs: Self) -> Result<Self, ExprError> {
+        let cross = |x: i128, y: i128| x.checked_mul(rhs.d)?.checked_add(y.checked_mul(self.d)?);
+        Self::new(
+            cross(self.a, rhs.a),
+            cross(self.b, rhs.b),
+            self.d.checked_mul(rhs.d),
+        )
+    }
+
+    fn sub(self, rhs: Self) -> Result<Self, ExprError> {
+        let neg = Self::new(rhs.a.checked_neg(), rhs.b.checked_neg(), Some(rhs.d))?;
+        self.add(neg)
+    }
+
+    fn mul(self, rhs: Self) -> Result<Self, ExprError> {
+        let (x, k) = match (self.a, rhs.a) {
+            (_, 0) => (self, rhs),
+            (0, _) => (rhs, self),
+            _ => return Err(ExprError::NotLinear),
+        };
+        Self::new(
+            x.a.checked_mul(k.b),
+            x.b.checked_mul(k.b),
+            x.d.checked_mul(k.d),
+        )
+    }
+
+    fn div(self, rhs: Self) -> Result<Self, ExprError> {
+        if rhs.a != 0 {
+            return Err(ExprError::NotLinear);
+        }
+        if rhs.b == 0 {
+            return Err(ExprError::DivisionByZero);
+        }
+        self.mul(Self::new(Some(0), Some(rhs.d), Some(rhs.b))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operation_order() {
+        let examples = [
+            ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+            ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+            ("2 * 3 + (4 * 5)", 26, 46),
+            ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+            ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+            (
+                "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+                13632,
+                23340,
+            ),
+        ];
+        for (text, same, addition_first) in examples {
+            let eval = |rules: Precedence| rules.parse(text).unwrap().eval::<i64>().unwrap();
+            assert_eq!(eval(Precedence::same()), same);
+            assert_eq!(eval(Precedence::addition_first()), addition_first);
+        }
+        assert_eq!(
+            Precedence::standard()
+                .parse("1 + 2 * 3")
+                .unwrap()
+                .eval::<u32>(),
+            Ok(7)
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        let rules = Precedence::standard();
+        let show = |text| rules.parse(text).map(|e| e.to_string());
+        assert_eq!(show("a - b - c"), Ok("((a - b) - c)".to_string()));
+        assert_eq!(show("-x * 2"), Ok("(-x * 2)".to_string()));
+        let right = rules.with(BinOp::Sub, 1, Assoc::Right);
+        assert_eq!(
+            right.parse("a - b - c").unwrap().to_string(),
+            "(a - (b - c))"
+        );
+
+        assert_eq!(show("(1 + 2"), Err(ExprError::UnexpectedEnd));
+        assert_eq!(
+            show("1 + 2)"),
+            Err(ExprError::UnexpectedToken {
+                index: 5,
+                found: ")".to_string()
+            })
+        );
+        assert_eq!(
+            show("1 % 2"),
+            Err(ExprError::UnexpectedToken {
+                index: 2,
+                found: "%".to_string()
+            })
+        );
+        assert_eq!(
+            show("99999999999999999999"),
+            Err(ExprError::LiteralTooLarge { index: 0 })
+        );
+    }
+
+    #[test]
+    fn evaluation() {
+        let expr = Precedence::standard().parse("x * x - 7 / y").unwrap();
+        let vars = |name: &str| match name {
+            "x" => Some(3.0),
+            "y" => Some(2.0),
+            _ => None,
+        };
+        assert_eq!(expr.eval_with::<f64>(vars), Ok(5.5));
+        assert_eq!(
+            expr.eval::<i64>(),
+            Err(ExprError::UnknownVariable("x".to_string()))
+        );
+        let mut names = Vec::new();
+        expr.visit_variables(&mut |name| names.push(name));
+        assert_eq!(names, ["x", "x", "y"]);
+
+        let zero = Precedence::standard().parse("1 / (2 - 2)").unwrap();
+        assert_eq!(zero.eval::<i32>(), Err(ExprError::DivisionByZero));
+        let modular = Precedence::standard().parse("1 / 3 * 6").unwrap();
+        assert_eq!(modular.eval::<ModInt<7>>(), Ok(ModInt::new(2)));
+    }
+
+    #[test]
+    fn overflow() {
+        let eval_u32 = |text| Precedence::standard().parse(text).unwrap().eval::<u32>();
+        let eval_i64 = |text| Precedence::standard().parse(text).unwrap().eval::<i64>();
+        assert_eq!(eval_u32("-1"), Err(ExprError::Overflow));
+        assert_eq!(eval_u32("2 - 3 + 5"), Err(ExprError::Overflow));
+        assert_eq!(eval_u32("5000000000"), Err(ExprError::Overflow));
+        assert_eq!(eval_u32("65536 * 65536"), Err(ExprError::Overflow));
+        assert_eq!(eval_u32("65535 * 65537"), Ok(u32::MAX));
+        assert_eq!(
+            eval_i64("(-9223372036854775807 - 1) / -1"),
+            Err(ExprError::Overflow)
+        );
+        assert_eq!(eval_i64("-9223372036854775807 - 1"), Ok(i64::MIN));
+        // floats and modular integers can't overflow
+        let big = Precedence::standard()
+            .parse("4294967296 * 4294967296")
+            .unwrap();
+        assert_eq!(big.eval::<f64>(), Ok(2f64.powi(64)));
+        assert_eq!(big.eval::<ModInt<7>>(), Ok(ModInt::new(2)));
+        // 2 has no inverse modulo 10
+        let half = Precedence::standard().parse("1 / 2").unwrap();
+        assert_eq!(half.eval::<ModInt<10>>(), Err(ExprError::Overflow));
+        let third = Precedence::standard().parse("1 / 3").unwrap();
+        assert_eq!(third.eval::<ModInt<10>>(), Ok(ModInt::new(7)));
+    }
+
+    #[test]
+    fn solving() {
+        let rules = Precedence::standard();
+        let parse = |text| rules.parse(text).unwrap();
+        let no_vars = |_: &str| None;
+
+        let lhs = parse("(4 + 2 * (x - 3)) / 4");
+        assert_eq!(lhs.solve(&parse("150"), "x", no_vars), Ok(301));
+        assert_eq!(parse("-x / 3").solve(&parse("5"), "x", no_vars), Ok(-15));
+        assert_eq!(
+            parse("2 * x").solve(&parse("3"), "x", no_vars),
+            Err(ExprError::NotInteger {
+                numerator: 3,
+                denominator: 2
+            })
+        );
+        assert_eq!(
+            parse("x * x").solve(&parse("4"), "x", no_vars),
+            Err(ExprError::NotLinear)
+        );
+        assert_eq!(
+            parse("x - x").solve(&parse("0"), "x", no_vars),
+            Err(ExprError::NoUniqueSolution)
+        );
+        assert_eq!(parse("x + y").solve(&parse("10"), "x", |_| Some(4)), Ok(6));
+    }
+}
@@ -0,0 +1,456 @@
+//! Numbered context free grammars like the message rules from 2020 day 19.
+//!
+//! ```
+//! # use util::grammar::Grammar;
+//! let grammar: Grammar = "0: 1 2\n1: \"a\"\n2: 1 3 | 3 1\n3: \"b\"".parse().unwrap();
+//! assert!(grammar.matches(0, "aab"));
+//! assert!(!grammar.matches(0, "abb"));
+//! assert_eq!(grammar.match_lengths(0, "abaa"), [3]);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bits::BitSet;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GrammarError {
+    /// A line isn't of the form `id: "text"` or `id: a b | c d`
+    Syntax { line: usize, text: String },
+    /// A rule is defined twice in the same text
+    Duplicate(usize),
+    /// A rule refers to a rule that isn't defined
+    UnknownRule(usize),
+    /// The rule can reach itself, so it can't be compiled into an [`Automaton`]
+    Recursive(usize),
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrammarError::Syntax { line, text } => {
+                write!(f, "invalid rule on line {}: `{}`", line, text)
+            }
+            GrammarError::Duplicate(id) => write!(f, "rule {} is defined twice", id),
+            GrammarError::UnknownRule(id) => write!(f, "rule {} is not defined", id),
+            GrammarError::Recursive(id) => write!(f, "rule {} is recursive", id),
+        }
+    }
+}
+
+impl std::error::Error for GrammarError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// Matches exactly this text
+    Literal(String),
+    /// Matches any of the sequences of rules
+    Alternatives(Vec<Vec<usize>>),
+}
+
+impl FromStr for Rule {
+    type Err = ();
+
+    /// Parses the part after the colon, like `"a"` or `4 1 | 1 4`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(literal) = s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            return Ok(Rule::Literal(literal.to_string()));
+        }
+        let alternatives = s
+            .split('|')
+            .map(|seq| {
+                seq.split_whitespace()
+                    .map(|id| id.parse().map_err(|_| ()))
+                    .collect::<Result<Vec<usize>, ()>>()
+            })
+            .collect::<Result<Vec<_>, ()>>()?;
+        if alternatives.iter().any(Vec::is_empty) {
+            return Err(());
+        }
+        Ok(Rule::Alternatives(alternatives))
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Literal(text) => write!(f, "\"{}\"", text),
+            Rule::Alternatives(alternatives) => {
+                for (i, seq) in alternatives.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, " | ")?;
+                    }
+                    for (j, id) in seq.iter().enumerate() {
+                        if j != 0 {
+                            write!(f, " ")?;
+                        }
+                        write!(f, "{}", id)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A set of numbered rules. Rules may refer to themselves, like `8: 42 | 42 8`, as long as they
+/// consume some text before doing so.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grammar {
+    rules: BTreeMap<usize, Rule>,
+}
+
+impl FromStr for Grammar {
+    type Err = GrammarError;
+
+    /// Parses one rule per line, like `0: 4 1 5` or `4: "a"`. Blank lines are skipped
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut grammar = Grammar::new();
+        for (i, line) in s.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let syntax = || GrammarError::Syntax {
+                line: i + 1,
+                text: line.to_string(),
+            };
+            let (id, rule) = line.split_once(':').ok_or_else(syntax)?;
+            let id = id.trim().parse().map_err(|_| syntax())?;
+            let rule = rule.parse().map_err(|_| syntax())?;
+            if grammar.insert(id, rule).is_some() {
+                return Err(GrammarError::Duplicate(id));
+            }
+        }
+        Ok(grammar)
+    }
+}
+
+impl Grammar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces rule `id`, returning the old rule
+    pub fn insert(&mut self, id: usize, rule: Rule) -> Option<Rule> {
+        self.rules.insert(id, rule)
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Rule> {
+        self.rules.get(&id)
+    }
+
+    /// Returns the rules in order of their id
+    pub fn rules(&self) -> impl Iterator<Item = (usize, &Rule)> {
+        self.rules.iter().map(|(&id, rule)| (id, rule))
+    }
+
+    /// Makes sure every rule that is referred to is defined
+    pub fn check(&self) -> Result<(), GrammarError> {
+        for rule in self.rules.values() {
+            if let Rule::Alternatives(alternatives) = rule {
+                if let Some(&id) = alternatives
+                    .iter()
+                    .flatten()
+                    .find(|id| !self.rules.contains_key(id))
+                {
+                    return Err(GrammarError::UnknownRule(id));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[track_caller]
+    fn rule(&self, id: usize) -> &Rule {
+        self.rules
+            .get(&id)
+            .unwrap_or_else(|| panic!("rule {} is not defined", id))
+    }
+
+    /// Returns every length of a prefix of `text` that rule `id` matches, in increasing order.
+    ///
+    /// # Panics
+    /// This function panics if a needed rule isn't defined, or if a rule can reach itself
+    /// without consuming any text
+    #[track_caller]
+    pub fn match_lengths(&self, id: usize, text: &str) -> Vec<usize> {
+        let mut memo = HashMap::new();
+        self.ends(id, text, 0, &mut memo)
+    }
+
+    /// Returns true if rule `id` matches all of `text`
+    ///
+    /// # Panics
+    /// See [`Grammar::match_lengths`]
+    #[track_caller]
+    pub fn matches(&self, id: usize, text: &str) -> bool {
+        self.match_lengths(id, text).contains(&text.len())
+    }
+
+    /// Returns the sorted positions where a match of rule `id` starting at `start` can end.
+    ///
+    /// `memo` holds the result for each rule and start that has been tried, or `None` while
+    /// that rule is still being matched there, which means it reached itself without consuming
+    /// any text
+    #[track_caller]
+    fn ends(
+        &self,
+        id: usize,
+        text: &str,
+        start: usize,
+        memo: &mut HashMap<(usize, usize), Option<Vec<usize>>>,
+    ) -> Vec<usize> {
+        match memo.get(&(id, start)) {
+            Some(Some(ends)) => return ends.clone(),
+            Some(None) => panic!("rule {} is left recursive", id),
+            None => {}
+        }
+        memo.insert((id, start), None);
+
+        let mut ends = match self.rule(id) {
+            Rule::Literal(literal) => match text[start..].starts_with(literal.as_str()) {
+                true => vec![start + literal.len()],
+                false => vec![],
+            },
+            Rule::Alternatives(alternatives) => {
+                let mut ends = Vec::new();
+                for seq in alternatives {
+                    let mut positions = vec![start];
+                    for &next in seq {
+                        let mut after = Vec::new();
+                        for pos in positions {
+                            after.extend(self.ends(next, text, pos, memo));
+                        }
+                        after.sort_unstable();
+                        after.dedup();
+                        positions = after;
+                    }
+                    ends.extend(positions);
+                }
+                ends
+            }
+        };
+        ends.sort_unstable();
+        ends.dedup();
+        memo.insert((id, start), Some(ends.clone()));
+        ends
+    }
+
+    /// Compiles rule `id` and everything it refers to into an [`Automaton`], which is much
+    /// faster at checking lots of messages. This only works for rules that can't reach
+    /// themselves, since those match a regular language.
+    pub fn compile(&self, id: usize) -> Result<Automaton, GrammarError> {
+        let mut automaton = Automaton {
+            states: vec![State::Accept],
+            start: 0,
+        };
+        let mut stack = Vec::new();
+        automaton.start = self.build(id, 0, &mut automaton.states, &mut stack)?;
+        Ok(automaton)
+    }
+
+    /// Adds the states for rule `id` followed by the state `next`, returning the first state
+    fn build(
+        &self,
+        id: usize,
+        next: usize,
+        states: &mut Vec<State>,
+        stack: &mut Vec<usize>,
+    ) -> Result<usize, GrammarError> {
+        if stack.contains(&id) {
+            return Err(GrammarError::Recursive(id));
+        }
+        let rule = self.rules.get(&id).ok_or(GrammarError::UnknownRule(id))?;
+        stack.push(id);
+        let start = match rule {
+            Rule::Literal(literal) => {
+                let mut next = next;
+                for c in literal.chars().rev() {
+                    states.push(State::Char(c, next));
+                    next = states.len() - 1;
+                }
+                next
+            }
+            Rule::Alternatives(alternatives) => {
+                let mut starts = Vec::new();
+                for seq in alternatives {
+                    let mut next = next;
+                    for &part in seq.iter().rev() {
+                        next = self.build(part, next, states, stack)?;
+                    }
+                    starts.push(next);
+                }
+                states.push(State::Split(starts));
+                states.len() - 1
+            }
+        };
+        stack.pop();
+        Ok(start)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum State {
+    /// Consumes the character and moves to the state
+    Char(char, usize),
+    /// Moves to all of the states without consuming anything
+    Split(Vec<usize>),
+    Accept,
+}
+
+/// A nondeterministic finite automaton compiled from a [`Grammar`] rule, which checks messages
+/// in time linear in their length
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Automaton {
+    states: Vec<State>,
+    start: usize,
+}
+
+impl Automaton {
+    /// Returns the number of states, which grows with the size of the fully expanded rule
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Adds `state` and every state reachable from it without consuming a character to `set`
+    fn add(&self, set: &mut BitSet, state: usize) {
+        if !set.insert(state) {
+            return;
+        }
+        if let State::Split(next) = &self.states[state] {
+            for &next in next {
+                self.add(set, next);
+            }
+        }
+    }
+
+    /// Returns true if the compiled rule matches all of `text`
+    pub fn matches(&self, text: &str) -> bool {
+        let mut current = BitSet::new(self.states.len());
+        self.add(&mut current, self.start);
+        for c in text.chars() {
+            let mut next = BitSet::new(self.states.len());
+            for state in current.iter() {
+                if let State::Char(expected, to) = self.states[state] {
+                    if expected == c {
+                        self.add(&mut next, to);
+                    }
+                }
+            }
+            if next.is_empty() {
+                return false;
+            }
+            current = next;
+        }
+        // the accepting state is always the first one
+        current.contains(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = "42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: \"a\"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: \"b\"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1";
+
+    const MESSAGES: &str = "abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba";
+
+    #[test]
+    fn parse() {
+        let grammar: Grammar = RULES.parse().unwrap();
+        assert_eq!(grammar.get(1), Some(&Rule::Literal("a".to_string())));
+        assert_eq!(grammar.get(42).unwrap().to_string(), "9 14 | 10 1");
+        assert_eq!(grammar.check(), Ok(()));
+
+        assert_eq!(
+            "0: 1\n1 \"a\"".parse::<Grammar>(),
+            Err(GrammarError::Syntax {
+                line: 2,
+                text: "1 \"a\"".to_string()
+            })
+        );
+        assert_eq!(
+            "0: 1 |".parse::<Grammar>(),
+            Err(GrammarError::Syntax {
+                line: 1,
+                text: "0: 1 |".to_string()
+            })
+        );
+        assert_eq!(
+            "0: 1\n0: 2".parse::<Grammar>(),
+            Err(GrammarError::Duplicate(0))
+        );
+        let missing: Grammar = "0: 1 2\n1: \"a\"".parse().unwrap();
+        assert_eq!(missing.check(), Err(GrammarError::UnknownRule(2)));
+        assert_eq!(missing.compile(0), Err(GrammarError::UnknownRule(2)));
+    }
+
+    #[test]
+    fn looping_rules() {
+        let mut grammar: Grammar = RULES.parse().unwrap();
+        let count = |grammar: &Grammar| MESSAGES.lines().filter(|m| grammar.matches(0, m)).count();
+        assert_eq!(count(&grammar), 3);
+        let automaton = grammar.compile(0).unwrap();
+        assert_eq!(MESSAGES.lines().filter(|m| automaton.matches(m)).count(), 3);
+
+        grammar.insert(8, "42 | 42 8".parse().unwrap());
+        grammar.insert(11, "42 31 | 42 11 31".parse().unwrap());
+        assert_eq!(count(&grammar), 12);
+        assert_eq!(grammar.compile(0), Err(GrammarError::Recursive(11)));
+        assert_eq!(grammar.match_lengths(8, "bbabbbbaabaabba"), [5, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "rule 0 is left recursive")]
+    fn left_recursion() {
+        let grammar: Grammar = "0: 0 1 | 1\n1: \"a\"".parse().unwrap();
+        grammar.matches(0, "aaa");
+    }
+}
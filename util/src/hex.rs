@@ -0,0 +1,450 @@
+use crate::{Matrix, SignedPoint};
+
+/// A cell in a hex grid stored using axial coordinates.
+///
+/// The third cube coordinate is implied by `q + r + s == 0`, see [`CubeHex`] for when all three
+/// are needed. Axial coordinates work for both pointy-top and flat-top grids, only the meaning of
+/// each neighbor changes (see [`PointyDirection`] and [`FlatDirection`])
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AxialHex {
+    pub q: isize,
+    pub r: isize,
+}
+
+/// A cell in a hex grid stored using cube coordinates, where `q + r + s == 0` always holds
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CubeHex {
+    pub q: isize,
+    pub r: isize,
+    pub s: isize,
+}
+
+/// The two ways a hex grid can be laid out
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HexOrientation {
+    /// Rows of hexagons with a point on top. Neighbors are east, west, and the four diagonals
+    PointyTop,
+    /// Columns of hexagons with a flat top. Neighbors are north, south, and the four diagonals
+    FlatTop,
+}
+
+/// The six neighbors of a pointy-top hex (2020 day 24)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PointyDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+/// The six neighbors of a flat-top hex (2017 day 11)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FlatDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+/// Common interface for [`PointyDirection`] and [`FlatDirection`]
+pub trait HexDirection: Copy + Sized {
+    const ORIENTATION: HexOrientation;
+
+    /// Returns all six directions in clockwise order
+    fn all() -> [Self; 6];
+
+    /// Returns the `(q, r)` axial offset of a single step in this direction
+    fn to_axial_offsets(self) -> (isize, isize);
+
+    /// Parses a single direction token such as `ne` or `w`
+    fn from_token(token: &str) -> Option<Self>;
+
+    /// Returns the direction pointing the opposite way
+    fn reverse(self) -> Self {
+        let all = Self::all();
+        let (q, r) = self.to_axial_offsets();
+        *all.iter()
+            .find(|d| d.to_axial_offsets() == (-q, -r))
+            .unwrap()
+    }
+
+    /// Rotates this direction clockwise by `steps` sixths of a turn. Negative values rotate
+    /// counter-clockwise
+    fn rotate(self, steps: isize) -> Self {
+        let all = Self::all();
+        let offset = self.to_axial_offsets();
+        let index = all
+            .iter()
+            .position(|d| d.to_axial_offsets() == offset)
+            .unwrap();
+        all[(index as isize + steps).rem_euclid(6) as usize]
+    }
+
+    /// Parses a path of directions, like `nwwswee` or `ne,ne,s,s`.
+    ///
+    /// Commas and whitespace between tokens are ignored. Two letter tokens are preferred over
+    /// single letter ones, so `ne` is always north east, never north then east.
+    /// Returns `Err` with the byte offset of the first unknown token
+    fn parse_path(s: &str) -> Result<Vec<Self>, usize> {
+        let mut result = Vec::new();
+        let mut i = 0;
+        while i < s.len() {
+            let rest = &s[i..];
+            if rest.starts_with(|c: char| c == ',' || c.is_ascii_whitespace()) {
+                i += 1;
+                continue;
+            }
+            if let Some(dir) = rest.get(..2).and_then(Self::from_token) {
+                result.push(dir);
+                i += 2;
+            } else if let Some(dir) = rest.get(..1).and_then(Self::from_token) {
+                result.push(dir);
+                i += 1;
+            } else {
+                return Err(i);
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl HexDirection for PointyDirection {
+    const ORIENTATION: HexOrientation = HexOrientation::PointyTop;
+
+    fn all() -> [Self; 6] {
+        use PointyDirection::*;
+        [East, SouthEast, SouthWest, West, NorthWest, NorthEast]
+    }
+
+    fn to_axial_offsets(self) -> (isize, isize) {
+        match self {
+            PointyDirection::East => (1, 0),
+            PointyDirection::SouthEast => (0, 1),
+            PointyDirection::SouthWest => (-1, 1),
+            PointyDirection::West => (-1, 0),
+            PointyDirection::NorthWest => (0, -1),
+            PointyDirection::NorthEast => (1, -1),
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        Some(match token {
+            "e" | "E" => PointyDirection::East,
+            "se" | "SE" => PointyDirection::SouthEast,
+            "sw" | "SW" => PointyDirection::SouthWest,
+            "w" | "W" => PointyDirection::West,
+            "nw" | "NW" => PointyDirection::NorthWest,
+            "ne" | "NE" => PointyDirection::NorthEast,
+            _ => return None,
+        })
+    }
+}
+
+impl HexDirection for FlatDirection {
+    const ORIENTATION: HexOrientation = HexOrientation::FlatTop;
+
+    fn all() -> [Self; 6] {
+        use FlatDirection::*;
+        [North, NorthEast, SouthEast, South, SouthWest, NorthWest]
+    }
+
+    fn to_axial_offsets(self) -> (isize, isize) {
+        match self {
+            FlatDirection::North => (0, -1),
+            FlatDirection::NorthEast => (1, -1),
+            FlatDirection::SouthEast => (1, 0),
+            FlatDirection::South => (0, 1),
+            FlatDirection::SouthWest => (-1, 1),
+            FlatDirection::NorthWest => (-1, 0),
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        Some(match token {
+            "n" | "N" => FlatDirection::North,
+            "ne" | "NE" => FlatDirection::NorthEast,
+            "se" | "SE" => FlatDirection::SouthEast,
+            "s" | "S" => FlatDirection::South,
+            "sw" | "SW" => FlatDirection::SouthWest,
+            "nw" | "NW" => FlatDirection::NorthWest,
+            _ => return None,
+        })
+    }
+}
+
+impl std::str::FromStr for PointyDirection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_token(s).ok_or(())
+    }
+}
+
+impl std::str::FromStr for FlatDirection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_token(s).ok_or(())
+    }
+}
+
+/// Axial offsets of the six neighbors of any hex, independent of orientation
+const HEX_NEIGHBOR_OFFSETS: [(isize, isize); 6] =
+    [(1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1), (1, -1)];
+
+impl AxialHex {
+    pub const ORIGIN: AxialHex = AxialHex::new(0, 0);
+
+    pub const fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    /// Returns the implied third cube coordinate
+    pub fn s(self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn offset<D: HexDirection>(self, direction: D) -> Self {
+        self.offset_by(direction, 1)
+    }
+
+    /// Moves `count` hexes in `direction`
+    pub fn offset_by<D: HexDirection>(self, direction: D, count: isize) -> Self {
+        let (q, r) = direction.to_axial_offsets();
+        Self::new(self.q + q * count, self.r + r * count)
+    }
+
+    /// Follows every direction in `path` starting at `self`, returning the final hex
+    pub fn follow<D: HexDirection>(self, path: impl IntoIterator<Item = D>) -> Self {
+        path.into_iter().fold(self, |hex, d| hex.offset(d))
+    }
+
+    /// Returns the six hexes that share an edge with this hex
+    pub fn neighbors(self) -> [AxialHex; 6] {
+        HEX_NEIGHBOR_OFFSETS.map(|(q, r)| Self::new(self.q + q, self.r + r))
+    }
+
+    /// Returns the number of steps needed to walk from `self` to `other`
+    pub fn distance(self, other: Self) -> usize {
+        let q = self.q - other.q;
+        let r = self.r - other.r;
+        let s = self.s() - other.s();
+        (q.unsigned_abs() + r.unsigned_abs() + s.unsigned_abs()) / 2
+    }
+
+    /// Returns the number of steps needed to walk from the origin to `self`
+    pub fn length(self) -> usize {
+        self.distance(Self::ORIGIN)
+    }
+
+    /// Returns all hexes exactly `radius` steps away from `self`.
+    ///
+    /// A radius of zero yields only `self`, otherwise `6 * radius` hexes are returned
+    pub fn ring(self, radius: usize) -> Vec<AxialHex> {
+        if radius == 0 {
+            return vec![self];
+        }
+        let mut result = Vec::with_capacity(6 * radius);
+        let (start_q, start_r) = HEX_NEIGHBOR_OFFSETS[4];
+        let mut hex = Self::new(
+            self.q + start_q * radius as isize,
+            self.r + start_r * radius as isize,
+        );
+        // walk each of the six sides in order, each side is `radius` hexes long
+        for (q, r) in HEX_NEIGHBOR_OFFSETS {
+            for _ in 0..radius {
+                result.push(hex);
+                hex = Self::new(hex.q + q, hex.r + r);
+            }
+        }
+        result
+    }
+
+    /// Returns all hexes at most `radius` steps away from `self`, including `self`
+    pub fn within(self, radius: usize) -> Vec<AxialHex> {
+        let radius = radius as isize;
+        let mut result = Vec::new();
+        for q in -radius..=radius {
+            let r_min = (-radius).max(-q - radius);
+            let r_max = radius.min(-q + radius);
+            for r in r_min..=r_max {
+                result.push(Self::new(self.q + q, self.r + r));
+            }
+        }
+        result
+    }
+
+    /// Converts to "doubled" coordinates, where each hex maps to a unique cell on a square grid
+    /// and neighbors stay visually adjacent.
+    ///
+    /// Pointy-top grids double the column, flat-top grids double the row
+    pub fn to_doubled(self, orientation: HexOrientation) -> SignedPoint {
+        match orientation {
+            HexOrientation::PointyTop => SignedPoint::new(self.r, 2 * self.q + self.r),
+            HexOrientation::FlatTop => SignedPoint::new(2 * self.r + self.q, self.q),
+        }
+    }
+}
+
+impl CubeHex {
+    /// Creates a new cube coordinate.
+    ///
+    /// # Panics
+    /// This function panics if `q + r + s != 0`
+    #[track_caller]
+    pub fn new(q: isize, r: isize, s: isize) -> Self {
+        assert_eq!(q + r + s, 0, "cube coordinates must sum to zero");
+        Self { q, r, s }
+    }
+
+    pub fn distance(self, other: Self) -> usize {
+        AxialHex::from(self).distance(other.into())
+    }
+}
+
+impl From<AxialHex> for CubeHex {
+    fn from(value: AxialHex) -> Self {
+        Self {
+            q: value.q,
+            r: value.r,
+            s: value.s(),
+        }
+    }
+}
+
+impl From<CubeHex> for AxialHex {
+    fn from(value: CubeHex) -> Self {
+        Self::new(value.q, value.r)
+    }
+}
+
+impl std::ops::Add for AxialHex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl std::ops::Sub for AxialHex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl std::ops::AddAssign for AxialHex {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Matrix<u8> {
+    /// Renders a set of hexes into a matrix for debugging, using doubled coordinates (see
+    /// [`AxialHex::to_doubled`]). Cells that are not part of any hex are set to `background_char`
+    ///
+    /// # Panics
+    /// This function panics if `it` is empty
+    pub fn from_hexes<I>(orientation: HexOrientation, background_char: u8, it: I) -> Self
+    where
+        I: Iterator<Item = (u8, AxialHex)> + Clone,
+    {
+        let points = it.map(|(c, hex)| (c, hex.to_doubled(orientation)));
+        let min_row = points.clone().map(|(_, p)| p.row).min().unwrap();
+        let max_row = points.clone().map(|(_, p)| p.row).max().unwrap();
+        let min_col = points.clone().map(|(_, p)| p.col).min().unwrap();
+        let max_col = points.clone().map(|(_, p)| p.col).max().unwrap();
+
+        let rows = (max_row - min_row + 1) as usize;
+        let cols = (max_col - min_col + 1) as usize;
+
+        let mut result = Matrix::new_with_value(rows, cols, background_char);
+        for (c, point) in points {
+            let row = (point.row - min_row) as usize;
+            let col = (point.col - min_col) as usize;
+            result.set(row, col, c);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pointy_path() {
+        let path = PointyDirection::parse_path("nwwswee").unwrap();
+        use PointyDirection::*;
+        assert_eq!(path, vec![NorthWest, West, SouthWest, East, East]);
+        assert_eq!(AxialHex::ORIGIN.follow(path), AxialHex::ORIGIN);
+
+        let path = PointyDirection::parse_path("esew").unwrap();
+        assert_eq!(
+            AxialHex::ORIGIN.follow(path),
+            AxialHex::ORIGIN.offset(SouthEast)
+        );
+        assert_eq!(PointyDirection::parse_path("nex"), Err(2));
+    }
+
+    #[test]
+    fn flat_distance() {
+        // examples from 2017 day 11
+        let cases = [
+            ("ne,ne,ne", 3),
+            ("ne,ne,sw,sw", 0),
+            ("ne,ne,s,s", 2),
+            ("se,sw,se,sw,sw", 3),
+        ];
+        for (path, expected) in cases {
+            let path = FlatDirection::parse_path(path).unwrap();
+            assert_eq!(AxialHex::ORIGIN.follow(path).length(), expected);
+        }
+    }
+
+    #[test]
+    fn rings() {
+        let center = AxialHex::new(3, -2);
+        assert_eq!(center.ring(0), vec![center]);
+        for radius in 1..5 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.len(), 6 * radius);
+            assert!(ring.iter().all(|h| h.distance(center) == radius));
+        }
+        assert_eq!(center.within(2).len(), 1 + 6 + 12);
+
+        let mut neighbors = center.neighbors().to_vec();
+        let mut ring = center.ring(1);
+        neighbors.sort();
+        ring.sort();
+        assert_eq!(neighbors, ring);
+    }
+
+    #[test]
+    fn rotate_and_reverse() {
+        for d in PointyDirection::all() {
+            assert_eq!(d.rotate(3), d.reverse());
+            assert_eq!(d.rotate(-1).rotate(1), d);
+        }
+        assert_eq!(FlatDirection::North.rotate(1), FlatDirection::NorthEast);
+        assert_eq!(FlatDirection::North.reverse(), FlatDirection::South);
+    }
+
+    #[test]
+    fn render() {
+        let hexes = AxialHex::ORIGIN.within(1);
+        let mat = Matrix::from_hexes(
+            HexOrientation::PointyTop,
+            b'.',
+            hexes
+                .iter()
+                .map(|&h| (if h == AxialHex::ORIGIN { b'O' } else { b'#' }, h)),
+        );
+        assert_eq!(mat.format_as_chars(), ".#.#.\n#.O.#\n.#.#.");
+    }
+}
@@ -0,0 +1,400 @@
+//! Renders grids to PNG, PPM or SVG images, for puzzles too big to read with
+//! [`Matrix::print_as_chars`].
+//!
+//! ```no_run
+//! # use util::Matrix;
+//! let grid = Matrix::new_with_value(100, 100, 0u32);
+//! grid.render(|&n| if n > 1 { [255, 0, 0] } else { [0, 0, 0] })
+//!     .scale(4)
+//!     .grid_lines([40, 40, 40])
+//!     .path([(0, 0), (0, 1), (1, 1)], [0, 255, 0])
+//!     .save_png("vents.png")
+//!     .unwrap();
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::Matrix;
+
+/// A color as red, green and blue
+pub type Rgb = [u8; 3];
+
+/// An RGB image with 8 bits per channel
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    /// Stored in row major ordering, 3 bytes per pixel
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: background.repeat(width * height),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the raw pixel data, 3 bytes per pixel in row major order
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        let i = (y * self.width + x) * 3;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        let i = (y * self.width + x) * 3;
+        self.pixels[i..i + 3].copy_from_slice(&color);
+    }
+
+    /// Fills the `width` by `height` rectangle whose top left corner is `x`, `y`, clipping it to
+    /// the image
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgb) {
+        let x_end = (x + width).min(self.width);
+        for y in y..(y + height).min(self.height) {
+            for x in x..x_end {
+                self.set(x, y, color);
+            }
+        }
+    }
+
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let to_io = |e: png::EncodingError| io::Error::other(e);
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(to_io)?;
+        writer.write_image_data(&self.pixels).map_err(to_io)
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_png(BufWriter::new(File::create(path)?))
+    }
+
+    /// Writes this image as a binary PPM, which almost every image viewer can open
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels)?;
+        out.flush()
+    }
+
+    pub fn save_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_ppm(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// A grid of colored cells which can be drawn as an [`Image`] or written as an SVG.
+///
+/// Build one with [`Matrix::render`] or [`GridImage::from_points`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GridImage {
+    rows: usize,
+    cols: usize,
+    /// The color of each cell in row major ordering
+    cells: Vec<Rgb>,
+    /// The grid position of `cells[0]`, which is only non zero for point sets
+    origin: (i64, i64),
+    scale: usize,
+    grid_lines: Option<Rgb>,
+    paths: Vec<(Vec<(usize, usize)>, Rgb)>,
+}
+
+impl<T> Matrix<T> {
+    /// Colors each cell using `color` so this matrix can be saved as an image
+    pub fn render(&self, color: impl Fn(&T) -> Rgb) -> GridImage {
+        GridImage {
+            rows: self.rows(),
+            cols: self.cols(),
+            cells: self.iter().map(color).collect(),
+            origin: (0, 0),
+            scale: 1,
+            grid_lines: None,
+            paths: Vec::new(),
+        }
+    }
+}
+
+impl GridImage {
+    /// Renders a sparse set of `(row, col)` points, which may be negative, over `background`.
+    /// The image covers the bounding box of the points, see [`GridImage::origin`]
+    pub fn from_points(
+        points: impl IntoIterator<Item = ((i64, i64), Rgb)>,
+        background: Rgb,
+    ) -> Self {
+        let points: Vec<_> = points.into_iter().collect();
+        let bounds = |f: fn(&(i64, i64)) -> i64| {
+            let min = points.iter().map(|(p, _)| f(p)).min().unwrap_or(0);
+            let max = points.iter().map(|(p, _)| f(p)).max().unwrap_or(-1);
+            (min, (max - min + 1) as usize)
+        };
+        let (min_row, rows) = bounds(|p| p.0);
+        let (min_col, cols) = bounds(|p| p.1);
+        let mut cells = vec![background; rows * cols];
+        for ((row, col), color) in points {
+            cells[(row - min_row) as usize * cols + (col - min_col) as usize] = color;
+        }
+        Self {
+            rows,
+            cols,
+            cells,
+            origin: (min_row, min_col),
+            scale: 1,
+            grid_lines: None,
+            paths: Vec::new(),
+        }
+    }
+
+    /// Returns the point drawn in the top left cell. Positions passed to [`GridImage::path`]
+    /// are relative to this
+    pub fn origin(&self) -> (i64, i64) {
+        self.origin
+    }
+
+    /// Draws each cell as a `scale` by `scale` square
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Draws a one pixel line of `color` around every cell
+    pub fn grid_lines(mut self, color: Rgb) -> Self {
+        self.grid_lines = Some(color);
+        self
+    }
+
+    /// Colors the `(row, col)` cells along `path`, like the nodes returned by one of
+    /// `pathfinding`'s searches. Later paths are drawn over earlier ones
+    pub fn path(mut self, path: impl IntoIterator<Item = (usize, usize)>, color: Rgb) -> Self {
+        self.paths.push((path.into_iter().collect(), color));
+        self
+    }
+
+    /// Returns the final color of every cell after drawing paths
+    fn cell_colors(&self) -> Vec<Rgb> {
+        let mut cells = self.cells.clone();
+        for (path, color) in &self.paths {
+            for &(row, col) in path {
+                if row < self.rows && col < self.cols {
+                    cells[row * self.cols + col] = *color;
+                }
+            }
+        }
+        cells
+    }
+
+    /// Returns the distance in pixels from the start of one cell to the start of the next
+    fn stride(&self) -> usize {
+        self.scale + self.grid_lines.is_some() as usize
+    }
+
+    /// Returns the size of the image in pixels
+    fn size(&self) -> (usize, usize) {
+        let border = self.grid_lines.is_some() as usize;
+        (
+            self.cols * self.stride() + border,
+            self.rows * self.stride() + border,
+        )
+    }
+
+    pub fn to_image(&self) -> Image {
+        let (width, height) = self.size();
+        let mut image = Image::new(width, height, self.grid_lines.unwrap_or([0, 0, 0]));
+        let border = self.grid_lines.is_some() as usize;
+        let stride = self.stride();
+        for (i, color) in self.cell_colors().into_iter().enumerate() {
+            let (row, col) = (i / self.cols, i % self.cols);
+            let (x, y) = (col * stride + border, row * stride + border);
+            image.fill_rect(x, y, self.scale, self.scale, color);
+        }
+        image
+    }
+
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        self.to_image().write_png(out)
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.to_image().save_png(path)
+    }
+
+    pub fn write_ppm(&self, out: impl Write) -> io::Result<()> {
+        self.to_image().write_ppm(out)
+    }
+
+    pub fn save_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.to_image().save_ppm(path)
+    }
+
+    /// Writes this grid as an SVG. The most common color becomes the background, and runs of
+    /// same colored cells in a row share one `<rect>`, so large sparse grids stay small.
+    /// Grid lines are drawn over the cells as a single `<path>`
+    pub fn write_svg(&self, mut out: impl Write) -> io::Result<()> {
+        let hex = |[r, g, b]: Rgb| format!("#{:02x}{:02x}{:02x}", r, g, b);
+        let cells = self.cell_colors();
+        let mut counts = std::collections::HashMap::new();
+        for color in &cells {
+            *counts.entry(*color).or_insert(0usize) += 1;
+        }
+        let background = counts
+            .into_iter()
+            .max_by_key(|&(color, count)| (count, color))
+            .map(|(color, _)| color)
+            .unwrap_or([0, 0, 0]);
+
+        let (width, height) = self.size();
+        let border = self.grid_lines.is_some() as usize;
+        let stride = self.stride();
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" shape-rendering="crispEdges">"#,
+            width, height
+        )?;
+        writeln!(
+            out,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            width,
+            height,
+            hex(background)
+        )?;
+        for row in 0..self.rows {
+            let mut col = 0;
+            while col < self.cols {
+                let color = cells[row * self.cols + col];
+                let start = col;
+                while col < self.cols && cells[row * self.cols + col] == color {
+                    col += 1;
+                }
+                if color == background {
+                    continue;
+                }
+                writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    start * stride + border,
+                    row * stride + border,
+                    (col - start) * stride - border,
+                    self.scale,
+                    hex(color)
+                )?;
+            }
+        }
+        if let Some(color) = self.grid_lines {
+            let vertical = (0..=self.cols).map(|col| format!("M{}.5 0v{}", col * stride, height));
+            let horizontal = (0..=self.rows).map(|row| format!("M0 {}.5h{}", row * stride, width));
+            writeln!(
+                out,
+                r#"<path d="{}" stroke="{}" stroke-width="1"/>"#,
+                vertical.chain(horizontal).collect::<String>(),
+                hex(color)
+            )?;
+        }
+        writeln!(out, "</svg>")?;
+        out.flush()
+    }
+
+    pub fn save_svg(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_svg(BufWriter::new(File::create(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb = [255, 0, 0];
+    const GREY: Rgb = [128, 128, 128];
+    const BLACK: Rgb = [0, 0, 0];
+    const WHITE: Rgb = [255, 255, 255];
+
+    fn lit(c: &u8) -> Rgb {
+        if *c == b'#' {
+            WHITE
+        } else {
+            BLACK
+        }
+    }
+
+    #[test]
+    fn matrix_pixels() {
+        let grid = Matrix::new_from_chars("#.\n.#").unwrap();
+        let image = grid.render(lit).scale(2).to_image();
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.get(1, 1), WHITE);
+        assert_eq!(image.get(2, 1), BLACK);
+        assert_eq!(image.get(3, 3), WHITE);
+
+        let image = grid
+            .render(lit)
+            .scale(2)
+            .grid_lines(GREY)
+            .path([(1, 0), (9, 9)], RED)
+            .to_image();
+        // 2 cells of 2 pixels, with 3 lines around them
+        assert_eq!((image.width(), image.height()), (7, 7));
+        assert_eq!(image.get(0, 0), GREY);
+        assert_eq!(image.get(1, 1), WHITE);
+        assert_eq!(image.get(3, 1), GREY);
+        assert_eq!(image.get(4, 2), BLACK);
+        assert_eq!(image.get(1, 4), RED);
+        assert_eq!(image.get(5, 5), WHITE);
+    }
+
+    #[test]
+    fn sparse_points() {
+        let grid = GridImage::from_points([((-1, 2), RED), ((1, 4), WHITE)], BLACK);
+        assert_eq!(grid.origin(), (-1, 2));
+        let image = grid.to_image();
+        assert_eq!((image.width(), image.height()), (3, 3));
+        assert_eq!(image.get(0, 0), RED);
+        assert_eq!(image.get(2, 2), WHITE);
+        assert_eq!(image.get(1, 1), BLACK);
+
+        let empty = GridImage::from_points([], BLACK).to_image();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+    }
+
+    #[test]
+    fn encodings() {
+        let grid = Matrix::new_from_chars("##.\n...").unwrap().render(lit);
+
+        let mut ppm = Vec::new();
+        grid.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 3 * 2 * 3);
+        assert_eq!(&ppm[11..14], &WHITE);
+
+        let mut png = Vec::new();
+        grid.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let mut svg = Vec::new();
+        grid.clone().scale(10).write_svg(&mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains(r#"width="30" height="20""#));
+        assert!(svg.contains(r##"<rect width="30" height="20" fill="#000000"/>"##));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="20" height="10" fill="#ffffff"/>"##));
+        assert_eq!(svg.matches("<rect").count(), 2);
+
+        let mut svg = Vec::new();
+        grid.scale(10).grid_lines(GREY).write_svg(&mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains(r#"width="34" height="23""#));
+        assert!(svg.contains(r##"<rect x="1" y="1" width="21" height="10" fill="#ffffff"/>"##));
+        assert!(svg.contains(r##"d="M0.5 0v23M11.5 0v23M22.5 0v23M33.5 0v23M0 0.5h34"##));
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::{expected_type, Input, ParseError};
+
+/// The fields of a record made of `key:value` pairs, such as a 2020 day 4 passport
+pub type Record<'a> = HashMap<&'a str, &'a str>;
+
+/// Converts `\r\n` and `\r` line endings to `\n`
+pub(crate) fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Returns the number of lines in `text` before `part`, which must be a substring of `text`
+fn lines_before(text: &str, part: &str) -> usize {
+    let offset = part.as_ptr() as usize - text.as_ptr() as usize;
+    text[..offset].matches('\n').count()
+}
+
+fn error_at(line_number: usize, line: &str, column: usize, expected: String) -> ParseError {
+    ParseError {
+        line: line_number,
+        column: column + 1,
+        expected,
+        found: line[column..].to_owned(),
+    }
+}
+
+/// Returns each whitespace separated word in `line` along with the column it starts at
+fn words(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split(|c: char| c.is_ascii_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(move |word| (column_of(line, word), word))
+}
+
+/// Returns the column `word` starts at, which must be a substring of `line`
+fn column_of(line: &str, word: &str) -> usize {
+    word.as_ptr() as usize - line.as_ptr() as usize
+}
+
+/// Splits `text` into blocks of lines separated by one or more blank lines. Lines that only
+/// contain whitespace count as blank
+pub fn sections(text: &str) -> impl Iterator<Item = &str> {
+    let mut sections = Vec::new();
+    // the byte range of the section being built
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            sections.extend(current.take());
+        } else {
+            let start = current.map_or(offset, |(start, _)| start);
+            current = Some((start, offset + line.trim_end_matches('\n').len()));
+        }
+        offset += line.len();
+    }
+    sections.extend(current);
+    sections.into_iter().map(|(start, end)| &text[start..end])
+}
+
+/// Parses whitespace separated `key:value` pairs, which may span several lines
+pub fn parse_record(text: &str) -> Result<Record<'_>, ParseError> {
+    let mut record = Record::new();
+    for (i, line) in text.lines().enumerate() {
+        for (column, word) in words(line) {
+            let (key, value) = word
+                .split_once(':')
+                .ok_or_else(|| error_at(i + 1, line, column, "`key:value`".to_owned()))?;
+            record.insert(key, value);
+        }
+    }
+    Ok(record)
+}
+
+/// Parses rows of whitespace separated values, like the bingo boards from 2021 day 4.
+/// Any amount of whitespace may separate two columns, so right aligned numbers work
+pub fn parse_table<T: FromStr>(text: &str) -> Result<Vec<Vec<T>>, ParseError> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            words(line)
+                .map(|(column, word)| {
+                    word.parse()
+                        .map_err(|_| error_at(i + 1, line, column, expected_type::<T>()))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Parses a diagram of labeled stacks like the crates from 2022 day 5, returning each stack from
+/// bottom to top:
+///
+/// ```text
+///     [D]
+/// [N] [C]
+/// [Z] [M] [P]
+///  1   2   3
+/// ```
+///
+/// Each stack is the column of characters above its label, so any single character can be
+/// stacked (not just ASCII ones), and lines may be missing their trailing spaces
+pub fn parse_stack_diagram(text: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut lines = text.lines().rev();
+    let labels = lines.next().unwrap_or("");
+    // columns are counted in characters, as a multi-byte crate still takes up one column
+    let columns: Vec<usize> = words(labels)
+        .map(|(column, _)| labels[..column].chars().count())
+        .collect();
+    if columns.is_empty() {
+        return Err(error_at(
+            text.lines().count().max(1),
+            labels,
+            0,
+            "a line of stack labels".to_owned(),
+        ));
+    }
+    let mut stacks = vec![Vec::new(); columns.len()];
+    for line in lines {
+        let cells: Vec<char> = line.chars().collect();
+        for (stack, &column) in stacks.iter_mut().zip(&columns) {
+            match cells.get(column) {
+                Some(' ') | None => {}
+                Some(&c) => stack.push(c),
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+impl Input {
+    /// Returns the blocks of lines separated by one or more blank lines
+    pub fn sections(&self) -> impl Iterator<Item = &str> + '_ {
+        sections(&self.0)
+    }
+
+    /// Parses each line of each section as `T`, like the calorie lists from 2022 day 1
+    pub fn paragraph_groups<T: FromStr>(&self) -> Result<Vec<Vec<T>>, ParseError> {
+        self.sections()
+            .map(|section| {
+                let first_line = lines_before(&self.0, section);
+                section
+                    .lines()
+                    .enumerate()
+                    .map(|(i, line)| {
+                        let value = line.trim();
+                        value.parse().map_err(|_| {
+                            let column = column_of(line, value);
+                            error_at(first_line + i + 1, line, column, expected_type::<T>())
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Parses each section as a [`Record`] of `key:value` pairs
+    pub fn records(&self) -> Result<Vec<Record<'_>>, ParseError> {
+        self.sections()
+            .map(|section| {
+                parse_record(section).map_err(|mut e| {
+                    e.line += lines_before(&self.0, section);
+                    e
+                })
+            })
+            .collect()
+    }
+
+    /// Parses the whole input as a table of whitespace separated values, see [`parse_table`]
+    pub fn table<T: FromStr>(&self) -> Result<Vec<Vec<T>>, ParseError> {
+        parse_table(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IsTest;
+
+    use super::*;
+
+    #[test]
+    fn line_endings() {
+        let input = Input::new("1\r\n2\r\n  \t\r\n3\r4\n".to_owned(), IsTest::Yes);
+        assert_eq!(input.as_str(), "1\n2\n  \t\n3\n4\n");
+        assert_eq!(input.sections().collect::<Vec<_>>(), vec!["1\n2", "3\n4"]);
+        assert_eq!(
+            input.paragraph_groups::<u32>(),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+    }
+
+    #[test]
+    fn groups_report_absolute_lines() {
+        let input = Input::new("1\n2\n\n\n3\nx\n".to_owned(), IsTest::Yes);
+        assert_eq!(input.sections().collect::<Vec<_>>(), vec!["1\n2", "3\nx"]);
+        let err = input.paragraph_groups::<u32>().unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (6, 1, "x"));
+
+        let input = Input::new("1 \n 2\n\n3\n  x\n".to_owned(), IsTest::Yes);
+        let err = input.paragraph_groups::<u32>().unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (5, 3, "x"));
+        let input = Input::new("1 \n 2\n\n3\n".to_owned(), IsTest::Yes);
+        assert_eq!(
+            input.paragraph_groups::<u32>(),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+    }
+
+    #[test]
+    fn passports() {
+        let input = Input::new(
+            "ecl:gry pid:860033327\nbyr:1937\n\nhcl:#cfa07d eyr:2025\n\niyr:2013 hgt\n".to_owned(),
+            IsTest::Yes,
+        );
+        let err = input.records().unwrap_err();
+        assert_eq!((err.line, err.column), (6, 10));
+
+        let input = Input::new(input.as_str().replace(" hgt", ""), IsTest::Yes);
+        let records = input.records().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["pid"], "860033327");
+        assert_eq!(records[0]["byr"], "1937");
+        assert_eq!(records[1].get("hcl"), Some(&"#cfa07d"));
+    }
+
+    #[test]
+    fn bingo_table() {
+        let board = "22 13 17\n 8  2 23\n21  9 14";
+        assert_eq!(
+            parse_table::<u32>(board),
+            Ok(vec![vec![22, 13, 17], vec![8, 2, 23], vec![21, 9, 14]])
+        );
+        let err = parse_table::<u32>("1 2\n3  -4").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 4, "-4"));
+    }
+
+    #[test]
+    fn crate_stacks() {
+        let diagram = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3 ";
+        assert_eq!(
+            parse_stack_diagram(diagram),
+            Ok(vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']])
+        );
+        assert!(parse_stack_diagram("").is_err());
+        let diagram = "    [é]\n[ü] [ß]\n 1   2";
+        assert_eq!(
+            parse_stack_diagram(diagram),
+            Ok(vec![vec!['ü'], vec!['ß', 'é']])
+        );
+    }
+}
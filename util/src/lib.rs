@@ -1,13 +1,38 @@
+pub mod animation;
+pub mod bits;
+pub mod counting;
+mod cycle;
+mod direction;
+pub mod expr;
+pub mod grammar;
+mod hex;
+pub mod image;
+mod input;
 mod matrix;
+mod ocr;
+pub mod math;
 mod parser;
+mod pattern;
 mod problems;
+pub mod radix;
+pub mod ranges;
 mod runner;
+mod scan;
 mod shifter;
 mod traits;
+pub mod tree;
+pub mod vm;
 
+pub use cycle::*;
+pub use direction::*;
+pub use hex::*;
+pub use input::*;
 pub use matrix::*;
+pub use ocr::*;
 pub use crate::parser::*;
+pub use pattern::*;
 pub use problems::*;
 pub use runner::*;
+pub use scan::*;
 pub use shifter::*;
 pub use traits::*;
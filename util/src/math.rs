@@ -0,0 +1,438 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Returns the greatest common divisor of `a` and `b`, which is never negative
+///
+/// # Panics
+/// This function panics if the gcd is 2^63, which only happens when `a` and `b` are each
+/// `i64::MIN` or 0
+#[track_caller]
+pub fn gcd(a: i64, b: i64) -> i64 {
+    ext_gcd(a, b).0
+}
+
+/// Returns the least common multiple of `a` and `b`, or `None` if it doesn't fit in an `i64`
+pub fn lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    let (a, b) = (a as i128, b as i128);
+    let g = ext_gcd_wide(a, b).0;
+    i64::try_from((a / g * b).abs()).ok()
+}
+
+/// Returns the least common multiple of every value in `values`, or `None` on overflow.
+/// The lcm of nothing is 1
+pub fn lcm_all(values: impl IntoIterator<Item = i64>) -> Option<i64> {
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// The extended Euclidean algorithm.
+///
+/// Returns `(g, x, y)` where `g` is the gcd of `a` and `b` and `a * x + b * y == g`
+///
+/// # Panics
+/// This function panics if the gcd is 2^63, like [`gcd`]
+#[track_caller]
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = ext_gcd_wide(a as i128, b as i128);
+    let g = i64::try_from(g).expect("gcd doesn't fit in an i64");
+    (g, x as i64, y as i64)
+}
+
+pub(crate) fn ext_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+fn mod_inv_wide(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = ext_gcd_wide(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Returns `x` in `0..m` such that `a * x == 1 (mod m)`, or `None` if `a` and `m` aren't coprime
+///
+/// # Panics
+/// This function panics if `m` is not positive
+#[track_caller]
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+    mod_inv_wide(a as i128, m as i128).map(|x| x as i64)
+}
+
+/// Returns `base ^ exp (mod m)` in `0..m`
+///
+/// # Panics
+/// This function panics if `m` is not positive
+#[track_caller]
+pub fn mod_pow(base: i64, exp: u64, m: i64) -> i64 {
+    assert!(m > 0, "modulus must be positive");
+    mod_pow_wide(base as i128, exp, m as i128) as i64
+}
+
+fn mod_pow_wide(base: i128, mut exp: u64, m: i128) -> i128 {
+    let mut base = base.rem_euclid(m);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result
+}
+
+/// Why a system of congruences passed to [`crt`] couldn't be solved
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CrtError {
+    /// Two of the congruences contradict each other
+    NoSolution,
+    /// The combined modulus doesn't fit in an `i64`
+    Overflow,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrtError::NoSolution => write!(f, "congruences have no common solution"),
+            CrtError::Overflow => write!(f, "combined modulus overflows an i64"),
+        }
+    }
+}
+
+impl std::error::Error for CrtError {}
+
+/// Solves a system of congruences `x == residue (mod modulus)` using the generalized Chinese
+/// remainder theorem, so the moduli don't have to be coprime.
+///
+/// Returns `(x, m)` where `x` is in `0..m` and every solution is `x + k * m`. `m` is the lcm of the
+/// moduli. An empty system returns `(0, 1)`
+///
+/// # Panics
+/// This function panics if any modulus is not positive
+#[track_caller]
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Result<(i64, i64), CrtError> {
+    let (mut x, mut m) = (0i128, 1i128);
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus must be positive");
+        let (residue, modulus) = (
+            (residue as i128).rem_euclid(modulus as i128),
+            modulus as i128,
+        );
+
+        let (g, p, _) = ext_gcd_wide(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return Err(CrtError::NoSolution);
+        }
+        let step = modulus / g;
+        let combined = m * step;
+        if combined > i64::MAX as i128 {
+            return Err(CrtError::Overflow);
+        }
+        // p is the inverse of m / g modulo step, so this moves x by a multiple of m onto residue
+        let k = (diff / g % step) * (p % step) % step;
+        x = (x + m * k).rem_euclid(combined);
+        m = combined;
+    }
+    Ok((x as i64, m as i64))
+}
+
+/// An integer modulo `M`, always stored in `0..M`.
+///
+/// `M` must be positive, which is checked at compile time:
+/// ```compile_fail
+/// # use util::math::ModInt;
+/// let x = ModInt::<0>::new(1);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: i64) -> Self {
+        const { assert!(M > 0, "modulus must be positive") };
+        Self((value as i128).rem_euclid(M as i128) as u64)
+    }
+
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        Self(mod_pow_wide(self.0 as i128, exp, M as i128) as u64)
+    }
+
+    /// Returns the multiplicative inverse, or `None` if this value and `M` aren't coprime
+    pub fn inv(self) -> Option<Self> {
+        mod_inv_wide(self.0 as i128, M as i128).map(|x| Self(x as u64))
+    }
+}
+
+impl<const M: u64> From<i64> for ModInt<M> {
+    fn from(value: i64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(((self.0 as u128 + rhs.0 as u128) % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.0 == 0 {
+            self
+        } else {
+            Self(M - self.0)
+        }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self((self.0 as u128 * rhs.0 as u128 % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    /// # Panics
+    /// This function panics if `rhs` has no inverse modulo `M`
+    #[track_caller]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv().expect("divisor has no inverse")
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const M: u64> DivAssign for ModInt<M> {
+    #[track_caller]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+/// The linear function `x -> a * x + b (mod m)`.
+///
+/// Card shuffles like 2019 day 22 are all of this form (dealing into a new stack is `-x - 1`,
+/// cutting `n` cards is `x - n`, dealing with increment `n` is `n * x`), so a whole shuffle
+/// composes into one `Lcg` that can be repeated a huge number of times with [`Lcg::repeat`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Lcg {
+    pub a: i64,
+    pub b: i64,
+    pub m: i64,
+}
+
+impl Lcg {
+    /// # Panics
+    /// This function panics if `m` is not positive
+    #[track_caller]
+    pub fn new(a: i64, b: i64, m: i64) -> Self {
+        assert!(m > 0, "modulus must be positive");
+        Self {
+            a: a.rem_euclid(m),
+            b: b.rem_euclid(m),
+            m,
+        }
+    }
+
+    /// The function that leaves every value where it is
+    pub fn identity(m: i64) -> Self {
+        Self::new(1, 0, m)
+    }
+
+    pub fn apply(&self, x: i64) -> i64 {
+        ((self.a as i128 * x as i128 + self.b as i128).rem_euclid(self.m as i128)) as i64
+    }
+
+    /// Returns the function that applies `self` and then `next`
+    ///
+    /// # Panics
+    /// This function panics if the two functions use different moduli
+    #[track_caller]
+    pub fn then(&self, next: &Lcg) -> Lcg {
+        assert_eq!(
+            self.m, next.m,
+            "cannot compose functions with different moduli"
+        );
+        let m = self.m as i128;
+        let a = next.a as i128 * self.a as i128 % m;
+        let b = (next.a as i128 * self.b as i128 + next.b as i128) % m;
+        Lcg::new(a as i64, b as i64, self.m)
+    }
+
+    /// Returns the function that applies `self` `times` times in a row
+    pub fn repeat(&self, mut times: u64) -> Lcg {
+        let mut result = Lcg::identity(self.m);
+        let mut base = *self;
+        while times > 0 {
+            if times & 1 == 1 {
+                result = result.then(&base);
+            }
+            base = base.then(&base);
+            times >>= 1;
+        }
+        result
+    }
+
+    /// Returns the function that undoes `self`, or `None` if `a` has no inverse modulo `m`
+    pub fn inverse(&self) -> Option<Lcg> {
+        let inv = mod_inv(self.a, self.m)?;
+        let b = -(inv as i128) * self.b as i128 % self.m as i128;
+        Some(Lcg::new(inv, b as i64, self.m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_inverse() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm_all([2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm(i64::MAX, 2), None);
+        assert_eq!(lcm(i64::MIN, 1), None);
+        assert_eq!(lcm(i64::MIN, 0), Some(0));
+        assert_eq!(lcm(i64::MIN, i64::MIN), None);
+        assert_eq!(gcd(i64::MIN, 6), 2);
+
+        let (g, x, y) = ext_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(4, 8), None);
+        assert_eq!(mod_pow(2, 62, 1_000_000_007), (1i64 << 62) % 1_000_000_007);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd doesn't fit in an i64")]
+    fn gcd_too_large() {
+        gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn bus_schedule() {
+        // 2020 day 13: bus `id` at offset `i` leaves at t + i
+        let ids = "7,13,x,x,59,x,31,19";
+        let congruences = ids
+            .split(',')
+            .enumerate()
+            .filter_map(|(i, id)| id.parse::<i64>().ok().map(|id| (-(i as i64), id)));
+        assert_eq!(crt(congruences).map(|(t, _)| t), Ok(1068781));
+    }
+
+    #[test]
+    fn crt_non_coprime() {
+        assert_eq!(crt([(2, 4), (4, 6)]), Ok((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), Err(CrtError::NoSolution));
+        assert_eq!(crt(Vec::<(i64, i64)>::new()), Ok((0, 1)));
+
+        let big = 4_611_686_018_427_387_847; // prime just below 2^62
+        assert_eq!(crt([(1, big), (2, 5)]), Err(CrtError::Overflow));
+    }
+
+    #[test]
+    fn mod_int() {
+        type M7 = ModInt<7>;
+        let a = M7::new(-1);
+        assert_eq!(a.value(), 6);
+        assert_eq!(a + M7::new(3), M7::new(2));
+        assert_eq!(M7::new(2) - M7::new(5), M7::new(4));
+        assert_eq!(M7::new(3) * M7::new(5), M7::new(1));
+        assert_eq!(M7::new(1) / M7::new(3), M7::new(5));
+        assert_eq!(M7::new(3).pow(6), M7::new(1));
+        assert_eq!(ModInt::<8>::new(4).inv(), None);
+    }
+
+    #[test]
+    fn shuffle() {
+        let deal_new_stack = Lcg::new(-1, -1, 10);
+        let cut = |n: i64| Lcg::new(1, -n, 10);
+        let deal_increment = |n| Lcg::new(n, 0, 10);
+
+        // maps the starting position of each card to its final position
+        let shuffle = deal_increment(7)
+            .then(&deal_new_stack)
+            .then(&deal_new_stack);
+        let mut deck = [0; 10];
+        for card in 0..10 {
+            deck[shuffle.apply(card) as usize] = card;
+        }
+        assert_eq!(deck, [0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+
+        let shuffle = cut(6).then(&deal_increment(7)).then(&deal_new_stack);
+        let mut deck = [0; 10];
+        for card in 0..10 {
+            deck[shuffle.apply(card) as usize] = card;
+        }
+        assert_eq!(deck, [3, 0, 7, 4, 1, 8, 5, 2, 9, 6]);
+
+        let inverse = shuffle.inverse().unwrap();
+        let mut position = 4;
+        for _ in 0..1000 {
+            position = shuffle.apply(position);
+        }
+        assert_eq!(shuffle.repeat(1000).apply(4), position);
+        assert_eq!(inverse.repeat(1000).apply(position), 4);
+    }
+}
@@ -167,13 +167,54 @@ where
 }
 
 impl<T> Matrix<T> {
+    /// Creates a matrix with `cols` columns from the row major items of `it`
+    ///
+    /// # Panics
+    /// This function panics if `cols` is 0 or if `it` ends part way through a row
+    #[track_caller]
     pub fn new_from_iterator(cols: usize, it: impl Iterator<Item = T>) -> Self {
+        assert!(cols != 0, "a matrix needs at least one column");
         let data: Vec<T> = it.collect();
-        Self {
+        assert!(
+            data.len().is_multiple_of(cols),
+            "{} items don't fill rows of {} columns",
+            data.len(),
+            cols
+        );
+        let rows = data.len() / cols;
+        Self { data, cols, rows }
+    }
+
+    /// Creates a `rows` by `cols` matrix where each cell is initialized by calling `f(row, col)`
+    /// in row major order
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut data = Vec::with_capacity(rows * cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                data.push(f(row, col));
+            }
+        }
+        Self { data, cols, rows }
+    }
+
+    /// Creates a matrix from a list of rows, which must all be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let row_count = rows.len();
+        let cols = rows.first().map(|r| r.len()).unwrap_or(0);
+        if let Some(bad) = rows.iter().position(|r| r.len() != cols) {
+            return Err(format!(
+                "Non square matrix. row {} has {} cols, expected {}",
+                bad,
+                rows[bad].len(),
+                cols
+            ));
+        }
+        let data = rows.into_iter().flatten().collect();
+        Ok(Self {
             data,
             cols,
-            rows: 1,
-        }
+            rows: row_count,
+        })
     }
 
     pub fn len(&self) -> usize {
@@ -640,6 +681,29 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// Converts from the compass letters `N`, `E`, `S` and `W` (either case), with north being
+    /// [`Direction::Up`]
+    pub fn from_compass(value: u8) -> Option<Direction> {
+        Some(match value {
+            b'n' | b'N' => Direction::Up,
+            b'e' | b'E' => Direction::Right,
+            b's' | b'S' => Direction::Down,
+            b'w' | b'W' => Direction::Left,
+            _ => return None,
+        })
+    }
+
+    /// Converts from the arrows `^`, `v`, `<` and `>`
+    pub fn from_arrow(value: u8) -> Option<Direction> {
+        Some(match value {
+            b'^' => Direction::Up,
+            b'v' => Direction::Down,
+            b'<' => Direction::Left,
+            b'>' => Direction::Right,
+            _ => return None,
+        })
+    }
+
     /// Returns the "opposite" dimension.
     /// Useful with traversing all cols in a row, or all rows in a col with
     /// [`Matrix::corner_for_traverse`] and [`Matrix::traverse`] programmatically
@@ -663,6 +727,59 @@ impl Direction {
         }
     }
 
+    /// Returns the direction 90 degrees clockwise from this one
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    /// Returns the direction 90 degrees counter-clockwise from this one
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    /// Returns the direction pointing the opposite way
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// Rotates clockwise by `quarter_turns`. Negative values rotate counter-clockwise
+    pub fn rotate(self, quarter_turns: isize) -> Direction {
+        let mut result = self;
+        for _ in 0..quarter_turns.rem_euclid(4) {
+            result = result.turn_right();
+        }
+        result
+    }
+
+    /// Rotates clockwise by `degrees`. Negative values rotate counter-clockwise.
+    /// Returns `None` if `degrees` is not a multiple of 90
+    pub fn rotate_degrees(self, degrees: isize) -> Option<Direction> {
+        if degrees % 90 != 0 {
+            return None;
+        }
+        Some(self.rotate(degrees / 90))
+    }
+
+    /// Returns true for [`Direction::Up`] and [`Direction::Down`]
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
     pub fn all() -> [Direction; 4] {
         [
             Direction::Up,
@@ -740,6 +857,25 @@ impl TryFrom<char> for Direction {
     }
 }
 
+/// Parses a direction from a string containing a single direction character: one accepted by
+/// the `u8` implementation of `TryFrom`, by [`Direction::from_compass`] or by
+/// [`Direction::from_arrow`]
+impl FromStr for Direction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let &[b] = s.as_bytes() else {
+            return Err(());
+        };
+        Direction::try_from(b)
+            .ok()
+            .or_else(|| Direction::from_compass(b))
+            .or_else(|| Direction::from_arrow(b))
+            .ok_or(())
+    }
+}
+
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Point {
     pub row: usize,
@@ -821,8 +957,25 @@ impl SignedPoint {
     }
 
     pub fn offset(self, direction: Direction) -> Self {
+        self.offset_by(direction, 1)
+    }
+
+    /// Moves this point `count` cells in `direction`
+    pub fn offset_by(self, direction: Direction, count: isize) -> Self {
         let (row_delta, col_delta) = direction.to_unit_offsets();
-        Self::new(self.row + row_delta, self.col + col_delta)
+        Self::new(self.row + row_delta * count, self.col + col_delta * count)
+    }
+
+    /// Rotates this point clockwise around the origin by `quarter_turns`.
+    /// Negative values rotate counter-clockwise
+    pub fn rotate(self, quarter_turns: isize) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => Self::new(self.col, -self.row),
+            2 => Self::new(-self.row, -self.col),
+            3 => Self::new(-self.col, self.row),
+            _ => unreachable!(),
+        }
     }
 
     /// Returns true if this point has one zero axis and one non-zero axis
@@ -863,6 +1016,28 @@ impl std::ops::Sub for SignedPoint {
     }
 }
 
+impl std::ops::Add for SignedPoint {
+    type Output = SignedPoint;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl std::ops::AddAssign for SignedPoint {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl std::ops::Mul<isize> for SignedPoint {
+    type Output = SignedPoint;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.row * rhs, self.col * rhs)
+    }
+}
+
 /// A iterator that can be converted to yield (row, col) information along with the cell's value
 pub trait IntoEnumeratedCells<T>: Sized + Iterator<Item = T> + EnumeratedCellsIter {
     fn enumerate_cells(self) -> EnumeratedCells<T, Self>;
@@ -1019,6 +1194,397 @@ where
     }
 }
 
+/// One of the 8 ways a matrix can be rotated and flipped (the dihedral group of a square).
+///
+/// The matrix is first flipped horizontally if `flipped` is set, then rotated clockwise by
+/// `quarter_turns`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Orientation {
+    pub flipped: bool,
+    /// Number of 90 degree clockwise turns, in the range `0..4`
+    pub quarter_turns: u8,
+}
+
+impl Orientation {
+    /// The orientation that leaves a matrix unchanged
+    pub const IDENTITY: Orientation = Orientation {
+        flipped: false,
+        quarter_turns: 0,
+    };
+
+    /// Returns all 8 orientations, starting with [`Orientation::IDENTITY`]
+    pub fn all() -> [Orientation; 8] {
+        let mut result = [Orientation::IDENTITY; 8];
+        for (i, o) in result.iter_mut().enumerate() {
+            o.flipped = i >= 4;
+            o.quarter_turns = (i % 4) as u8;
+        }
+        result
+    }
+
+    /// Returns where the cell at `point` in a `rows` by `cols` matrix ends up after applying this
+    /// orientation
+    pub fn map_point(self, rows: usize, cols: usize, point: Point) -> Point {
+        let (mut rows, mut cols) = (rows, cols);
+        let Point { mut row, mut col } = point;
+        if self.flipped {
+            col = cols - 1 - col;
+        }
+        for _ in 0..self.quarter_turns % 4 {
+            // rotating clockwise moves the old left column to the new top row
+            (row, col) = (col, rows - 1 - row);
+            (rows, cols) = (cols, rows);
+        }
+        Point::new(row, col)
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone,
+{
+    /// Returns a copy of this matrix with rows and columns swapped
+    pub fn transpose(&self) -> Matrix<T> {
+        Matrix::from_fn(self.cols(), self.rows(), |row, col| {
+            self.get(col, row).clone()
+        })
+    }
+
+    /// Returns a copy of this matrix rotated 90 degrees clockwise
+    pub fn rotate_90(&self) -> Matrix<T> {
+        let rows = self.rows();
+        Matrix::from_fn(self.cols(), rows, |row, col| {
+            self.get(rows - 1 - col, row).clone()
+        })
+    }
+
+    /// Returns a copy of this matrix rotated 180 degrees
+    pub fn rotate_180(&self) -> Matrix<T> {
+        let (rows, cols) = (self.rows(), self.cols());
+        Matrix::from_fn(rows, cols, |row, col| {
+            self.get(rows - 1 - row, cols - 1 - col).clone()
+        })
+    }
+
+    /// Returns a copy of this matrix rotated 270 degrees clockwise (90 degrees counter-clockwise)
+    pub fn rotate_270(&self) -> Matrix<T> {
+        let cols = self.cols();
+        Matrix::from_fn(cols, self.rows(), |row, col| {
+            self.get(col, cols - 1 - row).clone()
+        })
+    }
+
+    /// Rotates clockwise by `quarter_turns`. Negative values rotate counter-clockwise
+    pub fn rotate(&self, quarter_turns: isize) -> Matrix<T> {
+        match quarter_turns.rem_euclid(4) {
+            0 => self.clone(),
+            1 => self.rotate_90(),
+            2 => self.rotate_180(),
+            3 => self.rotate_270(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns a copy of this matrix mirrored left to right
+    pub fn flip_horizontal(&self) -> Matrix<T> {
+        let cols = self.cols();
+        Matrix::from_fn(self.rows(), cols, |row, col| {
+            self.get(row, cols - 1 - col).clone()
+        })
+    }
+
+    /// Returns a copy of this matrix mirrored top to bottom
+    pub fn flip_vertical(&self) -> Matrix<T> {
+        let rows = self.rows();
+        Matrix::from_fn(rows, self.cols(), |row, col| {
+            self.get(rows - 1 - row, col).clone()
+        })
+    }
+
+    /// Returns a copy of this matrix transformed by `orientation`
+    pub fn oriented(&self, orientation: Orientation) -> Matrix<T> {
+        let quarter_turns = orientation.quarter_turns as isize;
+        if orientation.flipped {
+            self.flip_horizontal().rotate(quarter_turns)
+        } else {
+            self.rotate(quarter_turns)
+        }
+    }
+
+    /// Returns an iterator over all 8 rotations and reflections of this matrix along with the
+    /// orientation used to produce each one
+    pub fn orientations(&self) -> impl Iterator<Item = (Orientation, Matrix<T>)> + '_ {
+        Orientation::all()
+            .into_iter()
+            .map(|o| (o, self.oriented(o)))
+    }
+
+    /// Returns the cells along one edge of this matrix.
+    ///
+    /// Top and bottom edges are read left to right, left and right edges are read top to bottom
+    ///
+    /// # Panics
+    /// This function panics if this matrix has no rows
+    #[track_caller]
+    pub fn edge(&self, side: Direction) -> Vec<T> {
+        assert!(self.rows() != 0, "an empty matrix has no edges");
+        match side {
+            Direction::Up => self.first_row().cloned().collect(),
+            Direction::Down => self.last_row().cloned().collect(),
+            Direction::Left => self.column(0).cloned().collect(),
+            Direction::Right => self.column(self.cols() - 1).cloned().collect(),
+        }
+    }
+
+    /// Returns all four edges in the order up, right, down, left. See [`Matrix::edge`]
+    ///
+    /// # Panics
+    /// This function panics if this matrix has no rows
+    #[track_caller]
+    pub fn edges(&self) -> [Vec<T>; 4] {
+        [
+            self.edge(Direction::Up),
+            self.edge(Direction::Right),
+            self.edge(Direction::Down),
+            self.edge(Direction::Left),
+        ]
+    }
+
+    /// Returns an owned copy of the `rows` by `cols` region whose top left corner is at
+    /// [`row`, `col`]
+    ///
+    /// # Panics
+    /// This function panics if the region is not entirely inside this matrix
+    #[track_caller]
+    pub fn crop(&self, row: usize, col: usize, rows: usize, cols: usize) -> Matrix<T> {
+        self.view(row, col, rows, cols).to_matrix()
+    }
+
+    /// Removes `amount` cells from every side of this matrix
+    ///
+    /// # Panics
+    /// This function panics if this matrix has fewer than `2 * amount` rows or columns
+    #[track_caller]
+    pub fn trim_border(&self, amount: usize) -> Matrix<T> {
+        assert!(
+            amount <= self.rows() / 2 && amount <= self.cols() / 2,
+            "can't trim {} cells from every side of a {}x{} matrix",
+            amount,
+            self.rows(),
+            self.cols()
+        );
+        self.crop(
+            amount,
+            amount,
+            self.rows() - 2 * amount,
+            self.cols() - 2 * amount,
+        )
+    }
+
+    /// Surrounds this matrix with `amount` cells of `value` on every side
+    pub fn pad(&self, amount: usize, value: T) -> Matrix<T> {
+        self.pad_sides(amount, amount, amount, amount, value)
+    }
+
+    /// Adds the given number of rows or columns of `value` to each side of this matrix
+    pub fn pad_sides(
+        &self,
+        top: usize,
+        bottom: usize,
+        left: usize,
+        right: usize,
+        value: T,
+    ) -> Matrix<T> {
+        let rows = self.rows() + top + bottom;
+        let cols = self.cols() + left + right;
+        Matrix::from_fn(rows, cols, |row, col| {
+            match (row.checked_sub(top), col.checked_sub(left)) {
+                (Some(r), Some(c)) => self.try_get(r, c).unwrap_or(&value).clone(),
+                _ => value.clone(),
+            }
+        })
+    }
+
+    /// Combines a grid of equally sized tiles into one large matrix
+    ///
+    /// # Panics
+    /// This function panics if `tiles` is empty or the tiles are not all the same size
+    pub fn stitch(tiles: &Matrix<Matrix<T>>) -> Matrix<T> {
+        let tile_rows = tiles.get(0, 0).rows();
+        let tile_cols = tiles.get(0, 0).cols();
+        for (row, col, tile) in tiles.iter().enumerate_cells() {
+            if tile.rows() != tile_rows || tile.cols() != tile_cols {
+                panic!(
+                    "tile at row {}, col {} is {}x{}, expected {}x{}",
+                    row,
+                    col,
+                    tile.rows(),
+                    tile.cols(),
+                    tile_rows,
+                    tile_cols
+                );
+            }
+        }
+        Matrix::from_fn(
+            tiles.rows() * tile_rows,
+            tiles.cols() * tile_cols,
+            |row, col| {
+                tiles
+                    .get(row / tile_rows, col / tile_cols)
+                    .get(row % tile_rows, col % tile_cols)
+                    .clone()
+            },
+        )
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Returns an iterator over the cells in column `col`, from top to bottom
+    ///
+    /// # Panics
+    /// This function panics if `col` is outside of this matrix
+    #[track_caller]
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(
+            col < self.cols(),
+            "Column out of range! col {}, cols {}",
+            col,
+            self.cols()
+        );
+        self.data.iter().skip(col).step_by(self.cols)
+    }
+
+    /// Returns a borrowed view of the `rows` by `cols` region whose top left corner is at
+    /// [`row`, `col`]
+    ///
+    /// # Panics
+    /// This function panics if the region is not entirely inside this matrix
+    #[track_caller]
+    pub fn view(&self, row: usize, col: usize, rows: usize, cols: usize) -> MatrixView<'_, T> {
+        if row + rows > self.rows() || col + cols > self.cols() {
+            panic!(
+                "Sub matrix out of range! row {}, col {}, rows {}, cols {} in a {}x{} matrix",
+                row,
+                col,
+                rows,
+                cols,
+                self.rows(),
+                self.cols()
+            );
+        }
+        MatrixView {
+            mat: self,
+            row,
+            col,
+            rows,
+            cols,
+        }
+    }
+}
+
+/// A borrowed rectangular region of a [`Matrix`]. Created by [`Matrix::view`].
+///
+/// Coordinates passed to a view are relative to its top left corner
+#[derive(Clone, Copy, Debug)]
+pub struct MatrixView<'m, T> {
+    mat: &'m Matrix<T>,
+    row: usize,
+    col: usize,
+    rows: usize,
+    cols: usize,
+}
+
+impl<'m, T> MatrixView<'m, T> {
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn in_bounds(&self, row: usize, col: usize) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn try_get(&self, row: usize, col: usize) -> Option<&'m T> {
+        if self.in_bounds(row, col) {
+            self.mat.try_get(self.row + row, self.col + col)
+        } else {
+            None
+        }
+    }
+
+    #[track_caller]
+    pub fn get(&self, row: usize, col: usize) -> &'m T {
+        match self.try_get(row, col) {
+            Some(t) => t,
+            None => panic!(
+                "View index out of range! row {}, col {}, rows {}, cols {}",
+                row, col, self.rows, self.cols
+            ),
+        }
+    }
+
+    /// Returns the cells in row `row` of this view as a slice
+    ///
+    /// # Panics
+    /// This function panics if `row` is outside of this view
+    #[track_caller]
+    pub fn row(&self, row: usize) -> &'m [T] {
+        assert!(
+            row < self.rows,
+            "View row out of range! row {}, rows {}",
+            row,
+            self.rows
+        );
+        let start = self.mat.index(self.row + row, self.col);
+        &self.mat.data[start..start + self.cols]
+    }
+
+    /// Returns a row major iterator over this view, yielding `(row, col, value)` with coordinates
+    /// relative to the view
+    pub fn iter_cells(&self) -> impl Iterator<Item = (usize, usize, &'m T)> + '_ {
+        (0..self.rows).flat_map(move |row| {
+            self.row(row)
+                .iter()
+                .enumerate()
+                .map(move |(col, v)| (row, col, v))
+        })
+    }
+
+    /// Returns a row major iterator over this view
+    pub fn iter(&self) -> impl Iterator<Item = &'m T> + '_ {
+        (0..self.rows).flat_map(move |row| self.row(row).iter())
+    }
+
+    /// Returns a sub view of this view. Coordinates are relative to this view
+    #[track_caller]
+    pub fn view(&self, row: usize, col: usize, rows: usize, cols: usize) -> MatrixView<'m, T> {
+        if row + rows > self.rows || col + cols > self.cols {
+            panic!(
+                "Sub view out of range! row {}, col {}, rows {}, cols {} in a {}x{} view",
+                row, col, rows, cols, self.rows, self.cols
+            );
+        }
+        MatrixView {
+            mat: self.mat,
+            row: self.row + row,
+            col: self.col + col,
+            rows,
+            cols,
+        }
+    }
+
+    /// Copies the cells of this view into a new matrix
+    pub fn to_matrix(&self) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        Matrix::from_fn(self.rows, self.cols, |row, col| self.get(row, col).clone())
+    }
+}
+
 impl<T> Matrix<T>
 where
     T: std::fmt::Display,
@@ -1339,6 +1905,142 @@ mod tests {
         assert!(Direction::direction_between(1, 1, 2, 2).is_none());
     }
 
+    #[test]
+    fn rotate_direction() {
+        for dir in Direction::all() {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.rotate(2), dir.reverse());
+            assert_eq!(dir.rotate(-1), dir.turn_left());
+            assert_eq!(dir.rotate_degrees(270), Some(dir.turn_left()));
+            let (row, col) = dir.to_unit_offsets();
+            let rotated = SignedPoint::new(row, col).rotate(1);
+            assert_eq!(rotated.try_to_direction(), Some(dir.turn_right()));
+        }
+        assert_eq!(Direction::Up.rotate_degrees(45), None);
+    }
+
+    #[test]
+    fn parse_direction() {
+        for (s, dir) in [
+            ("N", Direction::Up),
+            ("^", Direction::Up),
+            ("e", Direction::Right),
+            (">", Direction::Right),
+            ("S", Direction::Down),
+            ("v", Direction::Down),
+            ("W", Direction::Left),
+            ("<", Direction::Left),
+            ("R", Direction::Right),
+        ] {
+            assert_eq!(s.parse::<Direction>(), Ok(dir));
+        }
+        assert!("NE".parse::<Direction>().is_err());
+        assert_eq!(Direction::try_from(b'N'), Err(()));
+        assert_eq!(Direction::try_from(b'<'), Err(()));
+        assert_eq!(Direction::from_compass(b'w'), Some(Direction::Left));
+        assert_eq!(Direction::from_compass(b'U'), None);
+        assert_eq!(Direction::from_arrow(b'v'), Some(Direction::Down));
+        assert_eq!(Direction::from_arrow(b'D'), None);
+    }
+
+    fn grid(s: &str) -> Matrix<u8> {
+        Matrix::new_from_chars(s).unwrap()
+    }
+
+    #[test]
+    fn rotate_and_flip() {
+        let mat = grid("abc\ndef");
+        assert_eq!(mat.transpose(), grid("ad\nbe\ncf"));
+        assert_eq!(mat.rotate_90(), grid("da\neb\nfc"));
+        assert_eq!(mat.rotate_180(), grid("fed\ncba"));
+        assert_eq!(mat.rotate_270(), grid("cf\nbe\nad"));
+        assert_eq!(mat.rotate(-1), mat.rotate_270());
+        assert_eq!(mat.flip_horizontal(), grid("cba\nfed"));
+        assert_eq!(mat.flip_vertical(), grid("def\nabc"));
+        assert_eq!(mat.rotate_90().rotate_90().rotate_90().rotate_90(), mat);
+    }
+
+    #[test]
+    fn all_orientations() {
+        let mat = grid("ab\ncd");
+        let mut all: Vec<_> = mat.orientations().map(|(_, m)| m).collect();
+        all.sort_by_key(|m| m.format_as_chars());
+        all.dedup();
+        assert_eq!(all.len(), 8);
+
+        let mat = grid("abc\ndef");
+        for (o, oriented) in mat.orientations() {
+            for (row, col, v) in mat.iter().enumerate_cells() {
+                let p = o.map_point(mat.rows(), mat.cols(), Point::new(row, col));
+                assert_eq!(oriented.get(p.row, p.col), v);
+            }
+        }
+    }
+
+    #[test]
+    fn views_and_edges() {
+        let mat = grid("abcd\nefgh\nijkl");
+        let view = mat.view(1, 1, 2, 2);
+        assert_eq!(view.to_matrix(), grid("fg\njk"));
+        assert_eq!(view.row(1), b"jk");
+        assert_eq!(*view.view(1, 0, 1, 1).get(0, 0), b'j');
+        assert_eq!(view.iter().copied().collect::<Vec<_>>(), b"fgjk");
+        assert_eq!(mat.crop(0, 2, 3, 2), grid("cd\ngh\nkl"));
+
+        assert_eq!(mat.edge(Direction::Up), b"abcd");
+        assert_eq!(mat.edge(Direction::Down), b"ijkl");
+        assert_eq!(mat.edge(Direction::Left), b"aei");
+        assert_eq!(mat.edge(Direction::Right), b"dhl");
+    }
+
+    #[test]
+    #[should_panic(expected = "View row out of range")]
+    fn view_row_out_of_range() {
+        let mat = grid("abcd\nefgh\nijkl");
+        // row 2 of the view is still inside the matrix, but not the view
+        mat.view(0, 0, 2, 2).row(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Column out of range")]
+    fn column_out_of_range() {
+        let _ = grid("ab\ncd").column(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no edges")]
+    fn edge_of_empty() {
+        Matrix::new_from_iterator(3, std::iter::empty::<u8>()).edge(Direction::Down);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't trim 2 cells")]
+    fn trim_too_much() {
+        grid("abc\ndef\nghi").trim_border(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "don't fill rows")]
+    fn partial_row() {
+        Matrix::new_from_iterator(3, 0..4);
+    }
+
+    #[test]
+    fn pad_and_stitch() {
+        let mat = grid("ab\ncd");
+        let padded = mat.pad(1, b'.');
+        assert_eq!(padded, grid("....\n.ab.\n.cd.\n...."));
+        assert_eq!(padded.trim_border(1), mat);
+        assert_eq!(mat.pad_sides(0, 1, 2, 0, b'.'), grid("..ab\n..cd\n...."));
+
+        let tiles = Matrix::from_rows(vec![
+            vec![mat.clone(), mat.rotate_90()],
+            vec![mat.flip_vertical(), mat.clone()],
+        ])
+        .unwrap();
+        assert_eq!(Matrix::stitch(&tiles), grid("abca\ncddb\ncdab\nabcd"));
+    }
+
     #[test]
     fn try_to_direction() {
         for x in -50..=50 {
@@ -0,0 +1,96 @@
+/// The letters that show up in puzzle answers drawn as art, in the 6 row font used by 2016, 2019,
+/// 2021 and 2022
+const LETTERS: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LETTER_HEIGHT: usize = 6;
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Reads the letters drawn by `art`, where `#` or `█` are lit and anything else is dark.
+///
+/// Letters must be separated by at least one dark column. Returns `None` if the art isn't 6 rows
+/// tall or contains a shape that isn't a known letter
+pub fn ocr(art: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .skip_while(|row: &Vec<bool>| !row.contains(&true))
+        .collect();
+    let end = rows.iter().rposition(|row| row.contains(&true))? + 1;
+    let rows = &rows[..end];
+    if rows.len() != LETTER_HEIGHT {
+        return None;
+    }
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |row: usize, col: usize| rows[row].get(col).copied().unwrap_or(false);
+    let column_lit = |col: usize| (0..LETTER_HEIGHT).any(|row| lit(row, col));
+
+    let mut text = String::new();
+    let mut col = 0;
+    while col < width {
+        if !column_lit(col) {
+            col += 1;
+            continue;
+        }
+        let start = col;
+        while col < width && column_lit(col) {
+            col += 1;
+        }
+        let glyph: Vec<String> = (0..LETTER_HEIGHT)
+            .map(|row| {
+                (start..col)
+                    .map(|c| if lit(row, c) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let glyph = glyph.join("\n");
+        let &(letter, _) = LETTERS.iter().find(|(_, shape)| *shape == glyph)?;
+        text.push(letter);
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_letters() {
+        let art = "\
+###..####..##..###..#..#.####..##..#..#.
+#..#.#....#..#.#..#.#..#.#....#..#.#..#.
+#..#.###..#....#..#.####.###..#....####.
+###..#....#.##.###..#..#.#....#.##.#..#.
+#....#....#..#.#....#..#.#....#..#.#..#.
+#....####..###.#....#..#.####..###.#..#.";
+        assert_eq!(ocr(art).as_deref(), Some("PEGPHEGH"));
+        assert_eq!(ocr(&art.replace("####..##..###", "#.....##..###")), None);
+
+        let art = "\n#..#.####.\n#..#.#....\n####.###..\n#..#.#....\n#..#.#....\n#..#.#....\n\n";
+        assert_eq!(ocr(art).as_deref(), Some("HF"));
+        let art = art.replace('#', "█").replace('.', " ");
+        assert_eq!(ocr(&art).as_deref(), Some("HF"));
+        assert_eq!(ocr("#"), None);
+    }
+}
@@ -1,124 +1,453 @@
 use std::str::FromStr;
 
-/// Simple parser interface for creating AOC style recursive decent parsers
+use crate::ParseError;
+
+/// Simple parser interface for creating AOC style recursive decent parsers.
+///
+/// Every method that can fail returns a [`ParseError`] pointing at the line and column where
+/// parsing stopped, and leaves the parser where it was. Use [`Parser::attempt`] or
+/// [`Parser::checkpoint`] to backtrack over more than one step
+#[derive(Clone, Debug)]
 pub struct Parser<'s> {
-    inner: &'s str,
+    source: &'s str,
+    start: usize,
+    end: usize,
+}
+
+/// A saved parser position, see [`Parser::checkpoint`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    start: usize,
+    end: usize,
 }
 
 impl<'s> Parser<'s> {
     pub fn new(s: &'s str) -> Self {
-        Self { inner: s }
+        Self {
+            source: s,
+            start: 0,
+            end: s.len(),
+        }
+    }
+
+    /// Returns the unparsed part of the input
+    pub fn as_str(&self) -> &'s str {
+        &self.source[self.start..self.end]
+    }
+
+    /// Returns the byte offset of the next character in the original input
+    pub fn position(&self) -> usize {
+        self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Saves the current position so it can be returned to with [`Parser::restore`]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            start: self.start,
+            end: self.end,
+        }
+    }
+
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.start = checkpoint.start;
+        self.end = checkpoint.end;
+    }
+
+    /// Runs `f`, moving back to where the parser was before if it fails
+    pub fn attempt<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let checkpoint = self.checkpoint();
+        let result = f(self);
+        if result.is_err() {
+            self.restore(checkpoint);
+        }
+        result
+    }
+
+    /// Creates an error at the current position saying that `expected` was expected
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.start, expected)
+    }
+
+    fn error_at(&self, offset: usize, expected: impl Into<String>) -> ParseError {
+        // point at the start of the character containing `offset`
+        let offset = (0..=offset)
+            .rev()
+            .find(|&i| self.source.is_char_boundary(i))
+            .unwrap_or(0);
+        let before = &self.source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let rest = &self.source[offset..self.end];
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: offset - line_start + 1,
+            expected: expected.into(),
+            found: rest.lines().next().unwrap_or("").to_owned(),
+        }
+    }
+
+    /// Consumes and returns the next character, which must be ascii. Use [`Parser::next_char`]
+    /// for any character
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<u8, ParseError> {
+        let b = self
+            .try_peek()
+            .filter(u8::is_ascii)
+            .ok_or_else(|| self.error("an ascii character"))?;
+        self.start += 1;
+        Ok(b)
+    }
+
+    /// Removes and returns the last character of the input, which must be ascii
+    pub fn last(&mut self) -> Result<u8, ParseError> {
+        let b = self
+            .as_str()
+            .as_bytes()
+            .last()
+            .copied()
+            .filter(u8::is_ascii)
+            .ok_or_else(|| {
+                self.error_at(
+                    self.end.saturating_sub(1).max(self.start),
+                    "an ascii character at the end",
+                )
+            })?;
+        self.end -= 1;
+        Ok(b)
+    }
+
+    pub fn next_char(&mut self) -> Result<char, ParseError> {
+        let c = self
+            .as_str()
+            .chars()
+            .next()
+            .ok_or_else(|| self.error("any character"))?;
+        self.start += c.len_utf8();
+        Ok(c)
+    }
+
+    /// Parses an unsigned number made of ascii digits
+    pub fn next_number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let len = self.digits_len(0);
+        self.parse_prefix(len, "a number")
+    }
+
+    /// Parses a number with an optional leading `+` or `-`
+    pub fn next_signed<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let sign = self.sign_len(0);
+        let len = self.digits_len(sign);
+        self.parse_prefix(len, "a signed number")
+    }
+
+    /// Parses a decimal number like `-1.5`, `3` or `2.5e-3`
+    pub fn next_float<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let bytes = self.as_str().as_bytes();
+        let mut len = self.digits_len(self.sign_len(0));
+        if bytes.get(len) == Some(&b'.') {
+            len = self.digits_len(len + 1);
+        }
+        if matches!(bytes.get(len), Some(b'e' | b'E')) {
+            let exponent = self.digits_len(self.sign_len(len + 1));
+            if exponent > self.sign_len(len + 1) {
+                len = exponent;
+            }
+        }
+        self.parse_prefix(len, "a decimal number")
     }
 
-    pub fn next(&mut self) -> u8 {
-        let b = self.inner.as_bytes()[0];
-        self.inner = &self.inner[1..];
-        b
+    fn sign_len(&self, offset: usize) -> usize {
+        match self.as_str().as_bytes().get(offset) {
+            Some(b'+' | b'-') => offset + 1,
+            _ => offset,
+        }
     }
 
-    pub fn last(&mut self) -> u8 {
-        let last = self.inner.len() - 1;
-        let b = self.inner.as_bytes()[last];
-        self.inner = &self.inner[..last];
-        b
+    /// Returns the offset of the first non digit at or after `offset`
+    fn digits_len(&self, offset: usize) -> usize {
+        let bytes = self.as_str().as_bytes();
+        offset
+            + bytes[offset.min(bytes.len())..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
     }
 
-    pub fn next_char(&mut self) -> char {
-        let b = self.inner.chars().next().unwrap();
-        self.inner = &self.inner[b.len_utf8()..];
-        b
+    fn parse_prefix<T: FromStr>(&mut self, len: usize, expected: &str) -> Result<T, ParseError> {
+        let value = self.as_str()[..len]
+            .parse()
+            .map_err(|_| self.error(expected))?;
+        self.start += len;
+        Ok(value)
     }
 
-    pub fn next_number<T>(&mut self) -> T
-    where
-        T: FromStr,
-        T::Err: std::fmt::Debug,
-    {
-        let s = self.advance_to(|c| !c.is_ascii_digit());
-        s.parse().unwrap()
+    /// Consumes characters while `f` returns true, returning them
+    pub fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &'s str {
+        self.advance_to(|c| !f(c))
     }
 
     /// Advances in the string until `f` returns true, returning the substring between the start
     /// and the character one before `f` returned true on
-    pub fn advance_to(&mut self, f: impl Fn(u8) -> bool) -> &str {
-        for (i, c) in self.inner.bytes().enumerate() {
-            if f(c) {
-                return self.advance(i);
-            }
+    pub fn advance_to(&mut self, f: impl Fn(u8) -> bool) -> &'s str {
+        let len = self.as_str().bytes().position(f).unwrap_or(self.len());
+        self.advance(len)
+    }
+
+    /// Consumes and returns the next `count` bytes
+    ///
+    /// # Panics
+    /// This function panics if fewer than `count` bytes are left
+    pub fn advance(&mut self, count: usize) -> &'s str {
+        let s = &self.as_str()[..count];
+        self.start += count;
+        s
+    }
+
+    /// Consumes an identifier made of ascii letters, digits and underscores that doesn't start with
+    /// a digit
+    pub fn ident(&mut self) -> Result<&'s str, ParseError> {
+        if !self.peek_is(|c| c.is_ascii_alphabetic() || c == b'_') {
+            return Err(self.error("an identifier"));
         }
-        // Return rest
-        self.advance(self.inner.len())
+        Ok(self.take_while(|c| c.is_ascii_alphanumeric() || c == b'_'))
     }
 
-    pub fn advance(&mut self, count: usize) -> &str {
-        let (s, rest) = self.inner.split_at(count);
-        self.inner = rest;
-        return s;
+    /// Skips over any whitespace, including newlines
+    pub fn skip_whitespace(&mut self) -> &'s str {
+        self.take_while(|c| c.is_ascii_whitespace())
     }
 
-    pub fn expect(&mut self, expected: u8) {
-        let s = self.inner;
-        let next = self.next();
-        if next != expected {
-            panic!(
-                "expected {}, got {}, in {s}",
-                expected as char, next as char
-            );
+    /// Consumes at least one whitespace character
+    pub fn expect_whitespace(&mut self) -> Result<(), ParseError> {
+        if self.skip_whitespace().is_empty() {
+            return Err(self.error("whitespace"));
         }
+        Ok(())
     }
 
-    pub fn expect_last(&mut self, expected: u8) {
-        let s = self.inner;
-        let next = self.last();
-        if next != expected {
-            panic!(
-                "expected last {}, got {}, in {s}",
-                expected as char, next as char
-            );
+    /// Consumes `expected`, which must be ascii. Use [`Parser::try_consume`] for any character
+    pub fn expect(&mut self, expected: u8) -> Result<(), ParseError> {
+        if !expected.is_ascii() || self.try_peek() != Some(expected) {
+            return Err(self.error(format!("`{}`", expected as char)));
         }
+        self.start += 1;
+        Ok(())
     }
 
-    pub fn peek(&self) -> u8 {
-        self.inner.as_bytes()[0]
+    pub fn expect_last(&mut self, expected: u8) -> Result<(), ParseError> {
+        if !expected.is_ascii() || self.as_str().as_bytes().last() != Some(&expected) {
+            return Err(self.error_at(
+                self.end.saturating_sub(1).max(self.start),
+                format!("`{}` at the end", expected as char),
+            ));
+        }
+        self.end -= 1;
+        Ok(())
+    }
+
+    pub fn expect_str(&mut self, expected: &str) -> Result<(), ParseError> {
+        if !self.try_consume_str(expected) {
+            return Err(self.error(format!("`{}`", expected)));
+        }
+        Ok(())
+    }
+
+    /// Succeeds only if all input has been consumed
+    pub fn expect_end(&mut self) -> Result<(), ParseError> {
+        if !self.is_empty() {
+            return Err(self.error("end of input"));
+        }
+        Ok(())
+    }
+
+    pub fn peek(&self) -> Result<u8, ParseError> {
+        self.try_peek().ok_or_else(|| self.error("any character"))
     }
 
     pub fn try_peek(&self) -> Option<u8> {
-        self.inner.as_bytes().get(0).copied()
+        self.as_str().as_bytes().first().copied()
+    }
+
+    /// Returns true if the input starts with `s`
+    pub fn peek_str(&self, s: &str) -> bool {
+        self.as_str().starts_with(s)
     }
 
-    /// Consumes the next character if it matches `pattern`.
-    /// If self is empty or the character is not `pattern`, self is unchanged and Err(()) is
-    /// returned
-    pub fn try_consume(&mut self, pattern: impl Into<char>) -> Result<(), ()> {
+    /// Consumes the next character if it matches `pattern`, returning true if it did
+    pub fn try_consume(&mut self, pattern: impl Into<char>) -> bool {
         let pattern = pattern.into();
-        match self.inner.chars().next() {
-            Some(c) if c == pattern => {
-                let _ = self.next_char();
-                Ok(())
-            }
-            Some(_) => Err(()),
-            None => Err(()),
+        self.try_consume_str(pattern.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Consumes `s` if the input starts with it, returning true if it did
+    pub fn try_consume_str(&mut self, s: &str) -> bool {
+        let found = self.peek_str(s);
+        if found {
+            self.start += s.len();
         }
+        found
     }
 
     pub fn peek_is_digit(&self) -> bool {
         self.peek_is(|c| c.is_ascii_digit())
-        //self.try_peek().map(|c| c.is_ascii_digit()).unwrap_or(false)
     }
 
     pub fn peek_is(&self, f: impl Fn(u8) -> bool) -> bool {
         self.try_peek().map(f).unwrap_or(false)
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
+    /// Parses zero or more items separated by `sep`.
+    ///
+    /// Returns no items if the first item fails right where it started. Any other failure,
+    /// like an item that fails part way through or one after a separator, is returned
+    pub fn sep_by<T>(
+        &mut self,
+        sep: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = Vec::new();
+        let start = self.checkpoint();
+        match item(self) {
+            Ok(first) => items.push(first),
+            Err(e) => {
+                self.restore(start);
+                let here = self.error("");
+                if (e.line, e.column) == (here.line, here.column) {
+                    return Ok(items);
+                }
+                return Err(e);
+            }
+        }
+        while self.try_consume_str(sep) {
+            items.push(item(self)?);
+        }
+        Ok(items)
     }
 
-    pub fn len(&self) -> usize {
-        self.inner.len()
+    /// Parses `open`, then `inner`, then `close`, returning what `inner` produced
+    pub fn delimited<T>(
+        &mut self,
+        open: &str,
+        close: &str,
+        inner: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        self.attempt(|p| {
+            p.expect_str(open)?;
+            let value = inner(p)?;
+            p.expect_str(close)?;
+            Ok(value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Packet {
+        Number(u32),
+        List(Vec<Packet>),
+    }
+
+    fn packet(p: &mut Parser) -> Result<Packet, ParseError> {
+        if p.peek_is_digit() {
+            Ok(Packet::Number(p.next_number()?))
+        } else {
+            p.delimited("[", "]", |p| p.sep_by(",", packet))
+                .map(Packet::List)
+        }
+    }
+
+    #[test]
+    fn nested_packets() {
+        let mut p = Parser::new("[1,[2,[]],10]");
+        assert_eq!(
+            packet(&mut p),
+            Ok(Packet::List(vec![
+                Packet::Number(1),
+                Packet::List(vec![Packet::Number(2), Packet::List(vec![])]),
+                Packet::Number(10),
+            ]))
+        );
+        assert!(p.expect_end().is_ok());
+
+        let mut p = Parser::new("[1,2\n[3,]");
+        let err = packet(&mut p).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+        assert_eq!(err.expected, "`]`");
+        assert_eq!(p.position(), 0);
+
+        let mut p = Parser::new("[1,[2,]]");
+        let err = packet(&mut p).unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (7, "]]"));
+
+        // the bad item is reported, not the outer list stopping early
+        let mut p = Parser::new("[[1,]]");
+        let err = packet(&mut p).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "`[`"));
+    }
+
+    #[test]
+    fn numbers() {
+        let mut p = Parser::new("-12,+3,4.5,-1e3,7.x");
+        assert_eq!(p.next_signed::<i32>(), Ok(-12));
+        p.expect(b',').unwrap();
+        assert_eq!(p.next_signed::<i32>(), Ok(3));
+        p.expect(b',').unwrap();
+        assert_eq!(p.next_float::<f64>(), Ok(4.5));
+        p.expect(b',').unwrap();
+        assert_eq!(p.next_number::<u32>().unwrap_err().expected, "a number");
+        assert_eq!(p.next_float::<f64>(), Ok(-1000.0));
+        p.expect(b',').unwrap();
+        assert_eq!(p.next_float::<f64>(), Ok(7.0));
+        assert_eq!(p.as_str(), "x");
+    }
+
+    #[test]
+    fn words_and_backtracking() {
+        let mut p = Parser::new("add  x_1 to y\nsub");
+        let start = p.checkpoint();
+        assert_eq!(p.ident(), Ok("add"));
+        assert!(p.expect_whitespace().is_ok());
+        assert_eq!(p.ident(), Ok("x_1"));
+        assert!(p.peek_str(" to"));
+        assert!(!p.try_consume_str(" from"));
+        assert!(p.try_consume_str(" to "));
+        assert_eq!(p.ident(), Ok("y"));
+        p.skip_whitespace();
+        assert_eq!(p.error("x").line, 2);
+        assert!(p.expect_str("mul").is_err());
+        assert_eq!(p.as_str(), "sub");
+
+        p.restore(start);
+        assert_eq!(p.as_str(), "add  x_1 to y\nsub");
+        assert!(p.expect_last(b'b').is_ok());
+        assert_eq!(p.last(), Ok(b'u'));
+        assert!(p.try_consume('a'));
+        assert_eq!(p.next(), Ok(b'd'));
+        assert_eq!(p.next_char(), Ok('d'));
+        assert_eq!(p.ident().unwrap_err().expected, "an identifier");
     }
 
-    pub fn as_str(&self) -> &str {
-        self.inner
+    #[test]
+    fn non_ascii() {
+        let mut p = Parser::new("é1ü");
+        assert_eq!(p.next().unwrap_err().expected, "an ascii character");
+        assert_eq!(p.last().unwrap_err().column, 4);
+        assert!(p.expect(0xC3).is_err());
+        assert!(p.expect_last(0xBC).is_err());
+        assert_eq!(p.next_char(), Ok('é'));
+        assert_eq!(p.next(), Ok(b'1'));
+        assert_eq!(p.as_str(), "ü");
     }
 }
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+
+use crate::{IntoEnumeratedCells, Matrix, Orientation, Point};
+
+/// A small matrix to search for inside a larger one. Cells set to `None` are wildcards and match
+/// any value
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Pattern<T> {
+    cells: Matrix<Option<T>>,
+}
+
+/// A single place where a [`Pattern`] was found
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PatternMatch {
+    /// The top left corner of the (oriented) pattern in the searched matrix
+    pub position: Point,
+    /// The orientation that was applied to the pattern to produce this match
+    pub orientation: Orientation,
+    /// The cells of the searched matrix covered by non-wildcard pattern cells
+    pub cells: Vec<Point>,
+}
+
+impl<T> Pattern<T> {
+    pub fn new(cells: Matrix<Option<T>>) -> Self {
+        Self { cells }
+    }
+
+    /// Creates a pattern from `mat`, where every cell for which `is_wildcard` returns true
+    /// matches anything
+    pub fn from_matrix(mat: Matrix<T>, is_wildcard: impl Fn(&T) -> bool) -> Self {
+        Self::new(mat.into_map(|v| if is_wildcard(&v) { None } else { Some(v) }))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.rows()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells.cols()
+    }
+
+    pub fn cells(&self) -> &Matrix<Option<T>> {
+        &self.cells
+    }
+
+    /// Returns the offsets of all non-wildcard cells relative to the top left corner
+    pub fn required_cells(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells
+            .iter()
+            .enumerate_cells()
+            .filter(|(_, _, v)| v.is_some())
+            .map(|(row, col, _)| Point::new(row, col))
+    }
+
+    /// Returns true if this pattern matches `mat` with its top left corner at [`row`, `col`].
+    /// Patterns that would extend past the edge of `mat` never match
+    pub fn matches_at(&self, mat: &Matrix<T>, row: usize, col: usize) -> bool
+    where
+        T: PartialEq,
+    {
+        if row + self.rows() > mat.rows() || col + self.cols() > mat.cols() {
+            return false;
+        }
+        self.cells
+            .iter()
+            .enumerate_cells()
+            .all(|(r, c, expected)| match expected {
+                Some(expected) => mat.get(row + r, col + c) == expected,
+                None => true,
+            })
+    }
+}
+
+impl Pattern<u8> {
+    /// Parses a pattern from lines of ASCII characters, treating `wildcard` as a wildcard.
+    ///
+    /// Lines may have different lengths, short lines are padded with wildcards so trailing
+    /// whitespace can be trimmed safely
+    pub fn from_chars(input: &str, wildcard: u8) -> Result<Self, String> {
+        let lines: Vec<&str> = input.lines().collect();
+        let cols = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        if cols == 0 {
+            return Err("Empty pattern".to_owned());
+        }
+        let rows = lines
+            .into_iter()
+            .map(|line| {
+                let mut row: Vec<_> = line
+                    .bytes()
+                    .map(|b| if b == wildcard { None } else { Some(b) })
+                    .collect();
+                row.resize(cols, None);
+                row
+            })
+            .collect();
+
+        Ok(Self::new(Matrix::from_rows(rows)?))
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: PartialEq + Clone,
+{
+    /// Returns every place `pattern` occurs in this matrix without rotating or flipping it.
+    /// Matches may overlap
+    pub fn find_pattern(&self, pattern: &Pattern<T>) -> Vec<PatternMatch> {
+        self.find_pattern_with(pattern, Orientation::IDENTITY)
+    }
+
+    /// Returns every place `pattern` occurs in this matrix in any of its 8 rotations and
+    /// reflections.
+    ///
+    /// Orientations that produce the same pattern (because the pattern is symmetric) are only
+    /// searched once, so each covered region is reported once
+    pub fn find_pattern_oriented(&self, pattern: &Pattern<T>) -> Vec<PatternMatch> {
+        let mut searched: Vec<Matrix<Option<T>>> = Vec::new();
+        let mut result = Vec::new();
+        for (orientation, cells) in pattern.cells.orientations() {
+            if searched.contains(&cells) {
+                continue;
+            }
+            let oriented = Pattern::new(cells);
+            result.extend(self.find_pattern_with(&oriented, orientation));
+            searched.push(oriented.cells);
+        }
+        result
+    }
+
+    fn find_pattern_with(
+        &self,
+        pattern: &Pattern<T>,
+        orientation: Orientation,
+    ) -> Vec<PatternMatch> {
+        let mut result = Vec::new();
+        if pattern.rows() > self.rows() || pattern.cols() > self.cols() {
+            return result;
+        }
+        for row in 0..=(self.rows() - pattern.rows()) {
+            for col in 0..=(self.cols() - pattern.cols()) {
+                if pattern.matches_at(self, row, col) {
+                    let position = Point::new(row, col);
+                    result.push(PatternMatch {
+                        position,
+                        orientation,
+                        cells: pattern.required_cells().map(|p| p + position).collect(),
+                    });
+                }
+            }
+        }
+        result
+    }
+
+    /// Sets every cell covered by `matches` to `value`
+    pub fn mark_matches(&mut self, matches: &[PatternMatch], value: T) {
+        for p in matches.iter().flat_map(|m| m.cells.iter()) {
+            self.set(p.row, p.col, value.clone());
+        }
+    }
+}
+
+impl PatternMatch {
+    /// Returns the set of cells covered by at least one of `matches`
+    pub fn covered_cells(matches: &[PatternMatch]) -> HashSet<Point> {
+        matches
+            .iter()
+            .flat_map(|m| m.cells.iter().copied())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_with_wildcards() {
+        let mat = Matrix::new_from_chars("#.#.#\n.#.#.\n#.#.#").unwrap();
+        let pattern = Pattern::from_chars("# #\n #", b' ').unwrap();
+        assert_eq!(pattern.cols(), 3);
+
+        let matches = mat.find_pattern(&pattern);
+        let positions: Vec<_> = matches.iter().map(|m| m.position).collect();
+        assert_eq!(
+            positions,
+            vec![Point::new(0, 0), Point::new(0, 2), Point::new(1, 1)]
+        );
+        assert_eq!(matches[0].cells.len(), 3);
+        assert_eq!(PatternMatch::covered_cells(&matches).len(), 6);
+    }
+
+    #[test]
+    fn find_oriented() {
+        let mat = Matrix::new_from_chars("....\n.#..\n.##.\n....").unwrap();
+        let pattern = Pattern::from_chars("##\n#", b'.').unwrap();
+        assert!(mat.find_pattern(&pattern).is_empty());
+
+        let matches = mat.find_pattern_oriented(&pattern);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].position, Point::new(1, 1));
+
+        let mut marked = mat.clone();
+        marked.mark_matches(&matches, b'O');
+        assert_eq!(marked.format_as_chars(), "....\n.O..\n.OO.\n....");
+    }
+
+    #[test]
+    fn symmetric_pattern_reported_once() {
+        let mat = Matrix::new_from_chars("###\n###").unwrap();
+        let pattern = Pattern::from_chars("#", b'.').unwrap();
+        assert_eq!(mat.find_pattern_oriented(&pattern).len(), 6);
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Context, Result};
 use log::*;
-use parser::{AocPage, Client, Part, ProblemStageWithAnswers, TestCases};
+use parser::{AocPage, Client, Part, ProblemStageWithAnswers, TestCase, TestCases};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Entry;
 use std::path::Path;
@@ -152,6 +152,47 @@ impl Problems {
     }
 }
 
+/// Prints the parameter values `input` will be solved with, if there are any
+fn log_params(input: &Input) {
+    let params: Vec<_> = input
+        .params()
+        .map(|(name, value)| format!("{name} = {value}"))
+        .collect();
+    if !params.is_empty() {
+        let kind = if input.is_test() { "example" } else { "real" };
+        info!("using {} parameters: {}", kind, params.join(", "));
+    }
+}
+
+/// Builds the input for `test`, with the parameters it sets by hand taking priority over the
+/// day's example values
+fn test_input(implementation: &dyn AocDay, test: &TestCase) -> Option<Input> {
+    let mut input = Input::new(test.input.clone()?, IsTest::Yes);
+    for (name, value) in &test.params {
+        match implementation.params().iter().find(|p| p.name == name) {
+            Some(param) => input = input.with_param(param.name, value),
+            None => warn!("Test case sets unknown parameter `{name}`"),
+        }
+    }
+    Some(input.with_params(implementation.params()))
+}
+
+/// Runs `part`, treating answers that just echo the input (like the day template does) as
+/// unimplemented
+fn solve(implementation: &dyn AocDay, part: Part, input: Input) -> Output {
+    let text = input.as_str().to_owned();
+    let output = match part {
+        Part::Part1 => implementation.part1(input),
+        Part::Part2 => implementation.part2(input),
+    };
+    if output.matches(&text) {
+        warn!("{} returned its input unchanged", part);
+        Output::Unimplemented
+    } else {
+        output
+    }
+}
+
 impl Data {
     /// Tries to run the given part using `implementation`, but if no test cases are available this
     /// logs and returns None
@@ -166,24 +207,18 @@ impl Data {
             Part::Part2 => self.tests.part2(),
         };
         test.map(|test| -> Option<_> {
-            let input = Input(test.clone().input?, IsTest::Yes);
+            let input = test_input(implementation, test)?;
+            log_params(&input);
             let expected = test.output.as_ref().map(|s| s.trim().to_owned());
-
-            let out = match part {
-                Part::Part1 => implementation.part1(input),
-                Part::Part2 => implementation.part2(input),
-            };
-            Some((out, expected))
+            Some((solve(implementation, part, input), expected))
         })
         .flatten()
     }
 
     pub fn run(&self, implementation: &dyn AocDay, part: Part) -> Result<Output> {
-        let input = Input(self.input.clone(), IsTest::No);
-        Ok(match part {
-            Part::Part1 => implementation.part1(input),
-            Part::Part2 => implementation.part2(input),
-        })
+        let input = Input::new(self.input.clone(), IsTest::No).with_params(implementation.params());
+        log_params(&input);
+        Ok(solve(implementation, part, input))
     }
 
     /// Merges the new values from `page` into `self`
@@ -253,3 +288,58 @@ impl Data {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Param;
+
+    const PARAMS: &[Param] = &[
+        Param::new("size", "7", "71"),
+        Param::new("steps", "12", "1024"),
+    ];
+
+    struct Grid;
+
+    impl AocDay for Grid {
+        fn part1(&self, input: Input) -> Output {
+            input.param::<u32>("size").into()
+        }
+
+        fn part2(&self, input: Input) -> Output {
+            input.param::<u32>("steps").into()
+        }
+
+        fn params(&self) -> &'static [Param] {
+            PARAMS
+        }
+    }
+
+    #[test]
+    fn test_case_params() {
+        let test = TestCase {
+            input: Some("#".to_owned()),
+            output: Some("11".to_owned()),
+            params: [("size", "11"), ("unknown", "1")]
+                .into_iter()
+                .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                .collect(),
+        };
+        let data = Data {
+            input: String::new(),
+            tests: TestCases::Part2 {
+                part1: test.clone(),
+                part2: test,
+            },
+            answers: ProblemStageWithAnswers::Part1 {
+                part1_incorrect_gusses: vec![],
+            },
+        };
+        let (output, expected) = data.run_test(&Grid, Part::Part1).unwrap();
+        assert_eq!(output, Output::Int(11));
+        assert_eq!(expected.as_deref(), Some("11"));
+        // parameters the test case doesn't set keep their example value
+        let (output, _) = data.run_test(&Grid, Part::Part2).unwrap();
+        assert_eq!(output, Output::Int(12));
+    }
+}
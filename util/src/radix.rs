@@ -0,0 +1,558 @@
+//! Parsing and formatting numbers in any base, including balanced bases like the SNAFU numbers
+//! from 2022 day 25, with custom digit alphabets.
+
+use std::fmt;
+
+use num_bigint::{BigInt, BigUint, Sign};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RadixError {
+    /// There were no digits to parse
+    Empty,
+    /// The input contained a character that isn't in the alphabet
+    InvalidDigit { index: usize, found: char },
+    /// The parsed value doesn't fit in the requested type
+    Overflow,
+}
+
+impl fmt::Display for RadixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RadixError::Empty => write!(f, "no digits to parse"),
+            RadixError::InvalidDigit { index, found } => {
+                write!(f, "invalid digit `{}` at index {}", found, index)
+            }
+            RadixError::Overflow => write!(f, "number too large for the target type"),
+        }
+    }
+}
+
+impl std::error::Error for RadixError {}
+
+/// An integer type that numbers can be parsed into and formatted from
+pub trait RadixInt: Sized {
+    fn zero() -> Self;
+
+    fn is_zero(&self) -> bool;
+
+    fn is_negative(&self) -> bool;
+
+    /// Returns `self * base + digit`, or `None` if that doesn't fit
+    fn push_digit(&self, base: u32, digit: i32) -> Option<Self>;
+
+    /// Splits off the least significant digit, which is always in `min..min + base`.
+    ///
+    /// Returns the remaining value and the digit, so that `rest * base + digit == self`
+    fn pop_digit(&self, base: u32, min: i32) -> (Self, i32);
+}
+
+macro_rules! impl_radix_int {
+    ($($ty:ty),*) => {
+        $(
+            impl RadixInt for $ty {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+
+                #[allow(unused_comparisons)]
+                fn is_negative(&self) -> bool {
+                    *self < 0
+                }
+
+                fn push_digit(&self, base: u32, digit: i32) -> Option<Self> {
+                    // the base may not fit in this type, but a leading digit doesn't need it
+                    let shifted = if *self == 0 {
+                        0
+                    } else {
+                        self.checked_mul(<$ty>::try_from(base).ok()?)?
+                    };
+                    match <$ty>::try_from(digit) {
+                        Ok(digit) => shifted.checked_add(digit),
+                        // a negative digit for an unsigned type, or one that doesn't fit at all
+                        Err(_) => {
+                            shifted.checked_sub(<$ty>::try_from(digit.unsigned_abs()).ok()?)
+                        }
+                    }
+                }
+
+                fn pop_digit(&self, base: u32, min: i32) -> (Self, i32) {
+                    let base_wide = i64::from(base);
+                    let (mut rest, mut digit) = match <$ty>::try_from(base) {
+                        Ok(base) => (self.div_euclid(base), self.rem_euclid(base) as i64),
+                        // only types narrower than the base get here, so the value fits in an
+                        // i64 and is a single digit, borrowing one from the next place if it's
+                        // negative
+                        Err(_) => {
+                            let value = *self as i64;
+                            (
+                                value.div_euclid(base_wide) as $ty,
+                                value.rem_euclid(base_wide),
+                            )
+                        }
+                    };
+                    if digit >= i64::from(min) + base_wide {
+                        digit -= base_wide;
+                        rest += 1;
+                    }
+                    (rest, digit as i32)
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl RadixInt for BigUint {
+    fn zero() -> Self {
+        BigUint::default()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.bits() == 0
+    }
+
+    fn is_negative(&self) -> bool {
+        false
+    }
+
+    fn push_digit(&self, base: u32, digit: i32) -> Option<Self> {
+        let shifted = self * base;
+        let magnitude = BigUint::from(digit.unsigned_abs());
+        if digit >= 0 {
+            Some(shifted + magnitude)
+        } else if shifted >= magnitude {
+            Some(shifted - magnitude)
+        } else {
+            None
+        }
+    }
+
+    fn pop_digit(&self, base: u32, min: i32) -> (Self, i32) {
+        let mut rest = self / base;
+        let mut digit = u32::try_from(self % base).unwrap() as i32;
+        if digit >= min + base as i32 {
+            digit -= base as i32;
+            rest += 1u32;
+        }
+        (rest, digit)
+    }
+}
+
+impl RadixInt for BigInt {
+    fn zero() -> Self {
+        BigInt::default()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.bits() == 0
+    }
+
+    fn is_negative(&self) -> bool {
+        self.sign() == Sign::Minus
+    }
+
+    fn push_digit(&self, base: u32, digit: i32) -> Option<Self> {
+        Some(self * base + digit)
+    }
+
+    fn pop_digit(&self, base: u32, min: i32) -> (Self, i32) {
+        // `%` takes the sign of `self`, so shift the remainder into `min..min + base`
+        let mut digit = i32::try_from(self % base).unwrap();
+        if digit < min {
+            digit += base as i32;
+        } else if digit >= min + base as i32 {
+            digit -= base as i32;
+        }
+        ((self - digit) / base, digit)
+    }
+}
+
+/// A positional number system: a base and the characters used for each digit.
+///
+/// Standard bases have digits `0..base` and write negative numbers with a leading `-`. Balanced
+/// bases have digits centered on zero, so every integer is written without a sign.
+///
+/// ```
+/// # use util::radix::Radix;
+/// let hex = Radix::new(16);
+/// assert_eq!(hex.format(255u8), "ff");
+/// assert_eq!(hex.parse::<i32>("-FF"), Ok(-255));
+///
+/// let snafu = Radix::balanced("=-012");
+/// assert_eq!(snafu.format(2022), "1=11-2");
+/// assert_eq!(snafu.parse::<u64>("1121-1110-1=0"), Ok(314159265));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Radix {
+    /// The characters for each digit, in order of increasing value
+    alphabet: Vec<char>,
+    /// The value of the first digit in `alphabet`
+    min: i32,
+    /// Whether parsing also accepts the uppercase version of each digit
+    ignore_case: bool,
+}
+
+impl Radix {
+    /// The standard base `base` number system using `0-9` and then `a-z` as digits, which parses
+    /// digits in either case like [`u32::from_str_radix`].
+    ///
+    /// # Panics
+    /// This function panics if `base` is not in `2..=36`
+    #[track_caller]
+    pub fn new(base: u32) -> Self {
+        assert!((2..=36).contains(&base), "base {} not in 2..=36", base);
+        Self {
+            alphabet: (0..base)
+                .map(|d| char::from_digit(d, base).unwrap())
+                .collect(),
+            min: 0,
+            ignore_case: true,
+        }
+    }
+
+    /// A standard number system whose base is the length of `alphabet`, with the first
+    /// character as zero. For example `".#"` for binary drawn with pixels.
+    #[track_caller]
+    pub fn with_alphabet(alphabet: &str) -> Self {
+        Self::with_offset(alphabet, 0)
+    }
+
+    /// A balanced number system whose base is the length of `alphabet`, with the middle
+    /// character as zero. For example `"=-012"` for SNAFU or `"-0+"` for balanced ternary.
+    ///
+    /// # Panics
+    /// This function panics if `alphabet` doesn't have an odd number of digits
+    #[track_caller]
+    pub fn balanced(alphabet: &str) -> Self {
+        let base = alphabet.chars().count();
+        assert!(
+            base % 2 == 1,
+            "balanced alphabet {:?} needs an odd length",
+            alphabet
+        );
+        Self::with_offset(alphabet, -(base as i32 / 2))
+    }
+
+    /// A number system where the first character of `alphabet` has the value `min` and each
+    /// following character is one more.
+    ///
+    /// # Panics
+    /// This function panics if `alphabet` has fewer than two characters, repeats a character,
+    /// or `min` isn't in `1 - base..=0`
+    #[track_caller]
+    pub fn with_offset(alphabet: &str, min: i32) -> Self {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let base = alphabet.len() as i32;
+        assert!(base >= 2, "an alphabet needs at least two digits");
+        assert!(
+            (1 - base..=0).contains(&min),
+            "digits must include zero, but start at {}",
+            min
+        );
+        for (i, c) in alphabet.iter().enumerate() {
+            assert!(!alphabet[..i].contains(c), "alphabet repeats `{}`", c);
+        }
+        Self {
+            alphabet,
+            min,
+            ignore_case: false,
+        }
+    }
+
+    pub fn base(&self) -> u32 {
+        self.alphabet.len() as u32
+    }
+
+    /// Returns the value of the smallest digit
+    pub fn min_digit(&self) -> i32 {
+        self.min
+    }
+
+    /// Returns the value of the largest digit
+    pub fn max_digit(&self) -> i32 {
+        self.min + self.base() as i32 - 1
+    }
+
+    /// Returns true if this system has negative digits, so never needs a sign
+    pub fn is_balanced(&self) -> bool {
+        self.min < 0
+    }
+
+    /// Returns the value of the digit `c`, or `None` if it isn't part of the alphabet
+    pub fn digit_value(&self, c: char) -> Option<i32> {
+        let pos = self.alphabet.iter().position(|&d| d == c).or_else(|| {
+            let lower = c.to_ascii_lowercase();
+            self.ignore_case
+                .then(|| self.alphabet.iter().position(|&d| d == lower))
+                .flatten()
+        })?;
+        Some(self.min + pos as i32)
+    }
+
+    /// Returns the character for the digit with value `value`, or `None` if it's out of range
+    pub fn digit_char(&self, value: i32) -> Option<char> {
+        let pos = usize::try_from(value - self.min).ok()?;
+        self.alphabet.get(pos).copied()
+    }
+
+    /// Parses `text`, which may start with a `-` in standard bases whose alphabet doesn't
+    /// contain `-`
+    pub fn parse<T: RadixInt>(&self, text: &str) -> Result<T, RadixError> {
+        let (negative, digits, offset) = match text.strip_prefix('-') {
+            Some(rest) if !self.is_balanced() && self.digit_value('-').is_none() => (true, rest, 1),
+            _ => (false, text, 0),
+        };
+        let values = digits
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                self.digit_value(c).ok_or(RadixError::InvalidDigit {
+                    index: i + offset,
+                    found: c,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if negative {
+            // accumulating negated digits lets the most negative value of each type parse
+            self.from_digits(values.into_iter().map(|d| -d))
+        } else {
+            self.from_digits(values)
+        }
+    }
+
+    /// Builds a number from digit values, most significant first. Digits aren't checked against
+    /// the alphabet, so this also works for carries that are out of range.
+    pub fn from_digits<T: RadixInt>(
+        &self,
+        digits: impl IntoIterator<Item = i32>,
+    ) -> Result<T, RadixError> {
+        let mut digits = digits.into_iter().peekable();
+        digits.peek().ok_or(RadixError::Empty)?;
+        let base = self.base();
+        let mut value = Some(T::zero());
+        for digit in digits {
+            value = value.and_then(|v| v.push_digit(base, digit));
+        }
+        value.ok_or(RadixError::Overflow)
+    }
+
+    /// Formats `value` in this number system
+    pub fn format<T: RadixInt>(&self, value: T) -> String {
+        let negative = !self.is_balanced() && value.is_negative();
+        let mut s: Vec<char> = self
+            .digits(value)
+            .map(|d| self.digit_char(if negative { -d } else { d }).unwrap())
+            .collect();
+        if s.is_empty() {
+            s.push(self.digit_char(0).unwrap());
+        }
+        if negative {
+            s.push('-');
+        }
+        s.iter().rev().collect()
+    }
+
+    /// Returns the digits of `value` from least to most significant. Zero has no digits.
+    ///
+    /// In standard bases the digits of a negative number are all negative or zero, so their
+    /// absolute values are the digits of its magnitude
+    pub fn digits<T: RadixInt>(&self, value: T) -> Digits<T> {
+        let min = if !self.is_balanced() && value.is_negative() {
+            1 - self.base() as i32
+        } else {
+            self.min
+        };
+        Digits {
+            value,
+            base: self.base(),
+            min,
+        }
+    }
+
+    /// Returns the digits of `value` from most to least significant, with zero as `[0]`
+    pub fn to_digits<T: RadixInt>(&self, value: T) -> Vec<i32> {
+        let mut digits: Vec<i32> = self.digits(value).collect();
+        if digits.is_empty() {
+            digits.push(0);
+        }
+        digits.reverse();
+        digits
+    }
+
+    /// Returns the sum of the digit values of `value`
+    pub fn digit_sum<T: RadixInt>(&self, value: T) -> i64 {
+        self.digits(value).map(i64::from).sum()
+    }
+}
+
+/// Iterator over the digits of a number, least significant first. See [`Radix::digits`]
+#[derive(Clone, Debug)]
+pub struct Digits<T> {
+    value: T,
+    base: u32,
+    min: i32,
+}
+
+impl<T: RadixInt> Iterator for Digits<T> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.value.is_zero() {
+            return None;
+        }
+        let (rest, digit) = self.value.pop_digit(self.base, self.min);
+        self.value = rest;
+        Some(digit)
+    }
+}
+
+/// Returns the sum of the digits of `value` in the standard base `base`
+pub fn digit_sum(value: u128, base: u32) -> u32 {
+    Radix::new(base).digits(value).sum::<i32>() as u32
+}
+
+/// Converts `value` to its reflected binary gray code, where consecutive numbers differ in
+/// exactly one bit
+pub fn to_gray(value: u128) -> u128 {
+    value ^ (value >> 1)
+}
+
+/// The inverse of [`to_gray`]
+pub fn from_gray(mut gray: u128) -> u128 {
+    let mut shift = 1;
+    while shift < 128 {
+        gray ^= gray >> shift;
+        shift <<= 1;
+    }
+    gray
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard() {
+        let binary = Radix::new(2);
+        assert_eq!(binary.format(10u8), "1010");
+        assert_eq!(binary.format(0u8), "0");
+        assert_eq!(binary.parse::<u8>("11111111"), Ok(255));
+        assert_eq!(binary.parse::<u8>("100000000"), Err(RadixError::Overflow));
+        assert_eq!(
+            binary.parse::<u8>("102"),
+            Err(RadixError::InvalidDigit {
+                index: 2,
+                found: '2'
+            })
+        );
+        assert_eq!(binary.parse::<u8>(""), Err(RadixError::Empty));
+
+        let base36 = Radix::new(36);
+        assert_eq!(
+            base36.format(i128::MIN),
+            format!("-{}", base36.format(i128::MAX as u128 + 1))
+        );
+        assert_eq!(
+            base36.parse::<i128>(&base36.format(i128::MIN)),
+            Ok(i128::MIN)
+        );
+        assert_eq!(base36.parse::<i8>("-3k"), Ok(-128));
+        assert_eq!(base36.parse::<u8>("-1"), Err(RadixError::Overflow));
+
+        let pixels = Radix::with_alphabet(".#");
+        assert_eq!(pixels.parse::<u32>("#.##"), Ok(11));
+        assert_eq!(pixels.format(-5), "-#.#");
+        assert_eq!(Radix::new(10).to_digits(1203u32), [1, 2, 0, 3]);
+        assert_eq!(Radix::new(10).digit_sum(-1203), -6);
+        assert_eq!(digit_sum(0xff, 16), 30);
+    }
+
+    #[test]
+    fn wide_alphabet() {
+        // more digits than an i8 or u8 can hold
+        let alphabet: String = (0..300).filter_map(|i| char::from_u32(0x100 + i)).collect();
+        let wide = Radix::with_alphabet(&alphabet);
+        assert_eq!(wide.base(), 300);
+        for value in [0u8, 1, 255] {
+            assert_eq!(wide.to_digits(value), [value as i32]);
+            assert_eq!(wide.parse::<u8>(&wide.format(value)), Ok(value));
+        }
+        for value in [i8::MIN, -1, 0, 1, i8::MAX] {
+            assert_eq!(wide.parse::<i8>(&wide.format(value)), Ok(value));
+        }
+        assert_eq!(wide.to_digits(-100i8), [-100]);
+        assert_eq!(wide.to_digits(1000u16), [3, 100]);
+        assert_eq!(
+            wide.parse::<u8>("\u{101}\u{100}"),
+            Err(RadixError::Overflow)
+        );
+
+        let balanced = Radix::balanced(&alphabet.chars().skip(1).collect::<String>());
+        for value in [-20000, -150, -149, 0, 149, 150, 20000i16] {
+            assert_eq!(balanced.parse::<i16>(&balanced.format(value)), Ok(value));
+        }
+    }
+
+    #[test]
+    fn balanced() {
+        let snafu = Radix::balanced("=-012");
+        let examples = [
+            (1, "1"),
+            (3, "1="),
+            (8, "2="),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+        ];
+        for (value, text) in examples {
+            assert_eq!(snafu.format(value), text);
+            assert_eq!(snafu.parse::<u64>(text), Ok(value));
+        }
+        assert_eq!(snafu.format(0), "0");
+        assert_eq!(snafu.parse::<i32>("-"), Ok(-1));
+        assert_eq!(snafu.parse::<u32>("-"), Err(RadixError::Overflow));
+
+        let ternary = Radix::balanced("-0+");
+        for value in -100..100i64 {
+            let text = ternary.format(value);
+            assert_eq!(ternary.parse::<i64>(&text), Ok(value));
+            assert!(ternary.digits(value).all(|d| (-1..=1).contains(&d)));
+        }
+        assert_eq!(ternary.to_digits(-4), [-1, -1]);
+    }
+
+    #[test]
+    fn bigint() {
+        let snafu = Radix::balanced("=-012");
+        let huge: BigInt = BigInt::from(u128::MAX) * 1000u32 - 7;
+        for value in [huge.clone(), -huge.clone()] {
+            let text = snafu.format(value.clone());
+            assert_eq!(snafu.parse::<BigInt>(&text), Ok(value));
+        }
+        assert_eq!(
+            snafu.parse::<BigUint>(&snafu.format(huge.clone())),
+            Ok(huge.to_biguint().unwrap())
+        );
+
+        let hex = Radix::new(16);
+        let big = BigUint::from(u128::MAX) + 1u32;
+        assert_eq!(hex.format(big.clone()), format!("1{}", "0".repeat(32)));
+        assert_eq!(hex.parse::<BigUint>(&hex.format(big.clone())), Ok(big));
+        assert_eq!(hex.format(-huge.clone()), format!("-{}", hex.format(huge)));
+    }
+
+    #[test]
+    fn gray() {
+        assert_eq!(
+            (0..8).map(to_gray).collect::<Vec<_>>(),
+            [0, 1, 3, 2, 6, 7, 5, 4]
+        );
+        for value in [0, 1, 12345, u128::MAX, u128::MAX / 3] {
+            assert_eq!(from_gray(to_gray(value)), value);
+        }
+    }
+}
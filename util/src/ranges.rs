@@ -0,0 +1,500 @@
+use std::fmt::Debug;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+/// An integer type that can be used as the bounds of a [`RangeSet`] or [`Cuboid`]
+pub trait RangeValue: Copy + Ord + Debug {
+    const MIN: Self;
+    const MAX: Self;
+
+    fn checked_succ(self) -> Option<Self>;
+    fn checked_pred(self) -> Option<Self>;
+
+    /// Returns the number of values in `self..=end`. Saturates at `u128::MAX` for the full range
+    /// of a 128 bit type
+    fn count_to(self, end: Self) -> u128;
+}
+
+macro_rules! impl_range_value {
+    ($($t:ty => $unsigned:ty),*) => {
+        $(
+            impl RangeValue for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn checked_succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn checked_pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count_to(self, end: Self) -> u128 {
+                    // the wrapped difference read as unsigned is exact, even when it doesn't fit
+                    // in the signed type
+                    (end.wrapping_sub(self) as $unsigned as u128).saturating_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_range_value!(
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize,
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize
+);
+
+/// Converts any range to its inclusive bounds, or `None` if the range is empty
+fn inclusive_bounds<T: RangeValue>(range: impl RangeBounds<T>) -> Option<(T, T)> {
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s.checked_succ()?,
+        Bound::Unbounded => T::MIN,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e,
+        Bound::Excluded(&e) => e.checked_pred()?,
+        Bound::Unbounded => T::MAX,
+    };
+    (start <= end).then_some((start, end))
+}
+
+/// A set of integers stored as sorted, non-overlapping, non-adjacent inclusive ranges.
+///
+/// Inserting `1..=3` and `4..=6` stores the single range `1..=6`, so two sets containing the same
+/// values always compare equal
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: RangeValue> RangeSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Creates a set containing the values in `range`
+    pub fn from_range(range: impl RangeBounds<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+
+    /// Returns true if the set contains no values
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of disjoint ranges used to store this set
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns the total number of values in the set
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .fold(0u128, |sum, &(s, e)| sum.saturating_add(s.count_to(e)))
+    }
+
+    /// Iterates over the ranges in this set in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// Returns the smallest value in the set
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.0)
+    }
+
+    /// Returns the largest value in the set
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.1)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.range_containing(value).is_some()
+    }
+
+    /// Returns the range of this set that `value` is part of
+    pub fn range_containing(&self, value: T) -> Option<RangeInclusive<T>> {
+        let i = self.ranges.partition_point(|r| r.1 < value);
+        match self.ranges.get(i) {
+            Some(&(s, e)) if s <= value => Some(s..=e),
+            _ => None,
+        }
+    }
+
+    /// Returns true if every value in `range` is in this set
+    pub fn contains_range(&self, range: impl RangeBounds<T>) -> bool {
+        let Some((start, end)) = inclusive_bounds(range) else {
+            return true;
+        };
+        matches!(self.range_containing(start), Some(r) if *r.end() >= end)
+    }
+
+    /// Returns true if any value in `range` is in this set
+    pub fn overlaps(&self, range: impl RangeBounds<T>) -> bool {
+        let Some((start, end)) = inclusive_bounds(range) else {
+            return false;
+        };
+        let i = self.ranges.partition_point(|r| r.1 < start);
+        matches!(self.ranges.get(i), Some(&(s, _)) if s <= end)
+    }
+
+    /// Adds every value in `range` to the set, merging it with any ranges it touches
+    pub fn insert(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = inclusive_bounds(range) else {
+            return;
+        };
+        // ranges i..j overlap or touch the new range
+        let i = self
+            .ranges
+            .partition_point(|r| r.1.checked_succ().is_some_and(|next| next < start));
+        let j = self
+            .ranges
+            .partition_point(|r| end.checked_succ().is_none_or(|next| r.0 <= next));
+        let merged = if i < j {
+            (start.min(self.ranges[i].0), end.max(self.ranges[j - 1].1))
+        } else {
+            (start, end)
+        };
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    /// Removes every value in `range` from the set, splitting any range it cuts through
+    pub fn remove(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = inclusive_bounds(range) else {
+            return;
+        };
+        let i = self.ranges.partition_point(|r| r.1 < start);
+        let j = self.ranges.partition_point(|r| r.0 <= end);
+        if i >= j {
+            return;
+        }
+        let (first, last) = (self.ranges[i], self.ranges[j - 1]);
+        let mut kept = Vec::with_capacity(2);
+        if first.0 < start {
+            kept.push((first.0, start.checked_pred().unwrap()));
+        }
+        if last.1 > end {
+            kept.push((end.checked_succ().unwrap(), last.1));
+        }
+        self.ranges.splice(i..j, kept);
+    }
+
+    /// Returns the values that are in either set
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range);
+        }
+        result
+    }
+
+    /// Returns the values that are in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(&&(s1, e1)), Some(&&(s2, e2))) = (a.peek(), b.peek()) {
+            let (start, end) = (s1.max(s2), e1.min(e2));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if e1 < e2 {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Returns the values that are in `self` but not in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range);
+        }
+        result
+    }
+
+    /// Returns every value of `T` that isn't in this set
+    pub fn complement(&self) -> Self {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        let mut next = Some(T::MIN);
+        for &(s, e) in &self.ranges {
+            if let Some(n) = next {
+                if n < s {
+                    ranges.push((n, s.checked_pred().unwrap()));
+                }
+            }
+            next = e.checked_succ();
+        }
+        if let Some(n) = next {
+            ranges.push((n, T::MAX));
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: RangeValue, R: RangeBounds<T>> FromIterator<R> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/// An axis-aligned box in `N` dimensions, including both corners
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cuboid<T, const N: usize> {
+    pub min: [T; N],
+    pub max: [T; N],
+}
+
+impl<T: RangeValue, const N: usize> Cuboid<T, N> {
+    /// Creates the cuboid with `min` and `max` as opposite corners
+    ///
+    /// # Panics
+    /// This function panics if `min` is greater than `max` on any axis
+    #[track_caller]
+    pub fn new(min: [T; N], max: [T; N]) -> Self {
+        assert!(
+            (0..N).all(|axis| min[axis] <= max[axis]),
+            "cuboid corners {:?} and {:?} are out of order",
+            min,
+            max
+        );
+        Self { min, max }
+    }
+
+    /// Creates a cuboid from the range it covers on each axis, or `None` if any range is empty
+    pub fn from_ranges(ranges: [RangeInclusive<T>; N]) -> Option<Self> {
+        let mut min = [T::MIN; N];
+        let mut max = [T::MIN; N];
+        for (axis, range) in ranges.into_iter().enumerate() {
+            (min[axis], max[axis]) = inclusive_bounds(range)?;
+        }
+        Some(Self { min, max })
+    }
+
+    /// Returns the number of points inside this cuboid
+    pub fn volume(&self) -> u128 {
+        (0..N).fold(1u128, |volume, axis| {
+            volume.saturating_mul(self.min[axis].count_to(self.max[axis]))
+        })
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        (0..N).all(|axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    /// Returns true if every point in `other` is inside `self`
+    pub fn contains_cuboid(&self, other: &Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    /// Returns the points that are in both cuboids, or `None` if they don't overlap
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut min = self.min;
+        let mut max = self.max;
+        for axis in 0..N {
+            min[axis] = min[axis].max(other.min[axis]);
+            max[axis] = max[axis].min(other.max[axis]);
+            if min[axis] > max[axis] {
+                return None;
+            }
+        }
+        Some(Self { min, max })
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns up to `2 * N` disjoint cuboids covering the points of `self` that aren't in `other`
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(overlap) = self.intersection(other) else {
+            return vec![*self];
+        };
+        // peel off the slabs before and after the overlap on each axis in turn, shrinking what is
+        // left until only the overlap remains
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for axis in 0..N {
+            if rest.min[axis] < overlap.min[axis] {
+                let mut below = rest;
+                below.max[axis] = overlap.min[axis].checked_pred().unwrap();
+                pieces.push(below);
+                rest.min[axis] = overlap.min[axis];
+            }
+            if rest.max[axis] > overlap.max[axis] {
+                let mut above = rest;
+                above.min[axis] = overlap.max[axis].checked_succ().unwrap();
+                pieces.push(above);
+                rest.max[axis] = overlap.max[axis];
+            }
+        }
+        pieces
+    }
+}
+
+/// A set of points in `N` dimensions stored as disjoint [`Cuboid`]s, for counting how many points
+/// are left on after a long sequence of on/off steps (2021 day 22)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CuboidSet<T, const N: usize> {
+    cuboids: Vec<Cuboid<T, N>>,
+}
+
+impl<T: RangeValue, const N: usize> CuboidSet<T, N> {
+    pub fn new() -> Self {
+        Self {
+            cuboids: Vec::new(),
+        }
+    }
+
+    /// The disjoint cuboids that make up this set, in no particular order
+    pub fn cuboids(&self) -> &[Cuboid<T, N>] {
+        &self.cuboids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cuboids.is_empty()
+    }
+
+    /// Returns the number of points in the set
+    pub fn volume(&self) -> u128 {
+        self.cuboids
+            .iter()
+            .fold(0u128, |sum, c| sum.saturating_add(c.volume()))
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.cuboids.iter().any(|c| c.contains(point))
+    }
+
+    /// Adds every point in `cuboid` to the set
+    pub fn insert(&mut self, cuboid: Cuboid<T, N>) {
+        self.remove(&cuboid);
+        self.cuboids.push(cuboid);
+    }
+
+    /// Removes every point in `cuboid` from the set
+    pub fn remove(&mut self, cuboid: &Cuboid<T, N>) {
+        let mut kept = Vec::with_capacity(self.cuboids.len());
+        for existing in self.cuboids.drain(..) {
+            if cuboid.contains_cuboid(&existing) {
+                continue;
+            }
+            kept.extend(existing.subtract(cuboid));
+        }
+        self.cuboids = kept;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_merges_adjacent() {
+        let mut set = RangeSet::new();
+        set.insert(1..=3);
+        set.insert(7..10);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=3, 7..=9]);
+        set.insert(4..=6);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=9]);
+        set.insert(20..=20);
+        set.insert(0..=25);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..=25]);
+        assert_eq!(set.len(), 26);
+        set.insert(5..5);
+        assert_eq!(set.range_count(), 1);
+    }
+
+    #[test]
+    fn remove_splits() {
+        let mut set: RangeSet<i32> = [0..=10, 20..=30].into_iter().collect();
+        set.remove(5..=25);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..=4, 26..=30]);
+        assert!(set.contains(4));
+        assert!(!set.contains(5));
+        assert_eq!(set.range_containing(28), Some(26..=30));
+        assert_eq!(set.range_containing(31), None);
+        set.remove(..);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: RangeSet<u8> = [0..=10, 20..=30].into_iter().collect();
+        let b: RangeSet<u8> = [5..=25].into_iter().collect();
+        assert_eq!(
+            a.intersection(&b).iter().collect::<Vec<_>>(),
+            vec![5..=10, 20..=25]
+        );
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![0..=30]);
+        assert_eq!(
+            a.difference(&b).iter().collect::<Vec<_>>(),
+            vec![0..=4, 26..=30]
+        );
+        assert_eq!(
+            a.complement().iter().collect::<Vec<_>>(),
+            vec![11..=19, 31..=255]
+        );
+        assert_eq!(a.complement().complement(), a);
+        assert!(a.contains_range(22..=28));
+        assert!(!a.contains_range(8..=12));
+        assert!(a.overlaps(8..=12));
+        assert!(!a.overlaps(11..20));
+        assert_eq!(RangeSet::<i8>::from_range(..).len(), 256);
+        assert_eq!(RangeSet::<i128>::from_range(..).len(), u128::MAX);
+    }
+
+    #[test]
+    fn cuboid_subtract() {
+        let a = Cuboid::new([0, 0, 0], [9, 9, 9]);
+        let b = Cuboid::new([3, 3, 3], [5, 12, 5]);
+        let pieces = a.subtract(&b);
+        let overlap = a.intersection(&b).unwrap();
+        assert_eq!(overlap, Cuboid::new([3, 3, 3], [5, 9, 5]));
+        assert_eq!(
+            pieces.iter().map(Cuboid::volume).sum::<u128>(),
+            a.volume() - overlap.volume()
+        );
+        for (i, p) in pieces.iter().enumerate() {
+            assert!(!p.overlaps(&b));
+            assert!(pieces[i + 1..].iter().all(|q| !p.overlaps(q)));
+        }
+        assert_eq!(a.subtract(&Cuboid::new([20, 0, 0], [30, 1, 1])), vec![a]);
+    }
+
+    #[test]
+    fn reactor_steps() {
+        // the small example from 2021 day 22
+        let steps = [
+            (true, [10, 10, 10], [12, 12, 12]),
+            (true, [11, 11, 11], [13, 13, 13]),
+            (false, [9, 9, 9], [11, 11, 11]),
+            (true, [10, 10, 10], [10, 10, 10]),
+        ];
+        let mut reactor = CuboidSet::new();
+        for (on, min, max) in steps {
+            let cuboid = Cuboid::new(min, max);
+            if on {
+                reactor.insert(cuboid);
+            } else {
+                reactor.remove(&cuboid);
+            }
+        }
+        assert_eq!(reactor.volume(), 39);
+        assert!(reactor.contains([10, 10, 10]));
+        assert!(!reactor.contains([11, 11, 11]));
+    }
+}
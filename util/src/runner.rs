@@ -1,4 +1,4 @@
-use crate::{problems::DB_PATH, AocDay, Data, Day, Problems, Year};
+use crate::{problems::DB_PATH, AocDay, Data, Day, Output, Problems, Year};
 use std::{ops::BitOrAssign, time::Duration as StdDuration};
 
 use anyhow::Result;
@@ -43,47 +43,56 @@ fn run(problems: &mut Problems, data: RunData) -> Result<()> {
 
     let mut run_part = |day_data: &mut Data, part| -> Result<RefreshStatus> {
         if let Some((output, expected)) = day_data.run_test(implementation, part) {
+            if output.is_unimplemented() {
+                warn!("day {} part {} is not implemented", day, part);
+                return Ok(RefreshStatus::NotRequired);
+            }
             if let Some(expected) = expected {
-                if expected.as_str() == output.as_str() {
+                if output.matches(&expected) {
                     info!("day {} part {} test succeeded!", day, part);
-                    info!("expected {} got {}", expected.as_str(), output.as_str());
+                    info!("expected {} got {}", expected, output);
+                } else if expected.contains('\n') || matches!(output, Output::Multiline(_)) {
+                    pretty_assertions::assert_eq!(expected, output.to_string());
                 } else {
-                    if expected.bytes().filter(|&b| b == b'\n').count() > 0 {
-                        pretty_assertions::assert_eq!(expected, output.as_str());
-                    } else {
-                        panic!(
-                            "{} test failed:\n  expected `{}`\n  real `{}`",
-                            part,
-                            expected,
-                            output.as_str()
-                        );
-                    }
+                    panic!(
+                        "{} test failed:\n  expected `{}`\n  real `{}`",
+                        part, expected, output
+                    );
                 }
             } else {
-                info!("{} test: {}", part, output.as_str());
+                info!("{} test: {}", part, output);
             }
         };
 
         let answer = day_data.run(implementation, part)?;
+        if answer.is_unimplemented() {
+            warn!("day {} part {} is not implemented", day, part);
+            return Ok(RefreshStatus::NotRequired);
+        }
+        let submission = answer.submission();
         println!("----------------------------------------");
         println!();
-        println!(
-            "     {} day {}, {} answer: {}",
-            year,
-            day,
-            part,
-            answer.as_str()
-        );
+        if let Output::Multiline(art) = &answer {
+            let letters = submission.as_deref().unwrap_or("unreadable art");
+            println!("{}\n", art);
+            println!("     {} day {}, {} answer: {}", year, day, part, letters);
+        } else {
+            println!("     {} day {}, {} answer: {}", year, day, part, answer);
+        }
         println!();
         println!("----------------------------------------");
         println!();
 
         if data.auto_submit {
-            if day_data.is_answer_already_submitted(&answer.0) {
+            let Some(submission) = submission else {
+                warn!("couldn't read the letters in the answer, submit it by hand");
+                return Ok(RefreshStatus::NotRequired);
+            };
+            if day_data.is_answer_already_submitted(&submission) {
                 println!("Same incorrect answer");
                 info!("refusing to submit again");
             } else {
-                match client.submit(year.0, day.0, part, answer.as_str())? {
+                match client.submit(year.0, day.0, part, &submission)? {
                     SubmitStatus::AlreadySubmitted => println!("Problem already submitted"),
                     SubmitStatus::Correct => {
                         println!("CORRECT");
@@ -92,7 +101,7 @@ fn run(problems: &mut Problems, data: RunData) -> Result<()> {
                     }
                     SubmitStatus::Incorrect => {
                         println!("Incorrect");
-                        day_data.add_incorrect_answer_for_current(answer.into_inner());
+                        day_data.add_incorrect_answer_for_current(submission);
                     }
                     SubmitStatus::Unknown(s) => {
                         println!("Unknown server responce");
@@ -0,0 +1,273 @@
+use std::any::type_name;
+use std::fmt;
+use std::str::FromStr;
+
+/// Describes where and why a line of input couldn't be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1 based line number
+    pub line: usize,
+    /// The 1 based column, counted in bytes
+    pub column: usize,
+    /// What the parser was looking for
+    pub expected: String,
+    /// The rest of the line starting at `column`
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "`{}`", self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A type that can be built from the text captured by the `{}`s in a [`LinePattern`].
+///
+/// Implemented for tuples of up to 8 [`FromStr`] types, so `(usize, String)` can be parsed from
+/// `"{} bags of {}"`. Use a 1-tuple for a single capture
+pub trait FromCaptures: Sized {
+    /// The number of captures this type is built from
+    const COUNT: usize;
+
+    /// Builds a value from `captures`, which always has [`Self::COUNT`] elements.
+    /// On failure, returns the index of the capture that couldn't be parsed and what was expected
+    fn from_captures(captures: &[&str]) -> Result<Self, (usize, String)>;
+}
+
+/// Describes what we expected when `T` fails to parse
+pub(crate) fn expected_type<T>() -> String {
+    format!("a value of type `{}`", type_name::<T>())
+}
+
+macro_rules! impl_from_captures {
+    ($count:literal; $($t:ident $i:tt),*) => {
+        impl<$($t: FromStr),*> FromCaptures for ($($t,)*) {
+            const COUNT: usize = $count;
+
+            fn from_captures(captures: &[&str]) -> Result<Self, (usize, String)> {
+                Ok(($(
+                    captures[$i].parse::<$t>().map_err(|_| ($i, expected_type::<$t>()))?,
+                )*))
+            }
+        }
+    };
+}
+
+impl_from_captures!(1; A 0);
+impl_from_captures!(2; A 0, B 1);
+impl_from_captures!(3; A 0, B 1, C 2);
+impl_from_captures!(4; A 0, B 1, C 2, D 3);
+impl_from_captures!(5; A 0, B 1, C 2, D 3, E 4);
+impl_from_captures!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_captures!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_captures!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Piece<'p> {
+    Literal(&'p str),
+    Capture,
+}
+
+/// A line format like `"move {} from {} to {}"`, where each `{}` captures text up to the next
+/// literal part of the pattern (or the end of the line).
+///
+/// ```
+/// # use util::LinePattern;
+/// let pattern = LinePattern::new("move {} from {} to {}");
+/// let (count, from, to): (usize, usize, usize) = pattern.parse_line("move 3 from 1 to 2").unwrap();
+/// assert_eq!((count, from, to), (3, 1, 2));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinePattern<'p> {
+    pieces: Vec<Piece<'p>>,
+}
+
+impl<'p> LinePattern<'p> {
+    /// # Panics
+    /// This function panics if two captures are next to each other, since there would be no way
+    /// to tell where the first one ends
+    #[track_caller]
+    pub fn new(pattern: &'p str) -> Self {
+        let mut pieces = Vec::new();
+        let mut rest = pattern;
+        while let Some(i) = rest.find("{}") {
+            if i != 0 {
+                pieces.push(Piece::Literal(&rest[..i]));
+            }
+            assert!(
+                pieces.last() != Some(&Piece::Capture),
+                "pattern `{}` has two captures without a literal between them",
+                pattern
+            );
+            pieces.push(Piece::Capture);
+            rest = &rest[i + 2..];
+        }
+        if !rest.is_empty() {
+            pieces.push(Piece::Literal(rest));
+        }
+        Self { pieces }
+    }
+
+    /// Returns the number of `{}`s in this pattern
+    pub fn capture_count(&self) -> usize {
+        self.pieces.iter().filter(|p| **p == Piece::Capture).count()
+    }
+
+    /// Splits `line` into the text matched by each capture, along with the column it starts at
+    fn captures<'l>(&self, line: &'l str) -> Result<Vec<(usize, &'l str)>, (usize, String)> {
+        let mut captures = Vec::new();
+        let mut pos = 0;
+        for (i, piece) in self.pieces.iter().enumerate() {
+            match piece {
+                Piece::Literal(literal) => {
+                    if !line[pos..].starts_with(literal) {
+                        return Err((pos, format!("`{}`", literal)));
+                    }
+                    pos += literal.len();
+                }
+                Piece::Capture => {
+                    let end = match self.pieces.get(i + 1) {
+                        Some(Piece::Literal(next)) => match line[pos..].find(next) {
+                            Some(len) => pos + len,
+                            None => return Err((pos, format!("text followed by `{}`", next))),
+                        },
+                        _ => line.len(),
+                    };
+                    captures.push((pos, &line[pos..end]));
+                    pos = end;
+                }
+            }
+        }
+        if pos != line.len() {
+            return Err((pos, "end of line".to_owned()));
+        }
+        Ok(captures)
+    }
+
+    /// Parses `line` as the line numbered `line_number` (1 based), which is only used for errors
+    ///
+    /// # Panics
+    /// This function panics if `T` isn't built from the same number of captures as this pattern
+    #[track_caller]
+    pub fn parse_numbered_line<T: FromCaptures>(
+        &self,
+        line: &str,
+        line_number: usize,
+    ) -> Result<T, ParseError> {
+        assert_eq!(
+            self.capture_count(),
+            T::COUNT,
+            "pattern has a different number of captures than `{}`",
+            type_name::<T>()
+        );
+        let error = |column: usize, expected| ParseError {
+            line: line_number,
+            column: column + 1,
+            expected,
+            found: line[column..].to_owned(),
+        };
+        let captures = self
+            .captures(line)
+            .map_err(|(column, expected)| error(column, expected))?;
+        let text: Vec<&str> = captures.iter().map(|c| c.1).collect();
+        T::from_captures(&text).map_err(|(i, expected)| error(captures[i].0, expected))
+    }
+
+    /// Parses a single line, reporting errors as line 1
+    #[track_caller]
+    pub fn parse_line<T: FromCaptures>(&self, line: &str) -> Result<T, ParseError> {
+        self.parse_numbered_line(line, 1)
+    }
+
+    /// Parses every line of `text`, stopping at the first error
+    #[track_caller]
+    pub fn parse_lines<T: FromCaptures>(&self, text: &str) -> Result<Vec<T>, ParseError> {
+        text.lines()
+            .enumerate()
+            .map(|(i, line)| self.parse_numbered_line(line, i + 1))
+            .collect()
+    }
+}
+
+/// Parses a single line using `pattern`, see [`LinePattern`]
+#[track_caller]
+pub fn scan<T: FromCaptures>(line: &str, pattern: &str) -> Result<T, ParseError> {
+    LinePattern::new(pattern).parse_line(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crate_moves() {
+        let text = "move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1";
+        let moves: Vec<(usize, usize, usize)> = LinePattern::new("move {} from {} to {}")
+            .parse_lines(text)
+            .unwrap();
+        assert_eq!(moves, vec![(1, 2, 1), (3, 1, 3), (2, 2, 1)]);
+
+        let (name, a, b): (String, u32, u32) = scan("class: 1-3", "{}: {}-{}").unwrap();
+        assert_eq!((name.as_str(), a, b), ("class", 1, 3));
+        let (path,): (String,) = scan("$ cd ..", "$ cd {}").unwrap();
+        assert_eq!(path, "..");
+    }
+
+    #[test]
+    fn errors_have_positions() {
+        let pattern = LinePattern::new("move {} from {} to {}");
+        let text = "move 1 from 2 to 1\nmove x from 1 to 3";
+        let err = pattern
+            .parse_lines::<(usize, usize, usize)>(text)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                line: 2,
+                column: 6,
+                expected: "a value of type `usize`".to_owned(),
+                found: "x from 1 to 3".to_owned(),
+            }
+        );
+
+        let err = pattern
+            .parse_line::<(usize, usize, usize)>("move 1 form 2 to 1")
+            .unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (6, "text followed by ` from `")
+        );
+
+        let err = pattern
+            .parse_line::<(usize, usize, usize)>("mov 1 from 2 to 1")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected `move `, found `mov 1 from 2 to 1`"
+        );
+
+        assert_eq!(scan::<(u8,)>("7 ", "{} "), Ok((7,)));
+        let err = scan::<(u8,)>("7 !", "{} ").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: expected end of line, found `!`"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn adjacent_captures() {
+        LinePattern::new("{}{}");
+    }
+}
@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::str::{FromStr, Lines};
 
+use crate::{ocr, FromCaptures, LinePattern, ParseError};
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum IsTest {
     Yes,
@@ -13,44 +18,132 @@ impl IsTest {
     }
 }
 
+/// Puzzle input, along with the values of the day's [`Param`]s
 #[derive(Clone, PartialEq, Eq, Debug)]
-pub struct Input(pub String, pub IsTest);
+pub struct Input(pub String, pub IsTest, pub BTreeMap<&'static str, String>);
 
+/// The answer to one part of a puzzle
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Output(pub String);
+pub enum Output {
+    Int(i128),
+    Str(String),
+    /// Text spanning several lines, usually letters drawn as art which are read with [`ocr`]
+    /// before submitting
+    Multiline(String),
+    /// The answer for a part that hasn't been solved yet. The runner skips these parts instead
+    /// of checking or submitting them
+    Unimplemented,
+}
+
+/// A named constant that differs between the example and the real puzzle, like the number of
+/// steps to simulate or the row to check
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value used for the example input from the puzzle page
+    pub example: &'static str,
+    /// The value used for the real puzzle input
+    pub real: &'static str,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, example: &'static str, real: &'static str) -> Self {
+        Self {
+            name,
+            example,
+            real,
+        }
+    }
+}
 
 pub trait AocDay {
     fn part1(&self, input: Input) -> Output;
     fn part2(&self, input: Input) -> Output;
+
+    /// The parameters this day reads with [`Input::param`]
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
 }
 
 impl Input {
+    /// Wraps puzzle input, normalizing line endings so Windows checkouts parse the same way
     pub fn new(inner: String, is_test: IsTest) -> Self {
-        Self(inner, is_test)
+        Self(
+            crate::input::normalize_line_endings(&inner),
+            is_test,
+            BTreeMap::new(),
+        )
     }
 
-    pub fn is_test(&self) -> bool {
-        matches!(self.1, IsTest::Yes)
+    /// Sets the parameter `name`, overriding the default from [`AocDay::params`]
+    pub fn with_param(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.2.insert(name, value.to_string());
+        self
     }
 
-    /// Returns an iterator over each line parsed as `T`
+    /// Fills in the example or real value of each parameter that hasn't been set already
+    pub fn with_params(mut self, params: &[Param]) -> Self {
+        for param in params {
+            let value = if self.is_test() {
+                param.example
+            } else {
+                param.real
+            };
+            self.2.entry(param.name).or_insert_with(|| value.to_owned());
+        }
+        self
+    }
+
+    /// Returns the value of the parameter `name`
     ///
     /// # Panics
-    /// This function will panic if any line fails to parse
-    pub fn nums<T>(&self) -> impl Iterator<Item = T> + '_
-    where
-        T: FromStr + std::fmt::Debug,
-    {
-        self.lines().map(|line| match line.parse::<T>() {
-            Ok(v) => v,
-            Err(_e) => panic!("failed to parse `{}`", line),
+    /// This function panics if `name` wasn't set, or if its value can't be parsed as `T`
+    #[track_caller]
+    pub fn param<T: FromStr>(&self, name: &str) -> T {
+        let value = self
+            .2
+            .get(name)
+            .unwrap_or_else(|| panic!("parameter `{}` was never set", name));
+        value.parse().unwrap_or_else(|_| {
+            panic!(
+                "parameter `{}` = `{}` is not a valid `{}`",
+                name,
+                value,
+                type_name::<T>()
+            )
         })
     }
 
-    pub fn ints(&self) -> impl Iterator<Item = i32> + '_ {
+    /// Returns every parameter that has been set, sorted by name
+    pub fn params(&self) -> impl Iterator<Item = (&'static str, &str)> + '_ {
+        self.2.iter().map(|(&name, value)| (name, value.as_str()))
+    }
+
+    pub fn is_test(&self) -> bool {
+        matches!(self.1, IsTest::Yes)
+    }
+
+    /// Parses each line as `T`, returning the position of the first line that fails
+    pub fn nums<T: FromStr>(&self) -> Result<Vec<T>, ParseError> {
+        let nums = self.parse_lines::<(T,)>("{}")?;
+        Ok(nums.into_iter().map(|(n,)| n).collect())
+    }
+
+    pub fn ints(&self) -> Result<Vec<i32>, ParseError> {
         self.nums()
     }
 
+    /// Parses each line using `pattern`, where every `{}` captures one field of `T`.
+    /// See [`LinePattern`] for how captures are matched
+    ///
+    /// # Panics
+    /// This function panics if `T` isn't built from the same number of captures as `pattern`
+    #[track_caller]
+    pub fn parse_lines<T: FromCaptures>(&self, pattern: &str) -> Result<Vec<T>, ParseError> {
+        LinePattern::new(pattern).parse_lines(&self.0)
+    }
+
     pub fn lines(&self) -> Lines {
         self.0.lines()
     }
@@ -77,21 +170,155 @@ impl AsRef<str> for Input {
     }
 }
 
+/// Trims the end of every line and any blank lines around `text`, and trims single lines
+/// entirely, so answers can be compared without worrying about how they were formatted
+fn normalize_whitespace(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let text = lines.join("\n");
+    let text = text.trim_matches('\n');
+    if text.contains('\n') {
+        text.to_owned()
+    } else {
+        text.trim().to_owned()
+    }
+}
+
 impl Output {
-    pub fn into_inner(self) -> String {
-        self.0
+    pub fn is_unimplemented(&self) -> bool {
+        matches!(self, Output::Unimplemented)
     }
 
-    pub fn as_str(&self) -> &str {
-        &self.0
+    /// Returns true if this answer is the same as `expected`, ignoring differences in whitespace.
+    /// Multi-line answers also match the letters they spell out
+    pub fn matches(&self, expected: &str) -> bool {
+        let expected = normalize_whitespace(expected);
+        match self {
+            Output::Unimplemented => false,
+            Output::Multiline(text) => {
+                normalize_whitespace(text) == expected
+                    || ocr(text).is_some_and(|letters| letters == expected)
+            }
+            _ => normalize_whitespace(&self.to_string()) == expected,
+        }
+    }
+
+    /// Returns the text to submit for this answer, or `None` if this part is unimplemented or the
+    /// letters in a multi-line answer couldn't be read
+    pub fn submission(&self) -> Option<String> {
+        match self {
+            Output::Int(n) => Some(n.to_string()),
+            Output::Str(s) => Some(s.trim().to_owned()),
+            Output::Multiline(text) => ocr(text),
+            Output::Unimplemented => None,
+        }
+    }
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Output::Int(n) => write!(f, "{}", n),
+            Output::Str(s) | Output::Multiline(s) => f.write_str(s),
+            Output::Unimplemented => f.write_str("not implemented"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Output {
+                fn from(n: $t) -> Self {
+                    Output::Int(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<u128> for Output {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Output::Int(n),
+            Err(_) => Output::Str(n.to_string()),
+        }
+    }
+}
+
+impl From<String> for Output {
+    fn from(s: String) -> Self {
+        if s.trim().contains('\n') {
+            Output::Multiline(s)
+        } else {
+            Output::Str(s)
+        }
+    }
+}
+
+impl From<&str> for Output {
+    fn from(s: &str) -> Self {
+        s.to_owned().into()
     }
 }
 
-impl<T> From<T> for Output
-where
-    T: ToString,
-{
-    fn from(s: T) -> Self {
-        Self(s.to_string())
+impl From<char> for Output {
+    fn from(c: char) -> Self {
+        Output::Str(c.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param::new("steps", "18", "256"),
+        Param::new("row", "10", "2000000"),
+    ];
+
+    #[test]
+    fn params() {
+        let input = Input::new(String::new(), IsTest::Yes).with_params(PARAMS);
+        assert_eq!(input.param::<u32>("steps"), 18);
+        assert_eq!(
+            input.params().collect::<Vec<_>>(),
+            vec![("row", "10"), ("steps", "18")]
+        );
+
+        let input = Input::new(String::new(), IsTest::No)
+            .with_param("row", 7)
+            .with_params(PARAMS);
+        assert_eq!(input.param::<u64>("steps"), 256);
+        assert_eq!(input.param::<i64>("row"), 7);
+    }
+
+    #[test]
+    fn outputs() {
+        assert_eq!(Output::from(42usize), Output::Int(42));
+        assert_eq!(
+            Output::from(u128::MAX).submission(),
+            Some(u128::MAX.to_string())
+        );
+        assert!(Output::from(-7i64).matches(" -7\n"));
+        assert!(!Output::from(7).matches("8"));
+        assert!(Output::from("CMZ").matches("CMZ\n\n"));
+        assert!(!Output::Unimplemented.matches("not implemented"));
+        assert_eq!(Output::Unimplemented.submission(), None);
+
+        let art = "#..#.####  \n#..#.#....\n####.###..\n#..#.#....\n#..#.#....\n#..#.#....\n";
+        let output = Output::from(art.to_owned());
+        assert!(matches!(output, Output::Multiline(_)));
+        assert!(output.matches(&format!("\n{}\n", art.replace("  \n", "\n"))));
+        assert!(output.matches("HF"));
+        assert_eq!(output.submission().as_deref(), Some("HF"));
+        assert_eq!(Output::from("##\n##".to_owned()).submission(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_param() {
+        Input::new(String::new(), IsTest::Yes).param::<u32>("steps");
     }
 }
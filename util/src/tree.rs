@@ -0,0 +1,343 @@
+//! An arena backed tree with parent links, for puzzles like the filesystem from 2022 day 7 or the
+//! snailfish numbers from 2021 day 18.
+//!
+//! ```
+//! # use util::tree::Tree;
+//! let mut tree = Tree::new("/".to_string());
+//! let root = tree.root();
+//! let b = tree.cd(root, "a/b", String::as_str, str::to_string);
+//! assert_eq!(tree.cd(b, "..", String::as_str, str::to_string), tree.parent(b).unwrap());
+//! assert_eq!(tree.to_string(), "- /\n  - a\n    - b\n");
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A handle to a node in a [`Tree`]. Handles of removed nodes may be reused for new nodes
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    data: T,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Tree<T> {
+    nodes: Vec<Option<Node<T>>>,
+    /// Slots of removed nodes that can be reused
+    free: Vec<usize>,
+}
+
+impl<T> Tree<T> {
+    /// Creates a tree with a single root node
+    pub fn new(root: T) -> Self {
+        Self {
+            nodes: vec![Some(Node {
+                data: root,
+                parent: None,
+                children: Vec::new(),
+            })],
+            free: Vec::new(),
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    /// Returns the number of nodes in the tree
+    pub fn len(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    /// Always false, since a tree can't remove its root
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    #[track_caller]
+    fn node(&self, id: NodeId) -> &Node<T> {
+        self.nodes[id.0].as_ref().expect("node was removed")
+    }
+
+    #[track_caller]
+    fn node_mut(&mut self, id: NodeId) -> &mut Node<T> {
+        self.nodes[id.0].as_mut().expect("node was removed")
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id).parent
+    }
+
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.node(id).children
+    }
+
+    pub fn is_leaf(&self, id: NodeId) -> bool {
+        self.node(id).children.is_empty()
+    }
+
+    /// Returns the number of edges between `id` and the root
+    pub fn depth(&self, id: NodeId) -> usize {
+        self.ancestors(id).count()
+    }
+
+    /// Returns the parent of `id`, then its parent and so on up to the root
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), |&id| self.parent(id))
+    }
+
+    /// Adds `data` as the last child of `parent`
+    pub fn push_child(&mut self, parent: NodeId, data: T) -> NodeId {
+        let index = self.node(parent).children.len();
+        self.insert_child(parent, index, data)
+    }
+
+    /// Adds `data` as the child of `parent` at `index`, shifting later children along
+    ///
+    /// # Panics
+    /// This function panics if `index` is greater than the number of children
+    #[track_caller]
+    pub fn insert_child(&mut self, parent: NodeId, index: usize, data: T) -> NodeId {
+        let node = Some(Node {
+            data,
+            parent: Some(parent),
+            children: Vec::new(),
+        });
+        let id = match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot] = node;
+                NodeId(slot)
+            }
+            None => {
+                self.nodes.push(node);
+                NodeId(self.nodes.len() - 1)
+            }
+        };
+        self.node_mut(parent).children.insert(index, id);
+        id
+    }
+
+    /// Moves every node of `other` into this tree, with its root as the last child of `parent`.
+    /// Returns the new id of `other`'s root
+    pub fn append_tree(&mut self, parent: NodeId, mut other: Tree<T>) -> NodeId {
+        let mut ids = BTreeMap::new();
+        for old in other.pre_order(other.root()) {
+            let node = other.nodes[old.0].take().unwrap();
+            let new_parent = node.parent.map_or(parent, |p| ids[&p]);
+            ids.insert(old, self.push_child(new_parent, node.data));
+        }
+        ids[&other.root()]
+    }
+
+    /// Removes `id` and all of its descendants, returning the data of `id`
+    ///
+    /// # Panics
+    /// This function panics if `id` is the root
+    #[track_caller]
+    pub fn remove(&mut self, id: NodeId) -> T {
+        let parent = self.parent(id).expect("can't remove the root");
+        self.node_mut(parent).children.retain(|&c| c != id);
+        self.clear_children(id);
+        self.free.push(id.0);
+        self.nodes[id.0].take().unwrap().data
+    }
+
+    /// Removes every descendant of `id`, turning it into a leaf
+    pub fn clear_children(&mut self, id: NodeId) {
+        let children = std::mem::take(&mut self.node_mut(id).children);
+        for child in children {
+            self.clear_children(child);
+            self.nodes[child.0] = None;
+            self.free.push(child.0);
+        }
+    }
+
+    /// Returns the first child of `parent` whose data matches `pred`
+    pub fn find_child(&self, parent: NodeId, mut pred: impl FnMut(&T) -> bool) -> Option<NodeId> {
+        self.children(parent)
+            .iter()
+            .copied()
+            .find(|&c| pred(&self[c]))
+    }
+
+    /// Follows `path` from `from` like `cd` in a shell and returns where it ends up.
+    ///
+    /// Path components are separated by `/`, and a leading `/` starts at the root. `..` moves to
+    /// the parent (staying put at the root), `.` stays put, and any other component moves to the
+    /// child whose `name` matches it, creating that child with `make` if it doesn't exist yet.
+    pub fn cd(
+        &mut self,
+        from: NodeId,
+        path: &str,
+        name: impl Fn(&T) -> &str,
+        mut make: impl FnMut(&str) -> T,
+    ) -> NodeId {
+        let mut current = if path.starts_with('/') {
+            self.root()
+        } else {
+            from
+        };
+        for part in path.split('/').filter(|p| !p.is_empty()) {
+            current = match part {
+                "." => current,
+                ".." => self.parent(current).unwrap_or(current),
+                _ => match self.find_child(current, |data| name(data) == part) {
+                    Some(child) => child,
+                    None => self.push_child(current, make(part)),
+                },
+            };
+        }
+        current
+    }
+
+    /// Returns `id` and all of its descendants, each node before its children
+    pub fn pre_order(&self, id: NodeId) -> Vec<NodeId> {
+        let mut order = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            order.push(id);
+            stack.extend(self.children(id).iter().rev());
+        }
+        order
+    }
+
+    /// Returns `id` and all of its descendants, each node after its children
+    pub fn post_order(&self, id: NodeId) -> Vec<NodeId> {
+        let mut order = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            order.push(id);
+            stack.extend(self.children(id));
+        }
+        order.reverse();
+        order
+    }
+
+    /// Returns the leaves under `id` from left to right
+    pub fn leaves(&self, id: NodeId) -> Vec<NodeId> {
+        self.pre_order(id)
+            .into_iter()
+            .filter(|&id| self.is_leaf(id))
+            .collect()
+    }
+
+    /// Computes a value for `id` and each of its descendants, bottom up. `f` gets each node's
+    /// data and the values of its children, in order.
+    ///
+    /// ```
+    /// # use util::tree::Tree;
+    /// let mut tree = Tree::new(0);
+    /// let a = tree.push_child(tree.root(), 0);
+    /// tree.push_child(a, 3);
+    /// tree.push_child(a, 4);
+    /// tree.push_child(tree.root(), 5);
+    ///
+    /// let sizes = tree.aggregate(tree.root(), |&size, children| size + children.iter().sum::<i32>());
+    /// assert_eq!(sizes[&a], 7);
+    /// assert_eq!(sizes[&tree.root()], 12);
+    /// ```
+    pub fn aggregate<A: Clone>(
+        &self,
+        id: NodeId,
+        mut f: impl FnMut(&T, &[A]) -> A,
+    ) -> BTreeMap<NodeId, A> {
+        let mut values: BTreeMap<NodeId, A> = BTreeMap::new();
+        for id in self.post_order(id) {
+            let children: Vec<A> = self
+                .children(id)
+                .iter()
+                .map(|c| values[c].clone())
+                .collect();
+            values.insert(id, f(&self[id], &children));
+        }
+        values
+    }
+}
+
+impl<T> Index<NodeId> for Tree<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, id: NodeId) -> &T {
+        &self.node(id).data
+    }
+}
+
+impl<T> IndexMut<NodeId> for Tree<T> {
+    #[track_caller]
+    fn index_mut(&mut self, id: NodeId) -> &mut T {
+        &mut self.node_mut(id).data
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Tree<T> {
+    /// Writes one node per line as `- data`, indented two spaces for each level
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut stack = vec![(self.root(), 0)];
+        while let Some((id, depth)) = stack.pop() {
+            writeln!(f, "{:indent$}- {}", "", self[id], indent = depth * 2)?;
+            stack.extend(self.children(id).iter().rev().map(|&c| (c, depth + 1)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editing() {
+        let mut tree = Tree::new('a');
+        let root = tree.root();
+        let b = tree.push_child(root, 'b');
+        let d = tree.push_child(root, 'd');
+        let c = tree.insert_child(root, 1, 'c');
+        let e = tree.push_child(b, 'e');
+        assert_eq!(tree.children(root), [b, c, d]);
+        assert_eq!(tree.depth(e), 2);
+        assert_eq!(tree.ancestors(e).collect::<Vec<_>>(), [b, root]);
+        assert_eq!(tree.leaves(root), [e, c, d]);
+        assert_eq!(
+            tree.post_order(root)
+                .into_iter()
+                .map(|id| tree[id])
+                .collect::<String>(),
+            "ebcda"
+        );
+
+        assert_eq!(tree.remove(b), 'b');
+        assert_eq!(tree.len(), 3);
+        let f = tree.push_child(c, 'f');
+        tree[f] = 'F';
+        assert_eq!(tree.to_string(), "- a\n  - c\n    - F\n  - d\n");
+        assert_eq!(tree.len(), 4);
+
+        let mut other = Tree::new('x');
+        other.push_child(other.root(), 'y');
+        let x = tree.append_tree(d, other);
+        assert_eq!(tree[tree.children(x)[0]], 'y');
+        assert_eq!(tree.parent(x), Some(d));
+        tree.clear_children(root);
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn navigation() {
+        let mut tree = Tree::new(String::from("/"));
+        let name = String::as_str;
+        let root = tree.root();
+        let a = tree.cd(root, "a", name, str::to_string);
+        let e = tree.cd(a, "e", name, str::to_string);
+        assert_eq!(tree.cd(e, "../..", name, str::to_string), root);
+        assert_eq!(tree.cd(e, "/a/./e", name, str::to_string), e);
+        assert_eq!(tree.cd(root, "..", name, str::to_string), root);
+        assert_eq!(tree.find_child(a, |n| n == "e"), Some(e));
+        assert_eq!(tree.len(), 3);
+    }
+}
@@ -0,0 +1,602 @@
+use std::collections::{HashSet, VecDeque};
+use std::ops::ControlFlow;
+
+/// The type of every register and literal in a [`Vm`]
+pub type Word = i64;
+
+/// An instruction argument, either a register index or a literal value
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Register(usize),
+    Literal(Word),
+}
+
+/// What a [`Vm`] should do after executing an instruction
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Flow {
+    /// Continue with the next instruction
+    Next,
+    /// Move the instruction pointer by this many instructions
+    Jump(Word),
+    /// Continue at this instruction index
+    Goto(usize),
+    /// Stop running. The instruction pointer is left on this instruction
+    Halt,
+    /// The instruction is waiting on input. It is retried once [`State::input`] is non-empty
+    Blocked,
+}
+
+/// Why a [`Vm`] stopped running
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Exit {
+    /// The instruction pointer left the program, or an instruction returned [`Flow::Halt`]
+    Halted,
+    /// An instruction returned [`Flow::Blocked`]
+    Blocked,
+    /// The instruction at `ip` was about to execute in a state that was already seen
+    Loop { ip: usize },
+    /// A cycle hook returned [`ControlFlow::Break`]
+    Stopped,
+}
+
+/// An instruction set for a [`Vm`], usually implemented by a plain enum of opcodes.
+///
+/// ```ignore
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// enum Op { Nop, Acc, Jmp }
+///
+/// impl Opcode for Op {
+///     const ALL: &'static [Self] = &[Op::Nop, Op::Acc, Op::Jmp];
+///     fn mnemonic(self) -> &'static str { ... }
+///     fn arity(self) -> usize { 1 }
+///     fn execute(self, args: &[Operand], state: &mut State) -> Flow { ... }
+/// }
+/// ```
+pub trait Opcode: Copy + Eq + std::fmt::Debug + 'static {
+    /// Every opcode, used to look up mnemonics when parsing
+    const ALL: &'static [Self];
+
+    fn mnemonic(self) -> &'static str;
+
+    /// The number of operands this opcode takes
+    fn arity(self) -> usize;
+
+    /// The number of cycles this opcode takes to execute
+    fn cycles(self) -> u64 {
+        1
+    }
+
+    fn execute(self, args: &[Operand], state: &mut State) -> Flow;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Instruction<O> {
+    pub op: O,
+    pub args: Vec<Operand>,
+}
+
+impl<O: Opcode> Instruction<O> {
+    pub fn new(op: O, args: Vec<Operand>) -> Self {
+        Self { op, args }
+    }
+
+    /// Parses a single instruction like `addx -5`, `jio a, +19` or `mul x w`.
+    ///
+    /// Operands may be separated by whitespace or commas. Operands that match one of
+    /// `registers` become [`Operand::Register`], anything else must be an integer
+    pub fn parse(line: &str, registers: &[&str]) -> Result<Self, String> {
+        let mut parts = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty());
+        let mnemonic = parts.next().ok_or("empty instruction")?;
+        let op = *O::ALL
+            .iter()
+            .find(|op| op.mnemonic() == mnemonic)
+            .ok_or_else(|| format!("unknown instruction `{mnemonic}`"))?;
+
+        let args = parts
+            .map(|arg| match registers.iter().position(|&r| r == arg) {
+                Some(index) => Ok(Operand::Register(index)),
+                None => arg
+                    .trim_start_matches('+')
+                    .parse()
+                    .map(Operand::Literal)
+                    .map_err(|_| format!("bad operand `{arg}` in `{line}`")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if args.len() != op.arity() {
+            return Err(format!(
+                "`{mnemonic}` takes {} operands, got {} in `{line}`",
+                op.arity(),
+                args.len()
+            ));
+        }
+        Ok(Self { op, args })
+    }
+}
+
+/// A list of instructions for a [`Vm`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Program<O> {
+    pub instructions: Vec<Instruction<O>>,
+}
+
+impl<O: Opcode> Program<O> {
+    /// Parses one instruction per non-empty line, see [`Instruction::parse`]
+    pub fn parse(input: &str, registers: &[&str]) -> Result<Self, String> {
+        let instructions = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                Instruction::parse(line, registers).map_err(|e| format!("line {}: {e}", i + 1))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { instructions })
+    }
+
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Returns a copy of this program with the instruction at `index` replaced
+    ///
+    /// # Panics
+    /// This function panics if `index` is out of bounds
+    #[track_caller]
+    pub fn patched(&self, index: usize, instruction: Instruction<O>) -> Self {
+        let mut program = self.clone();
+        program.instructions[index] = instruction;
+        program
+    }
+
+    /// Tries replacing each instruction for which `patch` returns a replacement, one at a time,
+    /// and returns the index of the first patch for which `test` succeeds along with its result.
+    ///
+    /// This is the "fix exactly one instruction" search from 2020 day 8
+    pub fn find_patch<R>(
+        &self,
+        patch: impl Fn(&Instruction<O>) -> Option<Instruction<O>>,
+        mut test: impl FnMut(Program<O>) -> Option<R>,
+    ) -> Option<(usize, R)> {
+        self.instructions.iter().enumerate().find_map(|(i, ins)| {
+            let replacement = patch(ins)?;
+            test(self.patched(i, replacement)).map(|r| (i, r))
+        })
+    }
+}
+
+/// A fixed set of named registers
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Registers {
+    names: &'static [&'static str],
+    values: Vec<Word>,
+}
+
+impl Registers {
+    /// Creates a register file with one register per name, all set to zero
+    pub fn new(names: &'static [&'static str]) -> Self {
+        Self {
+            names,
+            values: vec![0; names.len()],
+        }
+    }
+
+    /// Sets the initial value of a register
+    ///
+    /// # Panics
+    /// This function panics if there is no register called `name`
+    #[track_caller]
+    pub fn with(mut self, name: &str, value: Word) -> Self {
+        *self.by_name_mut(name) = value;
+        self
+    }
+
+    pub fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+
+    pub fn values(&self) -> &[Word] {
+        &self.values
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|&n| n == name)
+    }
+
+    /// # Panics
+    /// This function panics if there is no register called `name`
+    #[track_caller]
+    pub fn by_name(&self, name: &str) -> Word {
+        match self.index_of(name) {
+            Some(index) => self.values[index],
+            None => panic!("no register named `{name}`"),
+        }
+    }
+
+    /// # Panics
+    /// This function panics if there is no register called `name`
+    #[track_caller]
+    pub fn by_name_mut(&mut self, name: &str) -> &mut Word {
+        match self.index_of(name) {
+            Some(index) => &mut self.values[index],
+            None => panic!("no register named `{name}`"),
+        }
+    }
+
+    /// Returns the value of a literal, or the current value of a register
+    pub fn read(&self, operand: Operand) -> Word {
+        match operand {
+            Operand::Register(index) => self.values[index],
+            Operand::Literal(value) => value,
+        }
+    }
+
+    /// Returns the register referred to by `operand`
+    ///
+    /// # Panics
+    /// This function panics if `operand` is a literal
+    #[track_caller]
+    pub fn get_mut(&mut self, operand: Operand) -> &mut Word {
+        match operand {
+            Operand::Register(index) => &mut self.values[index],
+            Operand::Literal(value) => panic!("cannot write to literal {value}"),
+        }
+    }
+}
+
+/// Everything an instruction can access while executing
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct State {
+    pub registers: Registers,
+    pub input: VecDeque<Word>,
+    pub output: Vec<Word>,
+}
+
+impl State {
+    pub fn read(&self, operand: Operand) -> Word {
+        self.registers.read(operand)
+    }
+
+    /// # Panics
+    /// This function panics if `operand` is a literal
+    #[track_caller]
+    pub fn write(&mut self, operand: Operand, value: Word) {
+        *self.registers.get_mut(operand) = value;
+    }
+}
+
+/// How a [`Vm`] decides it is stuck in a loop
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum LoopDetection {
+    None,
+    /// An instruction is about to run a second time
+    Ip,
+    /// An instruction is about to run with the same registers as before
+    State,
+}
+
+/// Runs a [`Program`] one instruction at a time, counting cycles
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Vm<O> {
+    program: Program<O>,
+    pub state: State,
+    ip: isize,
+    cycle: u64,
+    /// Cycles of the instruction at `ip` that were already passed to a hook, but not counted in
+    /// `cycle` because the hook stopped part way through or the instruction blocked
+    pending: u64,
+}
+
+impl<O: Opcode> Vm<O> {
+    pub fn new(program: Program<O>, registers: Registers) -> Self {
+        Self {
+            program,
+            state: State {
+                registers,
+                input: VecDeque::new(),
+                output: Vec::new(),
+            },
+            ip: 0,
+            cycle: 0,
+            pending: 0,
+        }
+    }
+
+    pub fn program(&self) -> &Program<O> {
+        &self.program
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.state.registers
+    }
+
+    /// The index of the next instruction to execute. May be outside the program once halted
+    pub fn ip(&self) -> isize {
+        self.ip
+    }
+
+    /// The number of cycles taken by the instructions that have finished executing
+    pub fn cycle(&self) -> u64 {
+        self.cycle
+    }
+
+    fn current(&self) -> Option<&Instruction<O>> {
+        usize::try_from(self.ip)
+            .ok()
+            .and_then(|ip| self.program.instructions.get(ip))
+    }
+
+    /// Executes one instruction, calling `hook` once for every cycle it takes with the state
+    /// before the instruction takes effect (the "during the cycle" values from 2022 day 10).
+    /// Returns `Some` if the machine stopped.
+    ///
+    /// If a hook stops part way through an instruction, or the instruction blocks, the next step
+    /// carries on from the first cycle that no hook has seen yet, so every cycle is passed to a
+    /// hook exactly once
+    pub fn step_with_hook(
+        &mut self,
+        mut hook: impl FnMut(u64, &State) -> ControlFlow<()>,
+    ) -> Option<Exit> {
+        let instruction = self.current()?.clone();
+        while self.pending < instruction.op.cycles() {
+            self.pending += 1;
+            if hook(self.cycle + self.pending, &self.state).is_break() {
+                return Some(Exit::Stopped);
+            }
+        }
+        let flow = instruction.op.execute(&instruction.args, &mut self.state);
+        if flow == Flow::Blocked {
+            return Some(Exit::Blocked);
+        }
+        self.cycle += std::mem::take(&mut self.pending);
+        match flow {
+            Flow::Next => self.ip += 1,
+            Flow::Jump(offset) => self.ip += offset as isize,
+            Flow::Goto(target) => self.ip = target as isize,
+            Flow::Halt => return Some(Exit::Halted),
+            Flow::Blocked => unreachable!(),
+        }
+        None
+    }
+
+    /// Executes one instruction. Returns `Some` if the machine stopped
+    pub fn step(&mut self) -> Option<Exit> {
+        self.step_with_hook(|_, _| ControlFlow::Continue(()))
+    }
+
+    fn run_inner(
+        &mut self,
+        detection: LoopDetection,
+        mut hook: impl FnMut(u64, &State) -> ControlFlow<()>,
+    ) -> Exit {
+        let mut seen_ips = HashSet::new();
+        let mut seen_states = HashSet::new();
+        loop {
+            if self.current().is_none() {
+                return Exit::Halted;
+            }
+            let repeated = match detection {
+                LoopDetection::None => false,
+                LoopDetection::Ip => !seen_ips.insert(self.ip),
+                LoopDetection::State => {
+                    !seen_states.insert((self.ip, self.state.registers.values.clone()))
+                }
+            };
+            if repeated {
+                return Exit::Loop {
+                    ip: self.ip as usize,
+                };
+            }
+            if let Some(exit) = self.step_with_hook(&mut hook) {
+                return exit;
+            }
+        }
+    }
+
+    /// Runs until the program halts or blocks on input. Never returns if the program loops forever
+    pub fn run(&mut self) -> Exit {
+        self.run_inner(LoopDetection::None, |_, _| ControlFlow::Continue(()))
+    }
+
+    /// Like [`Vm::run`], but calls `hook` at every cycle, see [`Vm::step_with_hook`]
+    pub fn run_with_hook(&mut self, hook: impl FnMut(u64, &State) -> ControlFlow<()>) -> Exit {
+        self.run_inner(LoopDetection::None, hook)
+    }
+
+    /// Runs until the program halts, blocks, or is about to execute any instruction for the
+    /// second time
+    pub fn run_until_loop(&mut self) -> Exit {
+        self.run_inner(LoopDetection::Ip, |_, _| ControlFlow::Continue(()))
+    }
+
+    /// Runs until the program halts, blocks, or is about to execute an instruction with exactly the
+    /// same register values as a previous time. Unlike [`Vm::run_until_loop`] this allows loops
+    /// that make progress
+    pub fn run_until_state_repeats(&mut self) -> Exit {
+        self.run_inner(LoopDetection::State, |_, _| ControlFlow::Continue(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The handheld console from 2020 day 8
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    enum Console {
+        Nop,
+        Acc,
+        Jmp,
+    }
+
+    impl Opcode for Console {
+        const ALL: &'static [Self] = &[Console::Nop, Console::Acc, Console::Jmp];
+
+        fn mnemonic(self) -> &'static str {
+            match self {
+                Console::Nop => "nop",
+                Console::Acc => "acc",
+                Console::Jmp => "jmp",
+            }
+        }
+
+        fn arity(self) -> usize {
+            1
+        }
+
+        fn execute(self, args: &[Operand], state: &mut State) -> Flow {
+            match self {
+                Console::Nop => Flow::Next,
+                Console::Acc => {
+                    *state.registers.by_name_mut("acc") += state.read(args[0]);
+                    Flow::Next
+                }
+                Console::Jmp => Flow::Jump(state.read(args[0])),
+            }
+        }
+    }
+
+    /// The CPU from 2022 day 10
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    enum Crt {
+        Noop,
+        Addx,
+    }
+
+    impl Opcode for Crt {
+        const ALL: &'static [Self] = &[Crt::Noop, Crt::Addx];
+
+        fn mnemonic(self) -> &'static str {
+            match self {
+                Crt::Noop => "noop",
+                Crt::Addx => "addx",
+            }
+        }
+
+        fn arity(self) -> usize {
+            match self {
+                Crt::Noop => 0,
+                Crt::Addx => 1,
+            }
+        }
+
+        fn cycles(self) -> u64 {
+            match self {
+                Crt::Noop => 1,
+                Crt::Addx => 2,
+            }
+        }
+
+        fn execute(self, args: &[Operand], state: &mut State) -> Flow {
+            if self == Crt::Addx {
+                *state.registers.get_mut(Operand::Register(0)) += state.read(args[0]);
+            }
+            Flow::Next
+        }
+    }
+
+    const CONSOLE_EXAMPLE: &str =
+        "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+    #[test]
+    fn console_loop_and_patch() {
+        let program: Program<Console> = Program::parse(CONSOLE_EXAMPLE, &[]).unwrap();
+        let mut vm = Vm::new(program.clone(), Registers::new(&["acc"]));
+        assert_eq!(vm.run_until_loop(), Exit::Loop { ip: 1 });
+        assert_eq!(vm.registers().by_name("acc"), 5);
+
+        let swap = |ins: &Instruction<Console>| {
+            let op = match ins.op {
+                Console::Nop => Console::Jmp,
+                Console::Jmp => Console::Nop,
+                Console::Acc => return None,
+            };
+            Some(Instruction::new(op, ins.args.clone()))
+        };
+        let fixed = program.find_patch(swap, |patched| {
+            let mut vm = Vm::new(patched, Registers::new(&["acc"]));
+            match vm.run_until_loop() {
+                Exit::Halted => Some(vm.registers().by_name("acc")),
+                _ => None,
+            }
+        });
+        assert_eq!(fixed, Some((7, 8)));
+    }
+
+    #[test]
+    fn cycle_hook() {
+        let program = Program::parse("noop\naddx 3\naddx -5", &["x"]).unwrap();
+        let mut vm = Vm::<Crt>::new(program, Registers::new(&["x"]).with("x", 1));
+        let mut during = Vec::new();
+        let exit = vm.run_with_hook(|cycle, state| {
+            during.push((cycle, state.registers.by_name("x")));
+            ControlFlow::Continue(())
+        });
+        assert_eq!(exit, Exit::Halted);
+        assert_eq!(during, vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!(vm.registers().by_name("x"), -1);
+        assert_eq!(vm.cycle(), 5);
+
+        let mut vm = Vm::<Crt>::new(vm.program().clone(), Registers::new(&["x"]));
+        let exit = vm.run_with_hook(|cycle, _| match cycle {
+            3 => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        });
+        assert_eq!(exit, Exit::Stopped);
+    }
+
+    #[test]
+    fn resume_mid_instruction() {
+        let program = Program::parse("addx 3\naddx -5", &["x"]).unwrap();
+        let mut vm = Vm::<Crt>::new(program, Registers::new(&["x"]).with("x", 1));
+        let mut seen = Vec::new();
+        let exit = vm.run_with_hook(|cycle, _| {
+            seen.push(cycle);
+            match cycle {
+                1 => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            }
+        });
+        assert_eq!(exit, Exit::Stopped);
+        // the first addx hasn't finished, so nothing is counted or changed yet
+        assert_eq!((vm.ip(), vm.cycle()), (0, 0));
+        assert_eq!(vm.registers().by_name("x"), 1);
+
+        let exit = vm.run_with_hook(|cycle, _| {
+            seen.push(cycle);
+            ControlFlow::Continue(())
+        });
+        assert_eq!(exit, Exit::Halted);
+        assert_eq!(seen, vec![1, 2, 3, 4]);
+        assert_eq!(vm.cycle(), 4);
+        assert_eq!(vm.registers().by_name("x"), -1);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Program::<Console>::parse("nop +0\nfoo 1", &[]).is_err());
+        assert!(Program::<Console>::parse("acc", &[]).is_err());
+        assert!(Program::<Crt>::parse("addx y", &["x"]).is_err());
+        let ins = Instruction::<Crt>::parse("addx x", &["x"]).unwrap();
+        assert_eq!(ins.args, vec![Operand::Register(0)]);
+    }
+
+    #[test]
+    fn state_repeat() {
+        let program = Program::<Console>::parse("acc +1\njmp -1", &[]).unwrap();
+        let mut vm = Vm::new(program.clone(), Registers::new(&["acc"]));
+        assert_eq!(vm.run_until_loop(), Exit::Loop { ip: 0 });
+
+        // without the acc the registers never change, so the second visit is a repeat
+        let nop = Instruction::new(Console::Nop, vec![Operand::Literal(0)]);
+        let mut vm = Vm::new(program.patched(0, nop), Registers::new(&["acc"]));
+        assert_eq!(vm.run_until_state_repeats(), Exit::Loop { ip: 0 });
+        assert_eq!(vm.cycle(), 2);
+    }
+}
@@ -4,12 +4,19 @@ struct Day1;
 
 impl AocDay for Day1 {
     fn part1(&self, i: Input) -> Output {
-        i.ints().map(|a| a / 3 - 2).sum::<i32>().into()
+        i.ints()
+            .unwrap()
+            .into_iter()
+            .map(|a| a / 3 - 2)
+            .sum::<i32>()
+            .into()
     }
 
     fn part2(&self, i: Input) -> Output {
         let mut total = i
             .ints()
+            .unwrap()
+            .into_iter()
             .map(|a| {
                 let mut old_mass = a;
                 let mut total = 0;
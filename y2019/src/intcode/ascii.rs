@@ -0,0 +1,104 @@
+use util::Matrix;
+
+use super::{IntcodeError, Machine, Word};
+
+/// Output from an ASCII capable intcode program (2019 days 17, 21 and 25), split into printable
+/// text and any values outside the ASCII range, which usually hold the puzzle answer
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AsciiOutput {
+    pub text: String,
+    pub values: Vec<Word>,
+}
+
+impl AsciiOutput {
+    pub fn from_words(words: impl IntoIterator<Item = Word>) -> Self {
+        let mut output = Self::default();
+        for word in words {
+            match u8::try_from(word) {
+                Ok(b) if b.is_ascii() => output.text.push(b as char),
+                _ => output.values.push(word),
+            }
+        }
+        output
+    }
+
+    /// Returns the last value outside the ASCII range, if any
+    pub fn answer(&self) -> Option<Word> {
+        self.values.last().copied()
+    }
+
+    /// Parses the text as a grid of characters, ignoring trailing blank lines
+    pub fn to_matrix(&self) -> Result<Matrix<u8>, String> {
+        Matrix::new_from_chars(self.text.trim_end())
+    }
+}
+
+impl std::fmt::Display for AsciiOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl Machine {
+    /// Queues the bytes of `s` as input
+    pub fn push_str(&mut self, s: &str) {
+        self.extend_input(s.bytes().map(Word::from));
+    }
+
+    /// Queues the bytes of `line` as input, followed by a newline
+    pub fn push_line(&mut self, line: &str) {
+        self.push_str(line);
+        self.push_input(b'\n' as Word);
+    }
+
+    /// Queues `commands` as input, one per line, then runs until the machine needs more input or
+    /// halts
+    pub fn run_ascii<'a>(
+        &mut self,
+        commands: impl IntoIterator<Item = &'a str>,
+    ) -> Result<AsciiOutput, IntcodeError> {
+        for command in commands {
+            self.push_line(command);
+        }
+        self.run()?;
+        Ok(AsciiOutput::from_words(self.take_output()))
+    }
+
+    /// Runs until the machine needs input or halts, and parses the output as a grid of
+    /// characters, such as the camera view in 2019 day 17
+    pub fn run_matrix(&mut self) -> Result<Matrix<u8>, String> {
+        self.run_ascii([]).map_err(|e| e.to_string())?.to_matrix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn echo_lines() {
+        // copies input to output until it reads a 0
+        let mut machine = Machine::parse("3,100,1006,100,10,4,100,1105,1,0,104,1000,99").unwrap();
+        let output = machine.run_ascii(["hello", "world"]).unwrap();
+        assert_eq!(output.to_string(), "hello\nworld\n");
+        assert_eq!(output.answer(), None);
+
+        machine.push_input(0);
+        let output = machine.run_ascii([]).unwrap();
+        assert_eq!(output.text, "");
+        assert_eq!(output.answer(), Some(1000));
+        assert!(machine.is_halted());
+    }
+
+    #[test]
+    fn camera_matrix() {
+        let mut program = Vec::new();
+        for b in "#.\n.#\n\n".bytes() {
+            program.extend([104, b as Word]);
+        }
+        program.push(99);
+        let mat = Machine::new(program).run_matrix().unwrap();
+        assert_eq!(mat.rows(), 2);
+        assert_eq!(mat.format_as_chars(), "#.\n.#");
+    }
+}
@@ -0,0 +1,202 @@
+use super::{IntcodeError, Word};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Add,
+    Multiply,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustRelativeBase,
+    Halt,
+}
+
+/// How a parameter is interpreted
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// The parameter is an address in memory
+    Position,
+    /// The parameter is the value itself
+    Immediate,
+    /// The parameter is an address relative to the relative base
+    Relative,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Param {
+    pub mode: Mode,
+    pub value: Word,
+}
+
+/// A single decoded instruction
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    params: [Param; 3],
+}
+
+impl Opcode {
+    pub fn from_word(word: Word) -> Option<Opcode> {
+        Some(match word {
+            1 => Opcode::Add,
+            2 => Opcode::Multiply,
+            3 => Opcode::Input,
+            4 => Opcode::Output,
+            5 => Opcode::JumpIfTrue,
+            6 => Opcode::JumpIfFalse,
+            7 => Opcode::LessThan,
+            8 => Opcode::Equals,
+            9 => Opcode::AdjustRelativeBase,
+            99 => Opcode::Halt,
+            _ => return None,
+        })
+    }
+
+    pub fn param_count(self) -> usize {
+        match self {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => 3,
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => 2,
+            Opcode::Input | Opcode::Output | Opcode::AdjustRelativeBase => 1,
+            Opcode::Halt => 0,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Add => "add",
+            Opcode::Multiply => "mul",
+            Opcode::Input => "in",
+            Opcode::Output => "out",
+            Opcode::JumpIfTrue => "jnz",
+            Opcode::JumpIfFalse => "jz",
+            Opcode::LessThan => "lt",
+            Opcode::Equals => "eq",
+            Opcode::AdjustRelativeBase => "arb",
+            Opcode::Halt => "halt",
+        }
+    }
+}
+
+impl Mode {
+    pub fn from_word(word: Word) -> Option<Mode> {
+        Some(match word {
+            0 => Mode::Position,
+            1 => Mode::Immediate,
+            2 => Mode::Relative,
+            _ => return None,
+        })
+    }
+}
+
+impl Instruction {
+    /// Decodes the instruction starting at `ip`. Memory past the end of `memory` reads as zero
+    pub fn decode(memory: &[Word], ip: usize) -> Result<Instruction, IntcodeError> {
+        let read = |addr: usize| memory.get(addr).copied().unwrap_or(0);
+        let word = read(ip);
+        let opcode = Opcode::from_word(word % 100)
+            .ok_or(IntcodeError::UnknownOpcode { ip, opcode: word })?;
+
+        let mut params = [Param {
+            mode: Mode::Position,
+            value: 0,
+        }; 3];
+        let mut modes = word / 100;
+        for (i, param) in params.iter_mut().enumerate().take(opcode.param_count()) {
+            param.mode = Mode::from_word(modes % 10).ok_or(IntcodeError::UnknownMode {
+                ip,
+                instruction: word,
+            })?;
+            param.value = read(ip + 1 + i);
+            modes /= 10;
+        }
+        Ok(Instruction { opcode, params })
+    }
+
+    pub fn params(&self) -> &[Param] {
+        &self.params[..self.opcode.param_count()]
+    }
+
+    /// Returns the number of words this instruction takes up in memory
+    pub fn width(&self) -> usize {
+        1 + self.opcode.param_count()
+    }
+}
+
+impl std::fmt::Display for Param {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.mode {
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Immediate => write!(f, "{}", self.value),
+            Mode::Relative if self.value < 0 => write!(f, "[rb{}]", self.value),
+            Mode::Relative => write!(f, "[rb+{}]", self.value),
+        }
+    }
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.opcode.mnemonic())?;
+        for (i, param) in self.params().iter().enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(f, "{sep}{param}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Produces a human readable listing of `program`, one instruction per line prefixed by its
+/// address.
+///
+/// Words that do not decode to a valid instruction (or whose parameters would run past the end of
+/// `program`) are listed as `data`. Since intcode programs
+/// mix code and data freely, data that happens to look like an instruction is listed as one
+pub fn disassemble(program: &[Word]) -> String {
+    let mut result = String::new();
+    let mut ip = 0;
+    while ip < program.len() {
+        match Instruction::decode(program, ip) {
+            Ok(instruction) if ip + instruction.width() <= program.len() => {
+                result += &format!("{ip:>5}: {instruction}\n");
+                ip += instruction.width();
+            }
+            _ => {
+                result += &format!("{ip:>5}: data {}\n", program[ip]);
+                ip += 1;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_modes() {
+        let ins = Instruction::decode(&[1002, 4, 3, 4, 33], 0).unwrap();
+        assert_eq!(ins.opcode, Opcode::Multiply);
+        assert_eq!(
+            ins.params().iter().map(|p| p.mode).collect::<Vec<_>>(),
+            vec![Mode::Position, Mode::Immediate, Mode::Position]
+        );
+        assert_eq!(ins.to_string(), "mul [4], 3, [4]");
+
+        let ins = Instruction::decode(&[204, -1], 0).unwrap();
+        assert_eq!(ins.to_string(), "out [rb-1]");
+        assert!(Instruction::decode(&[42], 0).is_err());
+        assert!(Instruction::decode(&[301], 0).is_err());
+    }
+
+    #[test]
+    fn disassemble_listing() {
+        let listing = disassemble(&[1101, 100, -1, 4, 0, 99, 7]);
+        assert_eq!(
+            listing,
+            "    0: add 100, -1, [4]\n    4: data 0\n    5: halt\n    6: data 7\n"
+        );
+    }
+}
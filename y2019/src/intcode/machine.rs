@@ -0,0 +1,466 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+
+use super::{Instruction, Mode, Opcode, Param};
+
+/// The type of every value in intcode memory
+pub type Word = i64;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IntcodeError {
+    UnknownOpcode { ip: usize, opcode: Word },
+    UnknownMode { ip: usize, instruction: Word },
+    NegativeAddress { ip: usize, address: Word },
+    WriteToImmediate { ip: usize },
+    NegativeJump { ip: usize, target: Word },
+}
+
+impl std::fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntcodeError::UnknownOpcode { ip, opcode } => {
+                write!(f, "unknown opcode {opcode} at {ip}")
+            }
+            IntcodeError::UnknownMode { ip, instruction } => {
+                write!(f, "unknown parameter mode in {instruction} at {ip}")
+            }
+            IntcodeError::NegativeAddress { ip, address } => {
+                write!(f, "access to negative address {address} at {ip}")
+            }
+            IntcodeError::WriteToImmediate { ip } => {
+                write!(f, "write to immediate mode parameter at {ip}")
+            }
+            IntcodeError::NegativeJump { ip, target } => {
+                write!(f, "jump to negative address {target} at {ip}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IntcodeError {}
+
+/// Why a [`Machine`] stopped running
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// The machine produced a value
+    Output(Word),
+    /// The machine is waiting on an input instruction and the input queue is empty. Running
+    /// again after pushing input resumes at the same instruction
+    NeedsInput,
+    /// The machine executed a halt instruction
+    Halted,
+}
+
+/// An intcode computer (2019 days 2, 5, 7, 9, 11, 13, 15, 17, 19, 21, 23 and 25).
+///
+/// Memory grows on demand, so reads past the end of the program return zero and writes extend
+/// it. Input is read from an internal queue, and the machine pauses with [`Event::NeedsInput`]
+/// rather than blocking when the queue is empty. Cloning a machine copies its entire state, which
+/// makes it easy to branch when searching
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Machine {
+    memory: Vec<Word>,
+    ip: usize,
+    relative_base: Word,
+    input: VecDeque<Word>,
+    output: VecDeque<Word>,
+    halted: bool,
+    trace: bool,
+}
+
+/// A saved copy of a machine's state, see [`Machine::snapshot`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Snapshot(Machine);
+
+impl Machine {
+    pub fn new(program: Vec<Word>) -> Self {
+        Self {
+            memory: program,
+            ip: 0,
+            relative_base: 0,
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+            halted: false,
+            trace: false,
+        }
+    }
+
+    /// Parses a comma separated program such as `1,0,0,3,99`
+    pub fn parse(program: &str) -> Result<Self, std::num::ParseIntError> {
+        let program = program
+            .trim()
+            .split(',')
+            .map(|n| n.trim().parse())
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(program))
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn relative_base(&self) -> Word {
+        self.relative_base
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn memory(&self) -> &[Word] {
+        &self.memory
+    }
+
+    /// Reads the value at `address`. Addresses past the end of memory read as zero
+    pub fn read(&self, address: usize) -> Word {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    /// Writes `value` to `address`, growing memory if needed
+    pub fn write(&mut self, address: usize, value: Word) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+        self.memory[address] = value;
+    }
+
+    /// When enabled, every executed instruction is printed along with its address
+    pub fn set_trace(&mut self, enabled: bool) {
+        self.trace = enabled;
+    }
+
+    pub fn push_input(&mut self, value: Word) {
+        self.input.push_back(value);
+    }
+
+    pub fn extend_input(&mut self, values: impl IntoIterator<Item = Word>) {
+        self.input.extend(values);
+    }
+
+    /// Returns the number of input values that have not been consumed yet
+    pub fn pending_input(&self) -> usize {
+        self.input.len()
+    }
+
+    /// Removes and returns the oldest buffered output value
+    pub fn pop_output(&mut self) -> Option<Word> {
+        self.output.pop_front()
+    }
+
+    /// Removes and returns all buffered output values
+    pub fn take_output(&mut self) -> Vec<Word> {
+        self.output.drain(..).collect()
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.clone())
+    }
+
+    /// Restores the state saved by [`Machine::snapshot`]
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        *self = snapshot.0.clone();
+    }
+
+    fn address(&self, param: Param) -> Result<usize, IntcodeError> {
+        let address = match param.mode {
+            Mode::Position => param.value,
+            Mode::Relative => self.relative_base + param.value,
+            Mode::Immediate => return Err(IntcodeError::WriteToImmediate { ip: self.ip }),
+        };
+        usize::try_from(address).map_err(|_| IntcodeError::NegativeAddress {
+            ip: self.ip,
+            address,
+        })
+    }
+
+    fn load(&self, param: Param) -> Result<Word, IntcodeError> {
+        match param.mode {
+            Mode::Immediate => Ok(param.value),
+            _ => Ok(self.read(self.address(param)?)),
+        }
+    }
+
+    fn store(&mut self, param: Param, value: Word) -> Result<(), IntcodeError> {
+        let address = self.address(param)?;
+        self.write(address, value);
+        Ok(())
+    }
+
+    fn jump(&mut self, target: Word) -> Result<(), IntcodeError> {
+        self.ip = usize::try_from(target).map_err(|_| IntcodeError::NegativeJump {
+            ip: self.ip,
+            target,
+        })?;
+        Ok(())
+    }
+
+    /// Executes a single instruction.
+    ///
+    /// Returns `Some` if the instruction produced output, needs input that isn't available, or
+    /// halted, and `None` otherwise. A halted machine always returns [`Event::Halted`]
+    pub fn step(&mut self) -> Result<Option<Event>, IntcodeError> {
+        if self.halted {
+            return Ok(Some(Event::Halted));
+        }
+        let instruction = Instruction::decode(&self.memory, self.ip)?;
+        if self.trace {
+            println!(
+                "{:>5} (rb {:>5}): {}",
+                self.ip, self.relative_base, instruction
+            );
+        }
+        let p = instruction.params();
+        let next = self.ip + instruction.width();
+
+        let mut event = None;
+        match instruction.opcode {
+            Opcode::Add => self.store(p[2], self.load(p[0])? + self.load(p[1])?)?,
+            Opcode::Multiply => self.store(p[2], self.load(p[0])? * self.load(p[1])?)?,
+            Opcode::LessThan => {
+                let value = (self.load(p[0])? < self.load(p[1])?) as Word;
+                self.store(p[2], value)?
+            }
+            Opcode::Equals => {
+                let value = (self.load(p[0])? == self.load(p[1])?) as Word;
+                self.store(p[2], value)?
+            }
+            Opcode::Input => match self.input.pop_front() {
+                Some(value) => self.store(p[0], value)?,
+                // leave ip on this instruction so it is retried once input arrives
+                None => return Ok(Some(Event::NeedsInput)),
+            },
+            Opcode::Output => event = Some(Event::Output(self.load(p[0])?)),
+            Opcode::JumpIfTrue => {
+                if self.load(p[0])? != 0 {
+                    return self.jump(self.load(p[1])?).map(|_| None);
+                }
+            }
+            Opcode::JumpIfFalse => {
+                if self.load(p[0])? == 0 {
+                    return self.jump(self.load(p[1])?).map(|_| None);
+                }
+            }
+            Opcode::AdjustRelativeBase => self.relative_base += self.load(p[0])?,
+            Opcode::Halt => {
+                self.halted = true;
+                return Ok(Some(Event::Halted));
+            }
+        }
+        self.ip = next;
+        Ok(event)
+    }
+
+    /// Runs until the machine outputs a value, needs input, or halts. Output values are returned
+    /// directly instead of being buffered
+    pub fn run_until_event(&mut self) -> Result<Event, IntcodeError> {
+        loop {
+            if let Some(event) = self.step()? {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Runs until the machine needs input or halts, buffering all output.
+    /// Buffered output can be read with [`Machine::pop_output`] or [`Machine::take_output`]
+    pub fn run(&mut self) -> Result<Event, IntcodeError> {
+        loop {
+            match self.run_until_event()? {
+                Event::Output(value) => self.output.push_back(value),
+                event => return Ok(event),
+            }
+        }
+    }
+
+    /// Queues `input`, runs until the machine needs more input or halts, then returns all output
+    /// (including any that was buffered before this call)
+    pub fn run_with_input(
+        &mut self,
+        input: impl IntoIterator<Item = Word>,
+    ) -> Result<Vec<Word>, IntcodeError> {
+        self.extend_input(input);
+        self.run()?;
+        Ok(self.take_output())
+    }
+
+    /// Returns an iterator that runs the machine, yielding each output value as it is produced.
+    /// The iterator ends when the machine needs input or halts
+    ///
+    /// # Panics
+    /// The iterator panics if the machine hits an [`IntcodeError`]
+    pub fn outputs(&mut self) -> Outputs<'_> {
+        Outputs { machine: self }
+    }
+
+    /// Runs the machine to completion, reading input from `input` and sending output to
+    /// `output`. Blocks while waiting for input.
+    ///
+    /// Returns [`Event::NeedsInput`] if the machine needs input and every sender for `input` has
+    /// been dropped. Output sent after the receiver is dropped is discarded
+    pub fn run_with_channels(
+        &mut self,
+        input: &Receiver<Word>,
+        output: &Sender<Word>,
+    ) -> Result<Event, IntcodeError> {
+        loop {
+            match self.run_until_event()? {
+                Event::Output(value) => {
+                    let _ = output.send(value);
+                }
+                Event::NeedsInput => match input.recv() {
+                    Ok(value) => self.push_input(value),
+                    Err(_) => return Ok(Event::NeedsInput),
+                },
+                Event::Halted => return Ok(Event::Halted),
+            }
+        }
+    }
+
+    /// Runs this machine on a new thread, connected to the returned input sender and output
+    /// receiver. The thread returns the machine once it halts or its input is closed
+    pub fn spawn(
+        mut self,
+    ) -> (
+        Sender<Word>,
+        Receiver<Word>,
+        JoinHandle<Result<Machine, IntcodeError>>,
+    ) {
+        let (input_tx, input_rx) = mpsc::channel();
+        let (output_tx, output_rx) = mpsc::channel();
+        let handle = std::thread::spawn(move || {
+            self.run_with_channels(&input_rx, &output_tx)?;
+            Ok(self)
+        });
+        (input_tx, output_rx, handle)
+    }
+}
+
+/// Iterator over the output of a [`Machine`], see [`Machine::outputs`]
+pub struct Outputs<'m> {
+    machine: &'m mut Machine,
+}
+
+impl Iterator for Outputs<'_> {
+    type Item = Word;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(buffered) = self.machine.pop_output() {
+            return Some(buffered);
+        }
+        match self.machine.run_until_event() {
+            Ok(Event::Output(value)) => Some(value),
+            Ok(_) => None,
+            Err(e) => panic!("intcode error: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_memory(program: &str) -> Vec<Word> {
+        let mut machine = Machine::parse(program).unwrap();
+        assert_eq!(machine.run().unwrap(), Event::Halted);
+        machine.memory().to_vec()
+    }
+
+    fn run_output(program: &str, input: &[Word]) -> Vec<Word> {
+        let mut machine = Machine::parse(program).unwrap();
+        machine.run_with_input(input.iter().copied()).unwrap()
+    }
+
+    #[test]
+    fn day2_examples() {
+        assert_eq!(
+            run_memory("1,9,10,3,2,3,11,0,99,30,40,50"),
+            vec![3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]
+        );
+        assert_eq!(run_memory("1,0,0,0,99"), vec![2, 0, 0, 0, 99]);
+        assert_eq!(run_memory("2,3,0,3,99"), vec![2, 3, 0, 6, 99]);
+        assert_eq!(run_memory("2,4,4,5,99,0"), vec![2, 4, 4, 5, 99, 9801]);
+        assert_eq!(
+            run_memory("1,1,1,4,99,5,6,0,99"),
+            vec![30, 1, 1, 4, 2, 5, 6, 0, 99]
+        );
+    }
+
+    #[test]
+    fn day5_examples() {
+        assert_eq!(run_memory("1002,4,3,4,33"), vec![1002, 4, 3, 4, 99]);
+        assert_eq!(run_memory("1101,100,-1,4,0"), vec![1101, 100, -1, 4, 99]);
+        assert_eq!(run_output("3,0,4,0,99", &[1234]), vec![1234]);
+
+        let equal_to_8 = "3,9,8,9,10,9,4,9,99,-1,8";
+        let less_than_8 = "3,3,1107,-1,8,3,4,3,99";
+        for input in [7, 8, 9] {
+            assert_eq!(run_output(equal_to_8, &[input]), vec![(input == 8) as Word]);
+            assert_eq!(run_output(less_than_8, &[input]), vec![(input < 8) as Word]);
+        }
+
+        let jump = "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9";
+        assert_eq!(run_output(jump, &[0]), vec![0]);
+        assert_eq!(run_output(jump, &[5]), vec![1]);
+
+        let compare = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
+            1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,\
+            999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+        assert_eq!(run_output(compare, &[7]), vec![999]);
+        assert_eq!(run_output(compare, &[8]), vec![1000]);
+        assert_eq!(run_output(compare, &[9]), vec![1001]);
+    }
+
+    #[test]
+    fn day9_examples() {
+        let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let expected: Vec<Word> = quine.split(',').map(|n| n.parse().unwrap()).collect();
+        assert_eq!(run_output(quine, &[]), expected);
+
+        let big = run_output("1102,34915192,34915192,7,4,7,99,0", &[]);
+        assert_eq!(big[0].to_string().len(), 16);
+        assert_eq!(
+            run_output("104,1125899906842624,99", &[]),
+            vec![1125899906842624]
+        );
+    }
+
+    #[test]
+    fn pauses_for_input() {
+        // adds two inputs together
+        let mut machine = Machine::parse("3,11,3,12,1,11,12,13,4,13,99,0,0,0").unwrap();
+        assert_eq!(machine.run().unwrap(), Event::NeedsInput);
+        machine.push_input(4);
+        assert_eq!(machine.run().unwrap(), Event::NeedsInput);
+
+        let snapshot = machine.snapshot();
+        machine.push_input(5);
+        assert_eq!(machine.run().unwrap(), Event::Halted);
+        assert_eq!(machine.take_output(), vec![9]);
+
+        machine.restore(&snapshot);
+        machine.push_input(10);
+        assert_eq!(machine.outputs().collect::<Vec<_>>(), vec![14]);
+        assert!(machine.is_halted());
+    }
+
+    #[test]
+    fn channels() {
+        let machine = Machine::parse("3,11,3,12,1,11,12,13,4,13,99,0,0,0").unwrap();
+        let (tx, rx, handle) = machine.spawn();
+        tx.send(20).unwrap();
+        tx.send(22).unwrap();
+        assert_eq!(rx.recv().unwrap(), 42);
+        assert!(handle.join().unwrap().unwrap().is_halted());
+    }
+
+    #[test]
+    fn errors() {
+        let mut machine = Machine::parse("1101,1,1,-1,99").unwrap();
+        assert_eq!(
+            machine.run(),
+            Err(IntcodeError::NegativeAddress { ip: 0, address: -1 })
+        );
+        let mut machine = Machine::parse("11101,1,1,3,99").unwrap();
+        assert_eq!(machine.run(), Err(IntcodeError::WriteToImmediate { ip: 0 }));
+    }
+}
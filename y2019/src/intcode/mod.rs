@@ -0,0 +1,9 @@
+mod ascii;
+mod instruction;
+mod machine;
+mod network;
+
+pub use ascii::*;
+pub use instruction::*;
+pub use machine::*;
+pub use network::*;
@@ -0,0 +1,333 @@
+use std::ops::ControlFlow;
+
+use super::{Event, IntcodeError, Machine, Word};
+
+/// The number of consecutive rounds in which no machine does anything before a [`Network`] is
+/// considered idle. A single quiet round can happen while a machine is still waiting on a packet
+/// that was sent in the same round
+const IDLE_ROUNDS: usize = 2;
+
+/// A group of output values sent by one machine in a [`Network`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Packet {
+    /// The index of the machine that sent this packet
+    pub from: usize,
+    pub words: Vec<Word>,
+}
+
+/// Decides what happens to packets sent between machines in a [`Network`]
+pub trait Router {
+    /// The value produced when the router stops the network
+    type Output;
+
+    /// Called for every packet sent by a machine. Use [`Network::send`] to deliver it
+    fn route(&mut self, packet: Packet, network: &mut Network) -> ControlFlow<Self::Output>;
+
+    /// Called when every machine is halted or waiting on input that never arrives. Routers that
+    /// inject packets when the network goes quiet (like the NAT in 2019 day 23) do so here
+    fn idle(&mut self, _network: &mut Network) -> ControlFlow<Self::Output> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Runs many intcode machines cooperatively, collecting their output into packets and handing
+/// them to a [`Router`].
+///
+/// Each round runs every machine until it needs input or halts. When `idle_input` is set, a
+/// machine whose input queue is empty at the start of a round is given that value instead of
+/// waiting (2019 day 23 uses -1)
+#[derive(Clone, Debug)]
+pub struct Network {
+    machines: Vec<Machine>,
+    partial: Vec<Vec<Word>>,
+    packet_size: usize,
+    idle_input: Option<Word>,
+    idle_rounds: usize,
+    delivered: bool,
+}
+
+impl Network {
+    /// Creates a network where every `packet_size` outputs from a machine form one packet
+    ///
+    /// # Panics
+    /// This function panics if `packet_size` is zero
+    #[track_caller]
+    pub fn new(machines: Vec<Machine>, packet_size: usize) -> Self {
+        assert!(packet_size > 0, "packet size must be non-zero");
+        Self {
+            partial: vec![Vec::new(); machines.len()],
+            machines,
+            packet_size,
+            idle_input: None,
+            idle_rounds: 0,
+            delivered: false,
+        }
+    }
+
+    /// Creates a network of `count` copies of `machine`
+    pub fn with_copies(machine: &Machine, count: usize, packet_size: usize) -> Self {
+        Self::new(vec![machine.clone(); count], packet_size)
+    }
+
+    /// Gives `value` to machines with an empty input queue instead of letting them wait
+    pub fn with_idle_input(mut self, value: Word) -> Self {
+        self.idle_input = Some(value);
+        self
+    }
+
+    pub fn machines(&self) -> &[Machine] {
+        &self.machines
+    }
+
+    pub fn machine_mut(&mut self, index: usize) -> &mut Machine {
+        &mut self.machines[index]
+    }
+
+    pub fn all_halted(&self) -> bool {
+        self.machines.iter().all(Machine::is_halted)
+    }
+
+    /// Returns true if nothing has happened for long enough that nothing will happen until
+    /// another packet is sent
+    pub fn is_idle(&self) -> bool {
+        self.idle_rounds >= IDLE_ROUNDS
+    }
+
+    /// Queues `words` as input for the machine at index `to`
+    ///
+    /// # Panics
+    /// This function panics if `to` is not the index of a machine in this network
+    #[track_caller]
+    pub fn send(&mut self, to: usize, words: impl IntoIterator<Item = Word>) {
+        self.machines[to].extend_input(words);
+        self.delivered = true;
+    }
+
+    /// Runs every machine until it needs input or halts, returning the packets that were
+    /// completed. Output that doesn't fill a whole packet is kept for the next round
+    pub fn round(&mut self) -> Result<Vec<Packet>, IntcodeError> {
+        let mut packets = Vec::new();
+        let mut idle = true;
+        for (from, machine) in self.machines.iter_mut().enumerate() {
+            if machine.is_halted() {
+                continue;
+            }
+            let starved = machine.pending_input() == 0;
+            if let (true, Some(value)) = (starved, self.idle_input) {
+                machine.push_input(value);
+            }
+
+            let mut produced = false;
+            while let Event::Output(value) = machine.run_until_event()? {
+                produced = true;
+                let partial = &mut self.partial[from];
+                partial.push(value);
+                if partial.len() == self.packet_size {
+                    packets.push(Packet {
+                        from,
+                        words: std::mem::take(partial),
+                    });
+                }
+            }
+            idle &= starved && !produced;
+        }
+        self.idle_rounds = if idle { self.idle_rounds + 1 } else { 0 };
+        Ok(packets)
+    }
+
+    /// Runs rounds until `router` breaks, passing it every packet and notifying it whenever the
+    /// network goes idle.
+    ///
+    /// Returns `None` if the network stops making progress, that is every machine is halted or
+    /// idle and the router's idle hook didn't send anything
+    pub fn run<R: Router>(&mut self, router: &mut R) -> Result<Option<R::Output>, IntcodeError> {
+        loop {
+            for packet in self.round()? {
+                if let ControlFlow::Break(output) = router.route(packet, self) {
+                    return Ok(Some(output));
+                }
+            }
+            if self.is_idle() {
+                self.delivered = false;
+                if let ControlFlow::Break(output) = router.idle(self) {
+                    return Ok(Some(output));
+                }
+                if !self.delivered {
+                    return Ok(None);
+                }
+                self.idle_rounds = 0;
+            }
+        }
+    }
+}
+
+/// Connects amplifiers in series, sending each output to the next machine (2019 day 7).
+///
+/// With `feedback` set the last machine's output goes back to the first. Stops once every
+/// machine has halted, producing the last value output by the final machine
+#[derive(Clone, Debug, Default)]
+pub struct Chain {
+    pub feedback: bool,
+    pub last: Option<Word>,
+}
+
+impl Router for Chain {
+    type Output = Word;
+
+    fn route(&mut self, packet: Packet, network: &mut Network) -> ControlFlow<Word> {
+        let count = network.machines().len();
+        let is_last = packet.from + 1 == count;
+        if is_last {
+            self.last = packet.words.last().copied();
+        }
+        if !is_last || self.feedback {
+            network.send((packet.from + 1) % count, packet.words);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn idle(&mut self, network: &mut Network) -> ControlFlow<Word> {
+        match self.last {
+            Some(last) if network.all_halted() => ControlFlow::Break(last),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+/// Runs one copy of `program` per phase setting, connected as a [`Chain`], and feeds 0 to the
+/// first amplifier. Returns the final output of the last amplifier, or `None` if it never
+/// produced one
+pub fn run_amplifiers(
+    program: &Machine,
+    phases: &[Word],
+    feedback: bool,
+) -> Result<Option<Word>, IntcodeError> {
+    let mut network = Network::with_copies(program, phases.len(), 1);
+    for (i, &phase) in phases.iter().enumerate() {
+        network.send(i, [phase]);
+    }
+    network.send(0, [0]);
+    network.run(&mut Chain {
+        feedback,
+        last: None,
+    })
+}
+
+/// The NAT from 2019 day 23.
+///
+/// Packets are `[destination, x, y]`. Packets sent to [`Nat::ADDRESS`] are remembered, and the
+/// most recent one is sent to machine 0 whenever the network goes idle. Stops once it delivers
+/// the same `y` value twice in a row, producing that value.
+///
+/// Packets addressed to a machine that doesn't exist are dropped and counted in `dropped`
+#[derive(Clone, Debug, Default)]
+pub struct Nat {
+    /// The `y` value of the first packet sent to the NAT
+    pub first_y: Option<Word>,
+    /// The number of packets sent to an address with no machine
+    pub dropped: usize,
+    last_packet: Option<(Word, Word)>,
+    last_delivered_y: Option<Word>,
+}
+
+impl Nat {
+    pub const ADDRESS: Word = 255;
+
+    /// Creates a network of `count` copies of `program`, each given its address as the first
+    /// input, ready to be run with a [`Nat`]
+    pub fn network(program: &Machine, count: usize) -> Network {
+        let mut network = Network::with_copies(program, count, 3).with_idle_input(-1);
+        for address in 0..count {
+            network.send(address, [address as Word]);
+        }
+        network
+    }
+}
+
+impl Router for Nat {
+    type Output = Word;
+
+    fn route(&mut self, packet: Packet, network: &mut Network) -> ControlFlow<Word> {
+        let [to, x, y] = packet.words[..] else {
+            unreachable!("network packets always have 3 words")
+        };
+        if to == Self::ADDRESS {
+            self.first_y.get_or_insert(y);
+            self.last_packet = Some((x, y));
+        } else {
+            match usize::try_from(to) {
+                Ok(to) if to < network.machines().len() => network.send(to, [x, y]),
+                _ => self.dropped += 1,
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn idle(&mut self, network: &mut Network) -> ControlFlow<Word> {
+        let Some((x, y)) = self.last_packet else {
+            return ControlFlow::Continue(());
+        };
+        if self.last_delivered_y == Some(y) {
+            return ControlFlow::Break(y);
+        }
+        self.last_delivered_y = Some(y);
+        network.send(0, [x, y]);
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day7_examples() {
+        let program = Machine::parse("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0").unwrap();
+        assert_eq!(
+            run_amplifiers(&program, &[4, 3, 2, 1, 0], false),
+            Ok(Some(43210))
+        );
+
+        let program = Machine::parse(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+        )
+        .unwrap();
+        assert_eq!(
+            run_amplifiers(&program, &[9, 8, 7, 6, 5], true),
+            Ok(Some(139629729))
+        );
+    }
+
+    #[test]
+    fn nat_stops_on_repeated_y() {
+        // machine 0 sends [255, 7, 42] once, then every machine reads input forever
+        let program =
+            Machine::parse("3,100,1005,100,11,104,255,104,7,104,42,3,101,1105,1,11").unwrap();
+        let mut network = Nat::network(&program, 3);
+        let mut nat = Nat::default();
+        assert_eq!(network.run(&mut nat), Ok(Some(42)));
+        assert_eq!(nat.first_y, Some(42));
+    }
+
+    #[test]
+    fn nat_drops_unknown_addresses() {
+        // machine 0 sends [-1, 1, 2] and [7, 3, 4] before [255, 7, 42]
+        let program = Machine::parse(
+            "3,100,1005,100,23,104,-1,104,1,104,2,104,7,104,3,104,4,104,255,104,7,104,42,3,101,1105,1,23",
+        )
+        .unwrap();
+        let mut network = Nat::network(&program, 3);
+        let mut nat = Nat::default();
+        assert_eq!(network.run(&mut nat), Ok(Some(42)));
+        assert_eq!(nat.dropped, 2);
+    }
+
+    #[test]
+    fn stalled_network() {
+        // waits for input that is never sent
+        let program = Machine::parse("3,0,99").unwrap();
+        let mut network = Network::with_copies(&program, 2, 1);
+        assert_eq!(network.run(&mut Chain::default()), Ok(None));
+        assert!(!network.all_halted());
+    }
+}
@@ -1,10 +1,10 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day1;
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day1 {
     fn part1(&self, input: Input) -> Output {
-        let nums = input.nums();
+        let nums: Vec<u32> = input.nums().unwrap();
         for i in &nums {
             let compliment: u32 = 2020 - i;
             if nums.contains(&compliment) {
@@ -15,7 +15,7 @@ impl crate::traits::AocDay for S {
     }
 
     fn part2(&self, input: Input) -> Output {
-        let nums = input.nums();
+        let nums: Vec<i32> = input.nums().unwrap();
         for i in &nums {
             for j in &nums {
                 let compliment: i32 = 2020 - i - j;
@@ -27,3 +27,8 @@ impl crate::traits::AocDay for S {
         panic!();
     }
 }
+
+fn main() {
+    let d = Day1;
+    runner_main(&d, 2020, 1);
+}
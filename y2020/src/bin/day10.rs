@@ -1,6 +1,8 @@
-use crate::traits::*;
+use std::collections::HashMap;
 
-pub struct S;
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day10;
 
 fn count_helper(lines: &[u32], mut index: usize) -> usize {
     if index == lines.len() - 1 {
@@ -24,9 +26,9 @@ fn count_helper(lines: &[u32], mut index: usize) -> usize {
     count
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day10 {
     fn part1(&self, input: Input) -> Output {
-        let mut nums = input.nums();
+        let mut nums: Vec<u32> = input.nums().unwrap();
         nums.push(nums.iter().max().unwrap() + 3);
         nums.push(0);
         nums.sort();
@@ -45,7 +47,7 @@ impl crate::traits::AocDay for S {
 
     fn part2(&self, input: Input) -> Output {
         println!("WARN: Part 2 never fully implemented");
-        let mut nums = input.nums();
+        let mut nums: Vec<u32> = input.nums().unwrap();
         nums.push(0);
         nums.sort();
         nums.push(nums[nums.len() - 1] + 3);
@@ -66,3 +68,8 @@ impl crate::traits::AocDay for S {
         count.into()
     }
 }
+
+fn main() {
+    let d = Day10;
+    runner_main(&d, 2020, 10);
+}
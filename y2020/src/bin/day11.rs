@@ -0,0 +1,90 @@
+use util::{find_cycle, runner_main, AocDay, Input, IntoEnumeratedCells, Matrix, Output};
+
+struct Day11;
+
+impl AocDay for Day11 {
+    fn part1(&self, input: Input) -> Output {
+        let input: Matrix<u8> = Matrix::new_from_chars(input.as_str()).unwrap();
+        settle(input, |mat| {
+            let cols = mat.cols();
+            let it = mat.iter().enumerate_cells().map(|(row, col, s)| {
+                let around_count: usize = mat
+                    .neighbor_iter(row, col)
+                    .map(|s| if *s == b'#' { 1 } else { 0 })
+                    .sum();
+                if *s == b'L' && around_count == 0 {
+                    b'#'
+                } else if *s == b'#' && around_count >= 4 {
+                    b'L'
+                } else {
+                    *s
+                }
+            });
+            Matrix::new_from_iterator(cols, it)
+        })
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        let input: Matrix<u8> = Matrix::new_from_chars(input.as_str()).unwrap();
+        settle(input, |mat| {
+            let cols = mat.cols();
+            let it = mat.iter().enumerate_cells().map(|(row, col, s)| {
+                if *s == b'.' {
+                    return *s;
+                }
+                let around_count: usize = mat
+                    .neighbor_iter(row, col)
+                    .enumerate_cells()
+                    .map(|(n_row, n_col, _s)| {
+                        let r_dir = n_row as isize - row as isize;
+                        let c_dir = n_col as isize - col as isize;
+                        let mut p_row = n_row as isize;
+                        let mut p_col = n_col as isize;
+                        loop {
+                            if p_row < 0 || p_col < 0 {
+                                break 0;
+                            }
+                            match mat.try_get(p_row as usize, p_col as usize) {
+                                Some(s) => {
+                                    if *s == b'#' {
+                                        //We see a taken seat
+                                        break 1;
+                                    }
+                                    if *s == b'L' {
+                                        //We see an empty seat
+                                        break 0;
+                                    }
+                                }
+                                None => break 0,
+                            }
+                            p_row += r_dir;
+                            p_col += c_dir;
+                        }
+                    })
+                    .sum();
+
+                if *s == b'L' && around_count == 0 {
+                    b'#'
+                } else if *s == b'#' && around_count >= 5 {
+                    b'L'
+                } else {
+                    *s
+                }
+            });
+            Matrix::new_from_iterator(cols, it)
+        })
+    }
+}
+
+/// Applies `step` until the seats stop changing, and counts the taken seats
+fn settle(seats: Matrix<u8>, step: impl Fn(&Matrix<u8>) -> Matrix<u8>) -> Output {
+    // a layout that no longer changes is a loop of a single state
+    let cycle = find_cycle(seats, step);
+    assert_eq!(cycle.info.period, 1, "the seats never stop changing");
+    cycle.repeated.iter().filter(|&&s| s == b'#').count().into()
+}
+
+fn main() {
+    let d = Day11;
+    runner_main(&d, 2020, 11);
+}
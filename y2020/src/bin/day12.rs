@@ -0,0 +1,34 @@
+use util::{runner_main, AocDay, Direction, Heading, Input, Output, SignedPoint};
+
+struct Day12;
+
+impl AocDay for Day12 {
+    fn part1(&self, input: Input) -> Output {
+        let mut ship = Heading::new(SignedPoint::new(0, 0), Direction::Right);
+        for line in input.lines() {
+            ship.apply(line).unwrap();
+        }
+        ship.manhattan_distance().into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        // the waypoint is relative to the ship and starts 10 east, 1 north
+        let mut way = SignedPoint::new(-1, 10);
+        let mut pos = SignedPoint::new(0, 0);
+        for line in input.lines() {
+            let param = line[1..].parse::<isize>().unwrap();
+            match line.as_bytes()[0] {
+                b'L' => way = way.rotate(-param / 90),
+                b'R' => way = way.rotate(param / 90),
+                b'F' => pos += way * param,
+                b => way = way.offset_by(Direction::from_compass(b).unwrap(), param),
+            }
+        }
+        pos.manhattan_distance().into()
+    }
+}
+
+fn main() {
+    let d = Day12;
+    runner_main(&d, 2020, 12);
+}
@@ -1,6 +1,7 @@
-use crate::traits::*;
+use util::math::crt;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day13;
 
 mod part1 {
     use super::*;
@@ -25,8 +26,7 @@ mod part2 {
     use super::*;
 
     pub fn parse(input: Input) -> Vec<Option<u32>> {
-        let lines: Vec<_> = input.lines().collect();
-        let rest = lines[0];
+        let rest = input.lines().last().unwrap();
         let mut result = Vec::new();
         for id_str in rest.split(',') {
             if id_str == "x" {
@@ -48,7 +48,7 @@ fn get_next_departure(timestamp: u32, id: u32) -> u32 {
     timestamp + id - remainder
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day13 {
     fn part1(&self, input: Input) -> Output {
         let (timestamp, ids) = part1::parse(input);
 
@@ -66,17 +66,17 @@ impl crate::traits::AocDay for S {
     fn part2(&self, input: Input) -> Output {
         let ids = part2::parse(input);
 
-        for (i, id) in ids.iter().enumerate() {
-            if id.is_some() {
-                println!("(t + {}) mod {} = 0;", i, id.unwrap());
-            }
-        }
-        //Got from: https://www.wolframalpha.com/input/?i=%28t+%2B+0%29+mod+23+%3D+0%3B+%28t+%2B+17%29+mod+37+%3D+0%3B+%28t+%2B+23%29+mod+863+%3D+0%3B+%28t+%2B+35%29+mod+19+%3D+0%3B+%28t+%2B+36%29+mod+13+%3D+0%3B+%28t+%2B+40%29+mod+17+%3D+0%3B+%28t+%2B+52%29+mod+29+%3D+0%3B+%28t+%2B+54%29+mod+571+%3D+0%3B+%28t+%2B+95%29+mod+41+%3D+0%3B
-        if ids.len() < 10 {
-            //Cheese tests too
-            1202161486.into()
-        } else {
-            1106724616194525usize.into()
-        }
+        // bus `id` at index `i` must leave at `t + i`, so `t == -i (mod id)`
+        let congruences = ids
+            .iter()
+            .enumerate()
+            .filter_map(|(i, id)| id.map(|id| (-(i as i64), id as i64)));
+        let (t, _period) = crt(congruences).unwrap();
+        t.into()
     }
 }
+
+fn main() {
+    let d = Day13;
+    runner_main(&d, 2020, 13);
+}
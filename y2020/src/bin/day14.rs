@@ -1,6 +1,8 @@
-use crate::traits::*;
+use std::collections::HashMap;
 
-pub struct S;
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day14;
 
 fn next_address(count: &mut u64, mask: u64, base_address: u64) -> Option<u64> {
     if *count == 2_u64.pow(mask.count_ones()) {
@@ -18,7 +20,7 @@ fn next_address(count: &mut u64, mask: u64, base_address: u64) -> Option<u64> {
     Some(unset_address)
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day14 {
     fn part1(&self, input: Input) -> Output {
         let mut memory: HashMap<u64, u64> = HashMap::new();
         let mut mask_on: u64 = 0;
@@ -101,3 +103,8 @@ impl crate::traits::AocDay for S {
         result.into()
     }
 }
+
+fn main() {
+    let d = Day14;
+    runner_main(&d, 2020, 14);
+}
@@ -1,6 +1,8 @@
-use crate::traits::*;
+use std::collections::HashMap;
 
-pub struct S;
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day15;
 
 fn next(map: &HashMap<u32, u32>, last: u32, turn: u32) -> u32 {
     let next = match map.get(&last) {
@@ -16,14 +18,14 @@ fn next(map: &HashMap<u32, u32>, last: u32, turn: u32) -> u32 {
     next
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day15 {
     fn part1(&self, input: Input) -> Output {
         let end_turn = 2020;
 
         let mut turn = 1;
         let mut map: HashMap<u32, u32> = HashMap::new();
         let mut last = 0;
-        for num in input.nums_comma_separated() {
+        for num in input.as_str().trim().split(',').map(|n| n.parse().unwrap()) {
             map.insert(num, turn);
             turn += 1;
             last = num;
@@ -44,7 +46,7 @@ impl crate::traits::AocDay for S {
         let mut turn = 1;
         let mut map: HashMap<u32, u32> = HashMap::new();
         let mut last = 0;
-        for num in input.nums_comma_separated() {
+        for num in input.as_str().trim().split(',').map(|n| n.parse().unwrap()) {
             map.insert(num, turn);
             turn += 1;
             last = num;
@@ -59,3 +61,8 @@ impl crate::traits::AocDay for S {
         last.into()
     }
 }
+
+fn main() {
+    let d = Day15;
+    runner_main(&d, 2020, 15);
+}
@@ -1,8 +1,8 @@
 use std::collections::HashSet;
 
-use crate::traits::*;
+use util::{runner_main, scan, sections, AocDay, Input, Output, ParseError};
 
-pub struct S;
+struct Day16;
 
 #[derive(Debug)]
 struct Constraint {
@@ -21,17 +21,8 @@ pub struct Data {
     other_tickets: Vec<Ticket>,
 }
 
-fn from_base_10_str(input: &str) -> Result<u32, std::num::ParseIntError> {
-    std::str::FromStr::from_str(input)
-}
-
-fn int32(input: &str) -> nom::IResult<&str, u32> {
-    nom::combinator::map_res(nom::character::complete::digit1, from_base_10_str)(input)
-}
-
-fn parse_constraint(input: &str) -> Result<Constraint, scanfmt::ScanError> {
-    let (name, a1, a2, b1, b2): (_, _, u32, _, u32);
-    scanfmt::scanfmt!(input, "{}: {}-{} or {}-{}", name, a1, a2, b1, b2);
+fn parse_constraint(input: &str) -> Result<Constraint, ParseError> {
+    let (name, a1, a2, b1, b2): (String, u32, u32, u32, u32) = scan(input, "{}: {}-{} or {}-{}")?;
 
     Ok(Constraint {
         name,
@@ -50,7 +41,7 @@ mod part1 {
     use super::*;
 
     pub fn parse(input: &str) -> Data {
-        let mut sections = input.split("\n\n");
+        let mut sections = sections(input);
         let constraints: Vec<_> = sections
             .next()
             .unwrap()
@@ -95,7 +86,7 @@ mod part2 {
     use super::*;
 
     pub fn parse(input: &str) -> Data {
-        let mut sections = input.split("\n\n");
+        let mut sections = sections(input);
         let constraints: Vec<_> = sections
             .next()
             .unwrap()
@@ -157,7 +148,7 @@ mod part2 {
     }
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day16 {
     fn part1(&self, input: Input) -> Output {
         let data = part1::parse(input.as_str());
         let mut bad_sum = 0;
@@ -244,3 +235,8 @@ impl crate::traits::AocDay for S {
         result.into()
     }
 }
+
+fn main() {
+    let d = Day16;
+    runner_main(&d, 2020, 16);
+}
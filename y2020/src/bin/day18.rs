@@ -0,0 +1,26 @@
+use util::expr::Precedence;
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day18;
+
+fn homework(input: &Input, rules: Precedence) -> i64 {
+    input
+        .lines()
+        .map(|l| rules.parse(l).unwrap().eval::<i64>().unwrap())
+        .sum()
+}
+
+impl AocDay for Day18 {
+    fn part1(&self, input: Input) -> Output {
+        homework(&input, Precedence::same()).into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        homework(&input, Precedence::addition_first()).into()
+    }
+}
+
+fn main() {
+    let d = Day18;
+    runner_main(&d, 2020, 18);
+}
@@ -0,0 +1,31 @@
+use util::grammar::Grammar;
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day19;
+
+fn parse(input: &Input) -> (Grammar, Vec<&str>) {
+    let mut sections = input.sections();
+    let grammar = sections.next().unwrap().parse().unwrap();
+    let messages = sections.next().unwrap().lines().collect();
+    (grammar, messages)
+}
+
+impl AocDay for Day19 {
+    fn part1(&self, input: Input) -> Output {
+        let (grammar, messages) = parse(&input);
+        let automaton = grammar.compile(0).unwrap();
+        messages.iter().filter(|m| automaton.matches(m)).count().into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        let (mut grammar, messages) = parse(&input);
+        grammar.insert(8, "42 | 42 8".parse().unwrap());
+        grammar.insert(11, "42 31 | 42 11 31".parse().unwrap());
+        messages.iter().filter(|m| grammar.matches(0, m)).count().into()
+    }
+}
+
+fn main() {
+    let d = Day19;
+    runner_main(&d, 2020, 19);
+}
@@ -1,6 +1,6 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day2;
 
 mod part1 {
     fn from_base_10_str(input: &str) -> Result<i32, std::num::ParseIntError> {
@@ -70,12 +70,12 @@ mod part2 {
         {
             char_count += 1;
         }
- 
+
         Ok((input, char_count == 1))
     }
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day2 {
     fn part1(&self, input: Input) -> Output {
         let mut input = input.as_str();
         let mut count = 0;
@@ -102,3 +102,8 @@ impl crate::traits::AocDay for S {
         count.into()
     }
 }
+
+fn main() {
+    let d = Day2;
+    runner_main(&d, 2020, 2);
+}
@@ -0,0 +1,18 @@
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day21;
+
+impl AocDay for Day21 {
+    fn part1(&self, _input: Input) -> Output {
+        Output::Unimplemented
+    }
+
+    fn part2(&self, _input: Input) -> Output {
+        Output::Unimplemented
+    }
+}
+
+fn main() {
+    let d = Day21;
+    runner_main(&d, 2020, 21);
+}
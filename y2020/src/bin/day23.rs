@@ -0,0 +1,18 @@
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day23;
+
+impl AocDay for Day23 {
+    fn part1(&self, _input: Input) -> Output {
+        Output::Unimplemented
+    }
+
+    fn part2(&self, _input: Input) -> Output {
+        Output::Unimplemented
+    }
+}
+
+fn main() {
+    let d = Day23;
+    runner_main(&d, 2020, 23);
+}
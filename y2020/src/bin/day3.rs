@@ -1,8 +1,8 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day3;
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day3 {
     fn part1(&self, input: Input) -> Output {
         let terrain: Vec<_> = input.lines().collect();
 
@@ -51,3 +51,8 @@ impl crate::traits::AocDay for S {
         total.into()
     }
 }
+
+fn main() {
+    let d = Day3;
+    runner_main(&d, 2020, 3);
+}
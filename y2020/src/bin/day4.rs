@@ -1,18 +1,14 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day4;
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day4 {
     fn part1(&self, input: Input) -> Output {
         let mut count = 0;
-        for passport in input.as_str().split("\n\n") {
-            if passport.contains("byr:")
-                && passport.contains("iyr:")
-                && passport.contains("eyr:")
-                && passport.contains("hgt:")
-                && passport.contains("hcl:")
-                && passport.contains("ecl:")
-                && passport.contains("pid:")
+        for passport in input.records().unwrap() {
+            if ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"]
+                .iter()
+                .all(|key| passport.contains_key(key))
             {
                 count += 1;
             }
@@ -22,19 +18,9 @@ impl crate::traits::AocDay for S {
 
     fn part2(&self, input: Input) -> Output {
         let mut counter = 0;
-        for passport in input.as_str().split("\n\n") {
+        for passport in input.records().unwrap() {
             let mut valid_fields = 0;
-            for raw_pair in passport.split(|c| c == ' ' || c == '\n') {
-                let pair = raw_pair.trim();
-                let mut it = pair.split(":");
-                let key = match it.next() {
-                    Some(value) => value.trim(),
-                    None => break,
-                };
-                let value = match it.next() {
-                    Some(value) => value.trim(),
-                    None => break,
-                };
+            for (key, value) in passport {
                 let valid = match key {
                     "byr" => {
                         let year = value.parse::<i32>().unwrap();
@@ -42,20 +28,18 @@ impl crate::traits::AocDay for S {
                     }
                     "iyr" => {
                         let year = value.parse::<i32>().unwrap();
-                        year >= 2010 && year <= 2020
+                        (2010..=2020).contains(&year)
                     }
                     "eyr" => {
                         let year = value.parse::<i32>().unwrap();
-                        year >= 2020 && year <= 2030
+                        (2020..=2030).contains(&year)
                     }
                     "hgt" => {
                         if value.len() <= 2 {
                             false
                         } else {
-                            let height = value[..value.len() - 2 as usize]
-                                .parse::<i32>()
-                                .unwrap();
-                            let unit = &value[value.len() - 2 as usize..];
+                            let height = value[..value.len() - 2].parse::<i32>().unwrap();
+                            let unit = &value[value.len() - 2..];
                             if unit == "in" {
                                 height >= 59 && height <= 76
                             } else if unit == "cm" {
@@ -94,3 +78,8 @@ impl crate::traits::AocDay for S {
         counter.into()
     }
 }
+
+fn main() {
+    let d = Day4;
+    runner_main(&d, 2020, 4);
+}
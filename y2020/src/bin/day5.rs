@@ -1,8 +1,8 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day5;
 
 fn decode_id(line: &str) -> i32 {
     let mut number = 0;
@@ -15,7 +15,7 @@ fn decode_id(line: &str) -> i32 {
     number
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day5 {
     fn part1(&self, input: Input) -> Output {
         input
             .lines()
@@ -41,3 +41,8 @@ impl crate::traits::AocDay for S {
         panic!()
     }
 }
+
+fn main() {
+    let d = Day5;
+    runner_main(&d, 2020, 5);
+}
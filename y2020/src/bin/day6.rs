@@ -1,13 +1,13 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day6;
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day6 {
     fn part1(&self, input: Input) -> Output {
         let mut counter = 0;
-        for group in input.as_str().split("\n\n") {
+        for group in input.sections() {
             let mut set: HashSet<char> = HashSet::new();
             for line in group.lines() {
                 for c in line.chars() {
@@ -22,13 +22,13 @@ impl crate::traits::AocDay for S {
 
     fn part2(&self, input: Input) -> Output {
         let mut counter = 0;
-        for group in input.as_str().split("\n\n") {
+        for group in input.sections() {
             let mut set: HashMap<char, u8> = HashMap::new();
             let mut people = 0;
             for line in group.lines() {
                 for c in line.chars() {
                     match set.get(&c).cloned() {
-                        Some(count) => set.insert(c, count.clone() + 1),
+                        Some(count) => set.insert(c, count + 1),
                         None => set.insert(c, 1),
                     };
                 }
@@ -43,3 +43,8 @@ impl crate::traits::AocDay for S {
         counter.into()
     }
 }
+
+fn main() {
+    let d = Day6;
+    runner_main(&d, 2020, 6);
+}
@@ -1,24 +1,10 @@
 use std::collections::HashMap;
 
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day7;
 
-fn get_name<'a>(color: u32, names: &'a HashMap<&str, u32>) -> Option<&'a str> {
-    names
-        .iter()
-        .find_map(|(key, &val)| if color == val { Some(*key) } else { None })
-}
-
-fn contains_deep(
-    color: u32,
-    target_color: u32,
-    graph: &[Vec<(u32, u32)>],
-    backtrack: &mut HashMap<u32, u32>,
-    names: &HashMap<&str, u32>,
-    indent: u32,
-    target_count: u32,
-) -> bool {
+fn contains_deep(color: u32, target_color: u32, graph: &[Vec<(u32, u32)>]) -> bool {
     if target_color == color {
         return false;
     }
@@ -27,15 +13,7 @@ fn contains_deep(
         if *suspect == target_color {
             return true;
         }
-        if contains_deep(
-            *suspect,
-            target_color,
-            graph,
-            backtrack,
-            names,
-            indent + 1,
-            target_count,
-        ) {
+        if contains_deep(*suspect, target_color, graph) {
             return true;
         }
     }
@@ -91,23 +69,14 @@ fn sum_deep(
     result
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day7 {
     fn part1(&self, input: Input) -> Output {
         let mut counter = 0;
         let (graph, names) = parse(input.as_str());
 
-        let target_color = *names.get("shiny gold").unwrap() as u32;
-        let mut backtrack: HashMap<u32, u32> = HashMap::new();
+        let target_color = *names.get("shiny gold").unwrap();
         for connection in 0..graph.len() {
-            if contains_deep(
-                connection as u32,
-                target_color as u32,
-                &graph,
-                &mut backtrack,
-                &names,
-                0,
-                1,
-            ) {
+            if contains_deep(connection as u32, target_color, &graph) {
                 counter += 1;
             }
         }
@@ -117,8 +86,13 @@ impl crate::traits::AocDay for S {
     fn part2(&self, input: Input) -> Output {
         let (graph, names) = parse(input.as_str());
 
-        let target_color = *names.get("shiny gold").unwrap() as u32;
+        let target_color = *names.get("shiny gold").unwrap();
         let count = sum_deep(target_color, &graph, &names, 0);
         count.into()
     }
 }
+
+fn main() {
+    let d = Day7;
+    runner_main(&d, 2020, 7);
+}
@@ -0,0 +1,76 @@
+use util::vm::{Exit, Flow, Instruction, Opcode, Operand, Program, Registers, State, Vm};
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day8;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Op {
+    Nop,
+    Acc,
+    Jmp,
+}
+
+impl Opcode for Op {
+    const ALL: &'static [Self] = &[Op::Nop, Op::Acc, Op::Jmp];
+
+    fn mnemonic(self) -> &'static str {
+        match self {
+            Op::Nop => "nop",
+            Op::Acc => "acc",
+            Op::Jmp => "jmp",
+        }
+    }
+
+    fn arity(self) -> usize {
+        1
+    }
+
+    fn execute(self, args: &[Operand], state: &mut State) -> Flow {
+        match self {
+            Op::Nop => Flow::Next,
+            Op::Acc => {
+                *state.registers.by_name_mut("acc") += state.read(args[0]);
+                Flow::Next
+            }
+            Op::Jmp => Flow::Jump(state.read(args[0])),
+        }
+    }
+}
+
+/// Runs `program` until it halts or is about to repeat an instruction
+fn run(program: Program<Op>) -> (Exit, i64) {
+    let mut vm = Vm::new(program, Registers::new(&["acc"]));
+    let exit = vm.run_until_loop();
+    (exit, vm.registers().by_name("acc"))
+}
+
+impl AocDay for Day8 {
+    fn part1(&self, input: Input) -> Output {
+        let program = Program::parse(&input.0, &[]).unwrap();
+        run(program).1.into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        let program: Program<Op> = Program::parse(&input.0, &[]).unwrap();
+        let swap = |ins: &Instruction<Op>| {
+            let op = match ins.op {
+                Op::Nop => Op::Jmp,
+                Op::Jmp => Op::Nop,
+                Op::Acc => return None,
+            };
+            Some(Instruction::new(op, ins.args.clone()))
+        };
+        let (_, acc) = program
+            .find_patch(swap, |patched| match run(patched) {
+                (Exit::Halted, acc) => Some(acc),
+                _ => None,
+            })
+            .unwrap();
+        acc.into()
+    }
+}
+
+fn main() {
+    let d = Day8;
+    runner_main(&d, 2020, 8);
+}
@@ -1,4 +1,4 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output, Param};
 
 fn get_bad_number(lines: Vec<usize>, pre_length: usize, index: usize) -> Option<usize> {
     if lines.len() <= index as usize {
@@ -10,8 +10,8 @@ fn get_bad_number(lines: Vec<usize>, pre_length: usize, index: usize) -> Option<
             if i == j {
                 continue;
             }
-            let a = lines[(i + index - pre_length) as usize];
-            let b = lines[(j + index - pre_length) as usize];
+            let a = lines[i + index - pre_length];
+            let b = lines[j + index - pre_length];
             if a + b == value {
                 return get_bad_number(lines, pre_length, index + 1);
             }
@@ -20,26 +20,27 @@ fn get_bad_number(lines: Vec<usize>, pre_length: usize, index: usize) -> Option<
     Some(value)
 }
 
-pub struct S;
+struct Day9;
 
-impl crate::traits::AocDay for S {
+/// The example only considers the previous 5 numbers
+const PARAMS: &[Param] = &[Param::new("preamble", "5", "25")];
+
+impl AocDay for Day9 {
     fn part1(&self, input: Input) -> Output {
-        let num = if input.lines().count() > 50 {
-            25
-        } else {
-            5
-        };
-        get_bad_number(input.nums(), num, num).unwrap().into()
+        let num = input.param("preamble");
+        get_bad_number(input.nums().unwrap(), num, num)
+            .unwrap()
+            .into()
     }
 
     fn part2(&self, input: Input) -> Output {
         let bad = self
             .part1(input.clone())
-            .into_inner()
+            .to_string()
             .parse::<usize>()
             .unwrap();
 
-        let nums = input.nums();
+        let nums: Vec<usize> = input.nums().unwrap();
         for i in 0..nums.len() {
             let mut sum = 0;
             let mut j = i;
@@ -55,4 +56,13 @@ impl crate::traits::AocDay for S {
         }
         unreachable!()
     }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
+
+fn main() {
+    let d = Day9;
+    runner_main(&d, 2020, 9);
 }
@@ -1,13 +0,0 @@
-use crate::traits::*;
-
-pub struct S;
-
-impl AocDay for S {
-    fn part1(&self, input: Input) -> Output {
-        todo!()
-    }
-
-    fn part2(&self, input: Input) -> Output {
-        todo!()
-    }
-}
@@ -1,10 +1,10 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day1;
 
-impl AocDay for S {
-    fn part1(&self, input: crate::traits::Input) -> Output {
-        let input: Vec<u32> = input.nums();
+impl AocDay for Day1 {
+    fn part1(&self, input: Input) -> Output {
+        let input: Vec<u32> = input.nums().unwrap();
 
         let mut count = 0;
         for i in 0..input.len() - 1 {
@@ -16,8 +16,8 @@ impl AocDay for S {
         count.into()
     }
 
-    fn part2(&self, input: crate::traits::Input) -> Output {
-        let input = input.nums();
+    fn part2(&self, input: Input) -> Output {
+        let input: Vec<u32> = input.nums().unwrap();
         let mut count = 0;
         for i in 0..input.len() {
             let first = sum(&input, i);
@@ -33,3 +33,8 @@ impl AocDay for S {
 fn sum(data: &[u32], index: usize) -> u32 {
     data.iter().skip(index).take(3).sum()
 }
+
+fn main() {
+    let d = Day1;
+    runner_main(&d, 2021, 1);
+}
@@ -1,6 +1,6 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day10;
 
 fn matches(opener: char, closer: char) -> bool {
     opener == '(' && closer == ')'
@@ -9,7 +9,7 @@ fn matches(opener: char, closer: char) -> bool {
         || opener == '<' && closer == '>'
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day10 {
     fn part1(&self, input: Input) -> Output {
         let mut count = 0;
         let mut characters = Vec::new();
@@ -37,7 +37,7 @@ impl crate::traits::AocDay for S {
                             panic!("Unmatched {}", c);
                         }
                     },
-                    _ => unreachable!(c),
+                    _ => unreachable!("{}", c),
                 }
             }
         }
@@ -65,7 +65,7 @@ impl crate::traits::AocDay for S {
                             panic!("Unmatched {}", c);
                         }
                     },
-                    _ => unreachable!(c),
+                    _ => unreachable!("{}", c),
                 }
             }
             for unmatched in characters.into_iter().rev() {
@@ -86,3 +86,8 @@ impl crate::traits::AocDay for S {
         scores[scores.len() / 2].into()
     }
 }
+
+fn main() {
+    let d = Day10;
+    runner_main(&d, 2021, 10);
+}
@@ -1,8 +1,14 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, IntoEnumeratedCells, Matrix, Output};
 
-pub struct S;
+struct Day11;
 
-impl crate::traits::AocDay for S {
+fn cells(mat: &Matrix<u8>) -> Vec<(usize, usize)> {
+    (0..mat.rows())
+        .flat_map(|row| (0..mat.cols()).map(move |col| (row, col)))
+        .collect()
+}
+
+impl AocDay for Day11 {
     fn part1(&self, input: Input) -> Output {
         let mut mat = Matrix::new_from_single_nums(input.as_str()).unwrap();
         let mut flashes = 0;
@@ -13,14 +19,19 @@ impl crate::traits::AocDay for S {
             let mut flash_pos = Vec::new();
             loop {
                 let mut inner_flash_count = 0;
-                for (row, col) in mat.cells() {
+                for (row, col) in cells(&mat) {
                     if *mat.get(row, col) > 9 {
                         if !flash_pos.contains(&(row, col)) {
                             inner_flash_count += 1;
                             flash_pos.push((row, col));
                             println!("{} {} flashing", row, col);
-                            for adj in mat.neighbor_iter_mut(row, col) {
-                                *adj += 1;
+                            let neighbors: Vec<_> = mat
+                                .neighbor_iter(row, col)
+                                .enumerate_cells()
+                                .map(|(row, col, _)| (row, col))
+                                .collect();
+                            for (adj_row, adj_col) in neighbors {
+                                *mat.get_mut(adj_row, adj_col) += 1;
                             }
                         }
                     }
@@ -49,13 +60,18 @@ impl crate::traits::AocDay for S {
             let mut flash_pos = Vec::new();
             loop {
                 let mut inner_flash_count = 0;
-                for (row, col) in mat.cells() {
+                for (row, col) in cells(&mat) {
                     if *mat.get(row, col) > 9 {
                         if !flash_pos.contains(&(row, col)) {
                             inner_flash_count += 1;
                             flash_pos.push((row, col));
-                            for adj in mat.neighbor_iter_mut(row, col) {
-                                *adj += 1;
+                            let neighbors: Vec<_> = mat
+                                .neighbor_iter(row, col)
+                                .enumerate_cells()
+                                .map(|(row, col, _)| (row, col))
+                                .collect();
+                            for (adj_row, adj_col) in neighbors {
+                                *mat.get_mut(adj_row, adj_col) += 1;
                             }
                         }
                     }
@@ -76,3 +92,8 @@ impl crate::traits::AocDay for S {
         }
     }
 }
+
+fn main() {
+    let d = Day11;
+    runner_main(&d, 2021, 11);
+}
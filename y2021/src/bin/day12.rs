@@ -1,8 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day12;
 
 #[derive(Clone, Debug)]
 struct Vertex {
@@ -95,7 +95,7 @@ impl Graph {
 
         visited.insert(current_num);
         current_path.push(current_num);
-        if current.name == "end" { 
+        if current.name == "end" {
             current_path.pop();
             return 1;
         }
@@ -139,7 +139,7 @@ impl Graph {
                 }
                 let (_max_index, max_count) = max.unwrap();
 
-                if max_count >= 2 { 
+                if max_count >= 2 {
                     return 0;
                 }
             }
@@ -147,7 +147,7 @@ impl Graph {
 
         *visited.entry(current_num).or_default() += 1;
         current_path.push(current_num);
-        if current.name == "end" { 
+        if current.name == "end" {
             current_path.pop();
             return 1;
         }
@@ -166,9 +166,9 @@ impl Graph {
     }
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day12 {
     fn part1(&self, input: Input) -> Output {
-        let g = Graph::parse(input); 
+        let g = Graph::parse(input);
         let start = g.start();
 
         let mut current_path = Vec::new();
@@ -186,3 +186,8 @@ impl crate::traits::AocDay for S {
             .into()
     }
 }
+
+fn main() {
+    let d = Day12;
+    runner_main(&d, 2021, 12);
+}
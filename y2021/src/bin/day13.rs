@@ -1,8 +1,8 @@
 use std::collections::HashSet;
 
-use crate::traits::*;
+use util::{runner_main, sections, AocDay, Input, Output};
 
-pub struct S;
+struct Day13;
 
 struct Fold {
     axis: u8,
@@ -44,7 +44,7 @@ impl Fold {
 }
 
 fn parse(input: String) -> Result<Data, ()> {
-    let mut parts = input.split("\n\n");
+    let mut parts = sections(&input);
     let points = parts.next().unwrap();
     let folds = parts.next().unwrap();
     let mut r_points = HashSet::new();
@@ -71,7 +71,7 @@ fn parse(input: String) -> Result<Data, ()> {
     })
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day13 {
     fn part1(&self, input: Input) -> Output {
         let mut data = parse(input.into_inner()).unwrap();
         let fold = data.folds.remove(0);
@@ -87,20 +87,18 @@ impl crate::traits::AocDay for S {
         }
         let line_width = points.iter().map(|(x, _y)| *x).max().unwrap() + 1;
         let lines = points.iter().map(|(_x, y)| *y).max().unwrap() + 1;
+        let mut art = String::new();
         for y in 0..lines {
             for x in 0..line_width {
-                if points.contains(&(x, y)) {
-                    print!("#");
-                } else {
-                    print!(" ");
-                }
+                art.push(if points.contains(&(x, y)) { '#' } else { '.' });
             }
-            println!();
+            art.push('\n');
         }
-
-        let mut input = String::new();
-        println!("Input the characters that you see?");
-        std::io::stdin().read_line(&mut input).unwrap();
-        input.into()
+        art.into()
     }
 }
+
+fn main() {
+    let d = Day13;
+    runner_main(&d, 2021, 13);
+}
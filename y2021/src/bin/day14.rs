@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use util::counting::Counter;
+use util::{runner_main, sections, AocDay, Input, Output};
+
+struct Day14;
+
+#[derive(Debug)]
+struct Data {
+    template: Vec<char>,
+    rules: HashMap<(char, char), char>,
+}
+
+fn parse(input: Input) -> Data {
+    let p = input.into_inner();
+    let mut p = sections(&p);
+    let template = p.next().unwrap().trim().chars().collect();
+    let rules = p
+        .next()
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let (pair, insert) = line.split_once(" -> ").unwrap();
+            let (a, b) = pair.chars().collect_tuple().unwrap();
+            ((a, b), insert.chars().next().unwrap())
+        })
+        .collect();
+    Data { template, rules }
+}
+
+/// Runs `steps` pair insertions and returns the most common element count minus the least
+/// common element count
+fn polymerize(d: &Data, steps: usize) -> u64 {
+    let pairs: Counter<(char, char)> = d.template.iter().copied().tuple_windows().collect();
+    let pairs = pairs.simulate(steps, |&(a, b)| match d.rules.get(&(a, b)) {
+        Some(&c) => vec![(a, c), (c, b)],
+        None => vec![(a, b)],
+    });
+
+    // every element starts exactly one pair except the last, which never changes
+    let mut elements = pairs.map_keys(|&(a, _)| a);
+    elements.add(*d.template.last().unwrap(), 1);
+    elements.most_common().unwrap().1 - elements.least_common().unwrap().1
+}
+
+impl AocDay for Day14 {
+    fn part1(&self, input: Input) -> Output {
+        polymerize(&parse(input), 10).into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        polymerize(&parse(input), 40).into()
+    }
+}
+
+fn main() {
+    let d = Day14;
+    runner_main(&d, 2021, 14);
+}
@@ -0,0 +1,54 @@
+use pathfinding::prelude::dijkstra;
+use util::{runner_main, AocDay, Input, IntoEnumeratedCells, Matrix, Output};
+
+struct Day15;
+
+/// Total risk of the cheapest path from the top left to the bottom right corner
+fn lowest_risk(mat: &Matrix<u8>) -> usize {
+    let end = (mat.rows() - 1, mat.cols() - 1);
+    let (_path, cost) = dijkstra(
+        &(0, 0),
+        |&(row, col)| {
+            mat.adjacent_neighbor_iter(row, col)
+                .enumerate_cells()
+                .map(|(row, col, risk)| ((row, col), *risk as usize))
+                .collect::<Vec<_>>()
+        },
+        |&pos| pos == end,
+    )
+    .unwrap();
+    cost
+}
+
+impl AocDay for Day15 {
+    fn part1(&self, input: Input) -> Output {
+        let mat = Matrix::new_from_single_nums(input.as_str()).unwrap();
+        lowest_risk(&mat).into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        let small = Matrix::new_from_single_nums(input.as_str()).unwrap();
+        let mut mat: Matrix<u8> = Matrix::new_with_value(small.rows() * 5, small.cols() * 5, 0);
+        for row in 0..mat.rows() {
+            for col in 0..mat.cols() {
+                let big_row = row / small.rows();
+                let big_col = col / small.cols();
+
+                let o_val = small.get(row % small.rows(), col % small.cols());
+                let mut val = big_col as u8 + big_row as u8 + *o_val;
+                if val >= 10 {
+                    val = val % 10 + 1;
+                }
+
+                mat.set(row, col, val);
+            }
+        }
+
+        lowest_risk(&mat).into()
+    }
+}
+
+fn main() {
+    let d = Day15;
+    runner_main(&d, 2021, 15);
+}
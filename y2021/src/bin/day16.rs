@@ -0,0 +1,148 @@
+use util::bits::{BitError, BitReader};
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day16;
+
+#[derive(Debug)]
+struct Packet {
+    version: u8,
+    kind: Kind,
+}
+
+#[derive(Debug)]
+enum Kind {
+    Literal(u64),
+    Operator { id: u8, children: Vec<Packet> },
+}
+
+fn parse_packet(reader: &mut BitReader) -> Result<Packet, BitError> {
+    let version = reader.read(3)?;
+    let id = reader.read(3)?;
+    if id == 4 {
+        let mut value = 0;
+        loop {
+            let more = reader.read_bit()?;
+            // shift in a wider type so values too long for a u64 are reported, not wrapped
+            let wide = (value as u128) << 4 | reader.read::<u128>(4)?;
+            value = u64::try_from(wide).map_err(|_| BitError::Overflow {
+                value: wide,
+                ty: "u64",
+            })?;
+            if !more {
+                break;
+            }
+        }
+        return Ok(Packet {
+            version,
+            kind: Kind::Literal(value),
+        });
+    }
+
+    let mut children = Vec::new();
+    if reader.read_bit()? {
+        let count: usize = reader.read(11)?;
+        for _ in 0..count {
+            children.push(parse_packet(reader)?);
+        }
+    } else {
+        let length = reader.read(15)?;
+        let mut sub_packets = reader.sub_reader(length)?;
+        while sub_packets.remaining() != 0 {
+            children.push(parse_packet(&mut sub_packets)?);
+        }
+    }
+    Ok(Packet {
+        version,
+        kind: Kind::Operator { id, children },
+    })
+}
+
+fn parse(input: &Input) -> Packet {
+    let mut reader = BitReader::from_hex(input.as_str()).unwrap();
+    parse_packet(&mut reader).unwrap()
+}
+
+impl Packet {
+    fn version_sum(&self) -> usize {
+        let children = match &self.kind {
+            Kind::Literal(_) => 0,
+            Kind::Operator { children, .. } => children.iter().map(Packet::version_sum).sum(),
+        };
+        self.version as usize + children
+    }
+
+    fn value(&self) -> u64 {
+        let (id, children) = match &self.kind {
+            Kind::Literal(value) => return *value,
+            Kind::Operator { id, children } => (id, children),
+        };
+        let mut values = children.iter().map(Packet::value);
+        match id {
+            0 => values.sum(),
+            1 => values.product(),
+            2 => values.min().unwrap(),
+            3 => values.max().unwrap(),
+            _ => {
+                let (a, b) = (values.next().unwrap(), values.next().unwrap());
+                match id {
+                    5 => (a > b) as u64,
+                    6 => (a < b) as u64,
+                    7 => (a == b) as u64,
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+}
+
+impl AocDay for Day16 {
+    fn part1(&self, input: Input) -> Output {
+        parse(&input).version_sum().into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        parse(&input).value().into()
+    }
+}
+
+fn main() {
+    let d = Day16;
+    runner_main(&d, 2021, 16);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::IsTest;
+
+    fn input(hex: &str) -> Input {
+        Input::new(hex.to_owned(), IsTest::Yes)
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(parse(&input("D2FE28")).value(), 2021);
+        assert_eq!(parse(&input("8A004A801A8002F478")).version_sum(), 16);
+        assert_eq!(
+            parse(&input("A0016C880162017C3686B18A3D4780")).version_sum(),
+            31
+        );
+        assert_eq!(parse(&input("9C0141080250320F1802104A08")).value(), 1);
+        assert_eq!(parse(&input("880086C3E88112")).value(), 7);
+    }
+
+    #[test]
+    fn long_literal() {
+        let literal = |groups: usize| format!("000100{}01111", "11111".repeat(groups - 1));
+        let mut reader = BitReader::from_binary(&literal(16)).unwrap();
+        assert!(matches!(
+            parse_packet(&mut reader).unwrap().kind,
+            Kind::Literal(u64::MAX)
+        ));
+        let mut reader = BitReader::from_binary(&literal(17)).unwrap();
+        assert!(matches!(
+            parse_packet(&mut reader),
+            Err(BitError::Overflow { .. })
+        ));
+    }
+}
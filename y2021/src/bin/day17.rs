@@ -1,9 +1,9 @@
 use itertools::Itertools;
 use std::ops::RangeInclusive;
 
-use crate::traits::*;
+use util::{runner_main, scan, AocDay, Input, Output, ParseError};
 
-pub struct S;
+struct Day17;
 
 fn simulate(
     mut dx: i32,
@@ -31,22 +31,12 @@ fn simulate(
     }
 }
 
-fn parse(input: Input) -> Result<(i32, i32, i32, i32), scanfmt::ScanError> {
-    let (x1, x2, y1, y2): (i32, i32, i32, i32);
-    scanfmt::scanfmt!(
-        input.as_str().trim(),
-        "target area: x={}..{}, y=-{}..{}",
-        x1,
-        x2,
-        y1,
-        y2
-    );
-
-    Ok((x1, x2, y1, y2))
+fn parse(input: Input) -> Result<(i32, i32, i32, i32), ParseError> {
+    scan(input.as_str().trim(), "target area: x={}..{}, y=-{}..{}")
 }
 
-impl AocDay for S {
-    fn part1(&self, input: crate::traits::Input) -> Output {
+impl AocDay for Day17 {
+    fn part1(&self, input: Input) -> Output {
         let (x1, x2, y1, y2) = parse(input).unwrap();
         let x_target = x1..=x2;
         let y_target = y1..=y2;
@@ -59,15 +49,21 @@ impl AocDay for S {
         let x_target = &x_target;
         let y_target = &y_target;
 
-        let maxys = x_range.into_iter()
+        let maxys = x_range
+            .into_iter()
             .cartesian_product(y_range.into_iter())
             .filter_map(move |(x, y)| simulate(x, y, x_target, y_target))
             .collect::<Vec<_>>();
 
-        maxys.iter().max().unwrap().into()
+        (*maxys.iter().max().unwrap()).into()
     }
 
-    fn part2(&self, input: crate::traits::Input) -> Output {
-        todo!()
+    fn part2(&self, _input: Input) -> Output {
+        Output::Unimplemented
     }
 }
+
+fn main() {
+    let d = Day17;
+    runner_main(&d, 2021, 17);
+}
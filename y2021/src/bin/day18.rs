@@ -0,0 +1,119 @@
+use util::tree::{NodeId, Tree};
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day18;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Snail {
+    Pair,
+    Num(u32),
+}
+
+type Number = Tree<Snail>;
+
+fn parse_number(line: &[u8]) -> Number {
+    let mut tree = Tree::new(Snail::Pair);
+    let mut current = tree.root();
+    // the outer brackets are the root
+    for &c in &line[1..line.len() - 1] {
+        match c {
+            b'[' => current = tree.push_child(current, Snail::Pair),
+            b']' => current = tree.parent(current).unwrap(),
+            b'0'..=b'9' => {
+                tree.push_child(current, Snail::Num((c - b'0') as u32));
+            }
+            b',' => {}
+            _ => unreachable!("{}", c as char),
+        }
+    }
+    tree
+}
+
+fn parse(input: &Input) -> Vec<Number> {
+    input
+        .lines_bytes()
+        .filter(|l| !l.is_empty())
+        .map(parse_number)
+        .collect()
+}
+
+fn add(a: Number, b: Number) -> Number {
+    let mut sum = Tree::new(Snail::Pair);
+    let root = sum.root();
+    sum.append_tree(root, a);
+    sum.append_tree(root, b);
+    while explode(&mut sum) || split(&mut sum) {}
+    sum
+}
+
+fn value(sf: &Number, id: NodeId) -> u32 {
+    match sf[id] {
+        Snail::Num(n) => n,
+        Snail::Pair => unreachable!("not a regular number"),
+    }
+}
+
+/// Explodes the leftmost pair nested inside four pairs, returning false if there is none
+fn explode(sf: &mut Number) -> bool {
+    let leaves = sf.leaves(sf.root());
+    let Some(i) = leaves.iter().position(|&id| sf.depth(id) > 4) else {
+        return false;
+    };
+    let pair = sf.parent(leaves[i]).unwrap();
+    let (left, right) = (value(sf, leaves[i]), value(sf, leaves[i + 1]));
+    if let Some(&prev) = i.checked_sub(1).and_then(|p| leaves.get(p)) {
+        sf[prev] = Snail::Num(value(sf, prev) + left);
+    }
+    if let Some(&next) = leaves.get(i + 2) {
+        sf[next] = Snail::Num(value(sf, next) + right);
+    }
+    sf.clear_children(pair);
+    sf[pair] = Snail::Num(0);
+    true
+}
+
+/// Splits the leftmost number that is 10 or more, returning false if there is none
+fn split(sf: &mut Number) -> bool {
+    let leaves = sf.leaves(sf.root());
+    let Some(id) = leaves.into_iter().find(|&id| value(sf, id) >= 10) else {
+        return false;
+    };
+    let n = value(sf, id);
+    sf[id] = Snail::Pair;
+    sf.push_child(id, Snail::Num(n / 2));
+    sf.push_child(id, Snail::Num(n.div_ceil(2)));
+    true
+}
+
+fn magnitude(sf: &Number) -> u32 {
+    let magnitudes = sf.aggregate(sf.root(), |snail, children| match snail {
+        Snail::Num(n) => *n,
+        Snail::Pair => 3 * children[0] + 2 * children[1],
+    });
+    magnitudes[&sf.root()]
+}
+
+impl AocDay for Day18 {
+    fn part1(&self, input: Input) -> Output {
+        let sum = parse(&input).into_iter().reduce(add).unwrap();
+        magnitude(&sum).into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        let numbers = parse(&input);
+        let mut best = 0;
+        for (i, a) in numbers.iter().enumerate() {
+            for (j, b) in numbers.iter().enumerate() {
+                if i != j {
+                    best = best.max(magnitude(&add(a.clone(), b.clone())));
+                }
+            }
+        }
+        best.into()
+    }
+}
+
+fn main() {
+    let d = Day18;
+    runner_main(&d, 2021, 18);
+}
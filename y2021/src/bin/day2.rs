@@ -1,10 +1,9 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day2;
 
-impl AocDay for S {
+impl AocDay for Day2 {
     fn part1(&self, input: Input) -> Output {
-
         let mut horiz = 0;
         let mut depth = 0;
 
@@ -48,3 +47,8 @@ impl AocDay for S {
         (horiz * depth).into()
     }
 }
+
+fn main() {
+    let d = Day2;
+    runner_main(&d, 2021, 2);
+}
@@ -0,0 +1,52 @@
+use util::ranges::{Cuboid, CuboidSet};
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day22;
+
+fn parse(input: &Input) -> Vec<(bool, Cuboid<i64, 3>)> {
+    input
+        .lines()
+        .map(|line| {
+            let (state, coords) = line.split_once(' ').unwrap();
+            let mut min = [0; 3];
+            let mut max = [0; 3];
+            for (axis, range) in coords.split(',').enumerate() {
+                let (start, end) = range[2..].split_once("..").unwrap();
+                min[axis] = start.parse().unwrap();
+                max[axis] = end.parse().unwrap();
+            }
+            (state == "on", Cuboid::new(min, max))
+        })
+        .collect()
+}
+
+fn reboot(steps: impl IntoIterator<Item = (bool, Cuboid<i64, 3>)>) -> u128 {
+    let mut reactor = CuboidSet::new();
+    for (on, cuboid) in steps {
+        if on {
+            reactor.insert(cuboid);
+        } else {
+            reactor.remove(&cuboid);
+        }
+    }
+    reactor.volume()
+}
+
+impl AocDay for Day22 {
+    fn part1(&self, input: Input) -> Output {
+        let region = Cuboid::new([-50; 3], [50; 3]);
+        let steps = parse(&input)
+            .into_iter()
+            .filter_map(|(on, cuboid)| Some((on, cuboid.intersection(&region)?)));
+        reboot(steps).into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        reboot(parse(&input)).into()
+    }
+}
+
+fn main() {
+    let d = Day22;
+    runner_main(&d, 2021, 22);
+}
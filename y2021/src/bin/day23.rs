@@ -1,7 +1,8 @@
-use crate::traits::*;
-use bitset_core::BitSet;
+#![allow(dead_code)]
 
-pub struct S;
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day23;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 struct Config {
@@ -233,17 +234,22 @@ fn solve(
 }
 */
 
-impl AocDay for S {
-    fn part1(&self, input: Input) -> Output {
+impl AocDay for Day23 {
+    fn part1(&self, _input: Input) -> Output {
         /*let cfg = Config::parse(input.as_str());
         println!("{}", cfg);
         let mut costs = Vec::new();
         solve(cfg, 0, &mut costs);
         costs.into_iter().min().unwrap().into()*/
-        todo!()
+        Output::Unimplemented
     }
 
-    fn part2(&self, input: Input) -> Output {
-        todo!()
+    fn part2(&self, _input: Input) -> Output {
+        Output::Unimplemented
     }
 }
+
+fn main() {
+    let d = Day23;
+    runner_main(&d, 2021, 23);
+}
@@ -0,0 +1,628 @@
+use std::rc::Rc;
+
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day24;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct Register(u8);
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+enum Operand {
+    Register(Register),
+    Literal(i8),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Ins {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+#[derive(Debug, Clone)]
+struct Program(Vec<Ins>);
+
+impl Register {
+    fn parse<'a>(items: &mut impl Iterator<Item = &'a str>) -> Self {
+        let s = items.next().unwrap();
+        match s {
+            "w" => Register(0),
+            "x" => Register(1),
+            "y" => Register(2),
+            "z" => Register(3),
+            _ => panic!("Unexpected {}", s),
+        }
+    }
+}
+
+impl Operand {
+    fn parse<'a>(items: &mut impl Iterator<Item = &'a str>) -> Self {
+        let s = items.next().unwrap();
+        match s {
+            "w" => Operand::Register(Register(0)),
+            "x" => Operand::Register(Register(1)),
+            "y" => Operand::Register(Register(2)),
+            "z" => Operand::Register(Register(3)),
+            _ => Operand::Literal(s.parse().unwrap()),
+        }
+    }
+}
+
+impl Program {
+    fn parse(input: Input) -> Program {
+        let program = input
+            .lines()
+            .map(|line| {
+                let mut parts = line.split(' ');
+                let ins = parts.next().unwrap();
+                match ins {
+                    "inp" => Ins::Inp(Register::parse(&mut parts)),
+                    "add" => Ins::Add(Register::parse(&mut parts), Operand::parse(&mut parts)),
+                    "mul" => Ins::Mul(Register::parse(&mut parts), Operand::parse(&mut parts)),
+                    "div" => Ins::Div(Register::parse(&mut parts), Operand::parse(&mut parts)),
+                    "mod" => Ins::Mod(Register::parse(&mut parts), Operand::parse(&mut parts)),
+                    "eql" => Ins::Eql(Register::parse(&mut parts), Operand::parse(&mut parts)),
+                    bad => unreachable!("{}", bad),
+                }
+            })
+            .collect();
+
+        Program(program)
+    }
+
+    fn input_count(&self) -> usize {
+        self.0.iter().filter(|i| matches!(i, Ins::Inp(_))).count()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+enum BinOp {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+enum ExprKind {
+    Const(isize),
+    /// One digit of the model number, always in `1..=9`
+    Input(usize),
+    Bin(BinOp, Expr, Expr),
+}
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct Node {
+    kind: ExprKind,
+    /// The smallest and largest value this expression can take
+    min: isize,
+    max: isize,
+}
+
+/// A symbolic value computed by the ALU, simplified as it is built using the range of values
+/// each sub-expression can take
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct Expr(Rc<Node>);
+
+impl Expr {
+    fn new(kind: ExprKind, min: isize, max: isize) -> Self {
+        Self(Rc::new(Node { kind, min, max }))
+    }
+
+    fn constant(value: isize) -> Self {
+        Self::new(ExprKind::Const(value), value, value)
+    }
+
+    fn input(index: usize) -> Self {
+        Self::new(ExprKind::Input(index), 1, 9)
+    }
+
+    fn as_const(&self) -> Option<isize> {
+        match self.0.kind {
+            ExprKind::Const(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn is_non_negative(&self) -> bool {
+        self.0.min >= 0
+    }
+
+    /// If this expression is `x * k + e` with `x` and `e` non-negative, returns `x` and `e`
+    fn split_multiple_of(&self, k: isize) -> Option<(&Expr, &Expr)> {
+        let ExprKind::Bin(BinOp::Add, high, low) = &self.0.kind else {
+            return None;
+        };
+        let ExprKind::Bin(BinOp::Mul, x, factor) = &high.0.kind else {
+            return None;
+        };
+        (factor.as_const() == Some(k) && x.is_non_negative() && low.is_non_negative())
+            .then_some((x, low))
+    }
+
+    fn binary(op: BinOp, a: Expr, b: Expr) -> Self {
+        if let (Some(a), Some(b)) = (a.as_const(), b.as_const()) {
+            return Self::constant(match op {
+                BinOp::Add => a + b,
+                BinOp::Mul => a * b,
+                BinOp::Div => a / b,
+                BinOp::Mod => a % b,
+                BinOp::Eql => (a == b) as isize,
+            });
+        }
+        let (a_const, b_const) = (a.as_const(), b.as_const());
+        match op {
+            BinOp::Add if a_const == Some(0) => return b,
+            BinOp::Add if b_const == Some(0) => return a,
+            BinOp::Mul if a_const == Some(0) || b_const == Some(0) => return Self::constant(0),
+            BinOp::Mul if a_const == Some(1) => return b,
+            BinOp::Mul if b_const == Some(1) => return a,
+            BinOp::Div if b_const == Some(1) => return a,
+            BinOp::Div | BinOp::Mod if b_const.is_some_and(|k| k > 0) => {
+                let k = b_const.unwrap();
+                if a.is_non_negative() && a.0.max < k {
+                    return match op {
+                        BinOp::Div => Self::constant(0),
+                        _ => a,
+                    };
+                }
+                // (x * k + e) / k == x + e / k, and (x * k + e) % k == e % k
+                if let Some((x, e)) = a.split_multiple_of(k) {
+                    let low = Self::binary(op, e.clone(), b.clone());
+                    return match op {
+                        BinOp::Div => Self::binary(BinOp::Add, x.clone(), low),
+                        _ => low,
+                    };
+                }
+            }
+            BinOp::Eql if a == b => return Self::constant(1),
+            BinOp::Eql if a.0.max < b.0.min || b.0.max < a.0.min => return Self::constant(0),
+            _ => {}
+        }
+
+        let (min, max) = match op {
+            BinOp::Add => (
+                a.0.min.saturating_add(b.0.min),
+                a.0.max.saturating_add(b.0.max),
+            ),
+            BinOp::Mul => {
+                let products = [
+                    a.0.min.saturating_mul(b.0.min),
+                    a.0.min.saturating_mul(b.0.max),
+                    a.0.max.saturating_mul(b.0.min),
+                    a.0.max.saturating_mul(b.0.max),
+                ];
+                (
+                    *products.iter().min().unwrap(),
+                    *products.iter().max().unwrap(),
+                )
+            }
+            BinOp::Div => match b_const {
+                Some(k) if k > 0 => (a.0.min / k, a.0.max / k),
+                _ => {
+                    let bound = a.0.min.saturating_abs().max(a.0.max.saturating_abs());
+                    (-bound, bound)
+                }
+            },
+            BinOp::Mod if a.is_non_negative() && b.0.min > 0 => (0, a.0.max.min(b.0.max - 1)),
+            BinOp::Mod => {
+                let bound = b.0.min.saturating_abs().max(b.0.max.saturating_abs());
+                (-bound, bound)
+            }
+            BinOp::Eql => (0, 1),
+        };
+        Self::new(ExprKind::Bin(op, a, b), min, max)
+    }
+
+    fn eval(&self, inputs: &[isize]) -> isize {
+        match &self.0.kind {
+            ExprKind::Const(v) => *v,
+            ExprKind::Input(i) => inputs[*i],
+            ExprKind::Bin(op, a, b) => {
+                let (a, b) = (a.eval(inputs), b.eval(inputs));
+                match op {
+                    BinOp::Add => a + b,
+                    BinOp::Mul => a * b,
+                    BinOp::Div => a / b,
+                    BinOp::Mod => a % b,
+                    BinOp::Eql => (a == b) as isize,
+                }
+            }
+        }
+    }
+
+    /// Adds the index of every input this expression depends on to `inputs`
+    fn collect_inputs(&self, inputs: &mut Vec<usize>) {
+        match &self.0.kind {
+            ExprKind::Const(_) => {}
+            ExprKind::Input(i) => inputs.push(*i),
+            ExprKind::Bin(_, a, b) => {
+                a.collect_inputs(inputs);
+                b.collect_inputs(inputs);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+impl std::fmt::Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0.kind {
+            ExprKind::Const(v) => write!(f, "{v}"),
+            ExprKind::Input(i) => write!(f, "d{i}"),
+            ExprKind::Bin(op, a, b) => {
+                let op = match op {
+                    BinOp::Add => "+",
+                    BinOp::Mul => "*",
+                    BinOp::Div => "/",
+                    BinOp::Mod => "%",
+                    BinOp::Eql => "==",
+                };
+                write!(f, "({a} {op} {b})")
+            }
+        }
+    }
+}
+
+/// A condition on the inputs that an `eql` instruction needed to pick a branch
+#[derive(Debug, Clone)]
+struct Constraint {
+    lhs: Expr,
+    rhs: Expr,
+    equal: bool,
+    /// The inputs this constraint depends on, sorted
+    inputs: Vec<usize>,
+}
+
+impl Constraint {
+    fn holds(&self, inputs: &[isize]) -> bool {
+        (self.lhs.eval(inputs) == self.rhs.eval(inputs)) == self.equal
+    }
+}
+
+/// One way through the program, after deciding every `eql` that range analysis couldn't
+#[derive(Debug, Clone)]
+struct Path {
+    registers: [Expr; 4],
+    constraints: Vec<Constraint>,
+}
+
+impl Program {
+    /// Runs the program on symbolic inputs, forking whenever an `eql` could go either way
+    fn symbolic_paths(&self) -> Vec<Path> {
+        let mut paths = vec![Path {
+            registers: std::array::from_fn(|_| Expr::constant(0)),
+            constraints: Vec::new(),
+        }];
+        let mut next_input = 0;
+
+        for ins in &self.0 {
+            let mut forked = Vec::new();
+            for path in &mut paths {
+                let read = |op: Operand| match op {
+                    Operand::Register(reg) => path.registers[reg.0 as usize].clone(),
+                    Operand::Literal(lit) => Expr::constant(lit as isize),
+                };
+                let (dst, value) = match *ins {
+                    Ins::Inp(a) => (a, Expr::input(next_input)),
+                    Ins::Add(a, b) => (
+                        a,
+                        Expr::binary(BinOp::Add, read(Operand::Register(a)), read(b)),
+                    ),
+                    Ins::Mul(a, b) => (
+                        a,
+                        Expr::binary(BinOp::Mul, read(Operand::Register(a)), read(b)),
+                    ),
+                    Ins::Div(a, b) => (
+                        a,
+                        Expr::binary(BinOp::Div, read(Operand::Register(a)), read(b)),
+                    ),
+                    Ins::Mod(a, b) => (
+                        a,
+                        Expr::binary(BinOp::Mod, read(Operand::Register(a)), read(b)),
+                    ),
+                    Ins::Eql(a, b) => {
+                        let (lhs, rhs) = (read(Operand::Register(a)), read(b));
+                        let value = Expr::binary(BinOp::Eql, lhs.clone(), rhs.clone());
+                        if value.as_const().is_some() {
+                            (a, value)
+                        } else {
+                            let mut inputs = Vec::new();
+                            lhs.collect_inputs(&mut inputs);
+                            rhs.collect_inputs(&mut inputs);
+                            inputs.sort_unstable();
+                            inputs.dedup();
+                            let mut constraint = Constraint {
+                                lhs,
+                                rhs,
+                                equal: false,
+                                inputs,
+                            };
+                            let mut unequal = path.clone();
+                            unequal.registers[a.0 as usize] = Expr::constant(0);
+                            unequal.constraints.push(constraint.clone());
+                            forked.push(unequal);
+
+                            constraint.equal = true;
+                            path.constraints.push(constraint);
+                            (a, Expr::constant(1))
+                        }
+                    }
+                };
+                path.registers[dst.0 as usize] = value;
+            }
+            paths.extend(forked);
+            if matches!(ins, Ins::Inp(_)) {
+                next_input += 1;
+            }
+        }
+        paths
+    }
+
+    /// Finds the largest (or smallest) model number accepted by this program.
+    ///
+    /// Fails if no path through the program always ends with z == 0, since the final z of those
+    /// paths can't be solved for by only picking branches, or if no digits satisfy any path
+    fn solve(&self, largest: bool) -> Result<usize, String> {
+        let count = self.input_count();
+        let paths: Vec<Path> = self
+            .symbolic_paths()
+            .into_iter()
+            .filter(|path| path.registers[3].as_const() == Some(0))
+            .collect();
+        if paths.is_empty() {
+            return Err("z is never known to be 0 at the end of the program".to_string());
+        }
+        let best = paths
+            .iter()
+            .filter_map(|path| solve_constraints(&path.constraints, count, largest))
+            .map(|digits| digits.iter().fold(0, |n, &d| n * 10 + d as usize));
+        let best = if largest { best.max() } else { best.min() };
+        best.ok_or_else(|| "no model number is accepted".to_string())
+    }
+}
+
+/// Finds the largest (or smallest) digits that satisfy every constraint.
+///
+/// Digits are split into groups that share constraints. Groups don't affect each other, so each
+/// one is searched on its own, and digits without constraints are simply 9 (or 1)
+fn solve_constraints(
+    constraints: &[Constraint],
+    count: usize,
+    largest: bool,
+) -> Option<Vec<isize>> {
+    let mut group: Vec<usize> = (0..count).collect();
+    for constraint in constraints {
+        let target = group[constraint.inputs[0]];
+        for &input in &constraint.inputs[1..] {
+            let old = group[input];
+            group
+                .iter_mut()
+                .filter(|g| **g == old)
+                .for_each(|g| *g = target);
+        }
+    }
+
+    let mut digits = vec![0; count];
+    for id in 0..count {
+        let members: Vec<usize> = (0..count).filter(|&i| group[i] == id).collect();
+        if !members.is_empty() && !choose_digits(constraints, &members, &mut digits, largest) {
+            return None;
+        }
+    }
+    Some(digits)
+}
+
+/// Assigns `members` from most to least significant, preferring large (or small) digits and
+/// backtracking when a constraint fails
+fn choose_digits(
+    constraints: &[Constraint],
+    members: &[usize],
+    digits: &mut [isize],
+    largest: bool,
+) -> bool {
+    let Some((&index, rest)) = members.split_first() else {
+        return true;
+    };
+    for i in 1..=9 {
+        digits[index] = if largest { 10 - i } else { i };
+        // a constraint can be checked once its last input is assigned
+        let valid = constraints
+            .iter()
+            .filter(|c| c.inputs.last() == Some(&index))
+            .all(|c| c.holds(digits));
+        if valid && choose_digits(constraints, rest, digits, largest) {
+            return true;
+        }
+    }
+    false
+}
+
+impl AocDay for Day24 {
+    fn part1(&self, input: Input) -> Output {
+        let program = Program::parse(input);
+        program
+            .solve(true)
+            .unwrap_or_else(|e| panic!("{}", e))
+            .into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        let program = Program::parse(input);
+        program
+            .solve(false)
+            .unwrap_or_else(|e| panic!("{}", e))
+            .into()
+    }
+}
+
+fn main() {
+    let d = Day24;
+    runner_main(&d, 2021, 24);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::IsTest;
+
+    /// Runs the ALU directly, to check answers found symbolically
+    struct Computer([isize; 4]);
+
+    impl Register {
+        fn write(self, computer: &mut Computer, value: isize) {
+            computer.0[self.0 as usize] = value;
+        }
+
+        fn read(self, computer: &Computer) -> isize {
+            computer.0[self.0 as usize]
+        }
+    }
+
+    impl Operand {
+        fn read(self, computer: &Computer) -> isize {
+            match self {
+                Operand::Register(reg) => computer.0[reg.0 as usize],
+                Operand::Literal(lit) => lit as isize,
+            }
+        }
+    }
+
+    impl Computer {
+        fn new() -> Self {
+            Self([0; 4])
+        }
+
+        fn is_valid(&mut self, program: &Program, serial: &[u8]) -> bool {
+            if serial.contains(&0) {
+                return false;
+            }
+            let mut s_index = 0;
+            for ins in &program.0 {
+                match ins {
+                    Ins::Inp(op) => {
+                        op.write(self, (serial[s_index] as isize) - (b'0' as isize));
+                        s_index += 1;
+                    }
+                    Ins::Add(op_a, op_b) => {
+                        let a = op_a.read(self);
+                        let b = op_b.read(self);
+                        op_a.write(self, a + b);
+                    }
+                    Ins::Mul(op_a, op_b) => {
+                        let a = op_a.read(self);
+                        let b = op_b.read(self);
+                        op_a.write(self, a * b);
+                    }
+                    Ins::Div(op_a, op_b) => {
+                        let a = op_a.read(self);
+                        let b = op_b.read(self);
+                        op_a.write(self, a / b);
+                    }
+                    Ins::Mod(op_a, op_b) => {
+                        let a = op_a.read(self);
+                        let b = op_b.read(self);
+                        op_a.write(self, a % b);
+                    }
+                    Ins::Eql(op_a, op_b) => {
+                        let a = op_a.read(self);
+                        let b = op_b.read(self);
+                        let result = if a == b { 1 } else { 0 };
+                        op_a.write(self, result);
+                    }
+                }
+            }
+
+            self.0[3] == 0
+        }
+    }
+
+    /// Builds a program from the usual 14 block shape, each block given as (div z, add x, add y)
+    fn blocks(params: &[(i8, i8, i8)]) -> Program {
+        let mut src = String::new();
+        for (div, check, offset) in params {
+            src.push_str(&format!(
+                "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {div}\nadd x {check}\neql x w\n\
+                eql x 0\nmul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\n\
+                add y {offset}\nmul y x\nadd z y\n"
+            ));
+        }
+        Program::parse(Input::new(src.trim_end().to_string(), IsTest::Yes))
+    }
+
+    #[test]
+    fn reduce() {
+        let program = r#"inp y
+inp y
+mul y 0
+inp z
+add z y"#;
+        let program = Program::parse(Input::new(program.to_string(), IsTest::Yes));
+        let paths = program.symbolic_paths();
+        // y is zeroed before being added, so z is just the third input
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].registers[3], Expr::input(2));
+        assert_eq!(paths[0].registers[2], Expr::constant(0));
+
+        let program = Program::parse(Input::new(
+            "inp z\nadd z -3\neql z 0".to_string(),
+            IsTest::Yes,
+        ));
+        let paths = program.symbolic_paths();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].constraints[0].lhs.to_string(), "(d0 + -3)");
+    }
+
+    #[test]
+    fn solve_stack_program() {
+        let program = blocks(&[
+            (1, 11, 6),
+            (1, 13, 14),
+            (1, 15, 14),
+            (26, -8, 10),
+            (1, 13, 9),
+            (1, 15, 12),
+            (26, -11, 8),
+            (26, -4, 13),
+            (26, -15, 12),
+            (1, 14, 6),
+            (1, 14, 9),
+            (26, -1, 15),
+            (26, -8, 4),
+            (26, -14, 10),
+        ]);
+        assert_eq!(program.solve(true), Ok(99394899891971));
+        assert_eq!(program.solve(false), Ok(92171126131911));
+
+        assert!(Computer::new().is_valid(&program, b"99394899891971"));
+        assert!(Computer::new().is_valid(&program, b"92171126131911"));
+        assert!(!Computer::new().is_valid(&program, b"99394899891972"));
+    }
+
+    #[test]
+    fn unsolvable() {
+        // z is just the digit, so it is never 0
+        let program = Program::parse(Input::new("inp z".to_string(), IsTest::Yes));
+        assert!(program.solve(true).is_err());
+        // z is 0 only when the digit is both 5 and 6
+        let program = Program::parse(Input::new(
+            "inp w\nadd x w\neql x 5\nadd y w\neql y 6\nmul x y\neql x 0\nadd z x".to_string(),
+            IsTest::Yes,
+        ));
+        assert_eq!(
+            program.solve(false),
+            Err("no model number is accepted".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "z is never known to be 0 at the end of the program")]
+    fn unsolvable_part() {
+        Day24.part1(Input::new("inp z".to_string(), IsTest::Yes));
+    }
+}
@@ -1,14 +1,14 @@
 use std::sync::Arc;
 
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, IntoEnumeratedCells, Matrix, Output};
 
-pub struct S;
+struct Day25;
 
 fn step(mat: &Matrix<u8>) -> Matrix<u8> {
     let mut result = mat.clone();
     let rows = mat.rows();
     let cols = mat.cols();
-    for (row, o_col, c) in mat.enumerated_iter() {
+    for (row, o_col, c) in mat.iter().enumerate_cells() {
         let mut col = o_col;
         let c = *c;
         if c == b'>' {
@@ -23,7 +23,7 @@ fn step(mat: &Matrix<u8>) -> Matrix<u8> {
 
     let mat = result.clone();
 
-    for (o_row, col, c) in mat.enumerated_iter() {
+    for (o_row, col, c) in mat.iter().enumerate_cells() {
         let mut row = o_row;
         let c = *c;
         if c == b'v' {
@@ -39,15 +39,14 @@ fn step(mat: &Matrix<u8>) -> Matrix<u8> {
     result
 }
 
-impl AocDay for S {
+impl AocDay for Day25 {
     fn part1(&self, input: Input) -> Output {
         let mut mat = Arc::new(Matrix::new_from_chars(input.as_str()).unwrap());
-        let mut last = Arc::clone(&mat);
         let mut count = 0;
         loop {
             count += 1;
 
-            last = Arc::clone(&mat);
+            let last = Arc::clone(&mat);
             mat = Arc::new(step(&mat));
             if last == mat {
                 break count.into();
@@ -55,7 +54,12 @@ impl AocDay for S {
         }
     }
 
-    fn part2(&self, input: Input) -> Output {
-        todo!()
+    fn part2(&self, _input: Input) -> Output {
+        Output::Unimplemented
     }
 }
+
+fn main() {
+    let d = Day25;
+    runner_main(&d, 2021, 25);
+}
@@ -1,8 +1,8 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day3;
 
-impl AocDay for S {
+impl AocDay for Day3 {
     fn part1(&self, input: Input) -> Output {
         let mut gamma_count = [0usize; 20];
         let bits = input.lines().next().unwrap().len();
@@ -77,3 +77,8 @@ impl AocDay for S {
         (ox * co2).into()
     }
 }
+
+fn main() {
+    let d = Day3;
+    runner_main(&d, 2021, 3);
+}
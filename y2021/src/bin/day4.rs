@@ -1,9 +1,8 @@
-use crate::traits::*;
-use log::*;
+use util::{parse_table, runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day4;
 
-fn is_board_won(sorted_calls: &Vec<u32>, board: &Vec<Vec<u32>>) -> bool {
+fn is_board_won(sorted_calls: &[u32], board: &[Vec<u32>]) -> bool {
     let size = board.len() as i32;
     let check = |row_start: i32, col_start: i32, row_inc: i32, col_inc: i32| {
         for i in 0..size {
@@ -43,27 +42,22 @@ fn is_board_won(sorted_calls: &Vec<u32>, board: &Vec<Vec<u32>>) -> bool {
     return false;
 }
 
-impl crate::traits::AocDay for S {
+/// Returns the called numbers and the bingo boards
+fn parse(input: &Input) -> (Vec<u32>, Vec<Vec<Vec<u32>>>) {
+    let mut sections = input.sections();
+    let calls = sections
+        .next()
+        .unwrap()
+        .split(',')
+        .map(|v| v.parse::<u32>().unwrap())
+        .collect();
+    let boards = sections.map(|p| parse_table(p).unwrap()).collect();
+    (calls, boards)
+}
+
+impl AocDay for Day4 {
     fn part1(&self, input: Input) -> Output {
-        let mut split = input.as_str().split("\n\n");
-        let calls: Vec<u32> = split
-            .next()
-            .unwrap()
-            .split(',')
-            .filter_map(|v| v.parse::<u32>().ok())
-            .collect();
-
-        let boards: Vec<Vec<Vec<u32>>> = split
-            .map(|p| {
-                p.lines()
-                    .map(|line| {
-                        line.split(' ')
-                            .filter_map(|v| v.parse::<u32>().ok())
-                            .collect()
-                    })
-                    .collect()
-            })
-            .collect();
+        let (calls, boards) = parse(&input);
 
         let mut called = Vec::new();
         for call in calls {
@@ -90,25 +84,7 @@ impl crate::traits::AocDay for S {
     }
 
     fn part2(&self, input: Input) -> Output {
-        let mut split = input.as_str().split("\n\n");
-        let calls: Vec<u32> = split
-            .next()
-            .unwrap()
-            .split(',')
-            .filter_map(|v| v.parse::<u32>().ok())
-            .collect();
-
-        let boards: Vec<Vec<Vec<u32>>> = split
-            .map(|p| {
-                p.lines()
-                    .map(|line| {
-                        line.split(' ')
-                            .filter_map(|v| v.parse::<u32>().ok())
-                            .collect()
-                    })
-                    .collect()
-            })
-            .collect();
+        let (calls, boards) = parse(&input);
 
         let mut boards_won: Vec<_> = (0..(boards.len())).map(|_| false).collect();
         let mut win_count = 0;
@@ -145,3 +121,8 @@ impl crate::traits::AocDay for S {
         panic!()
     }
 }
+
+fn main() {
+    let d = Day4;
+    runner_main(&d, 2021, 4);
+}
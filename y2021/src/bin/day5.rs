@@ -1,8 +1,8 @@
 use itertools::Itertools;
 
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Matrix, Output};
 
-pub struct S;
+struct Day5;
 
 fn parse(lines: Vec<&str>) -> Vec<((i32, i32), (i32, i32))> {
     lines
@@ -21,7 +21,7 @@ fn parse(lines: Vec<&str>) -> Vec<((i32, i32), (i32, i32))> {
         .collect()
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day5 {
     fn part1(&self, input: Input) -> Output {
         let lines = parse(input.lines().collect());
         let size = (lines
@@ -93,3 +93,8 @@ impl crate::traits::AocDay for S {
         count.into()
     }
 }
+
+fn main() {
+    let d = Day5;
+    runner_main(&d, 2021, 5);
+}
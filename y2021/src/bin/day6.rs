@@ -0,0 +1,38 @@
+use util::counting::{Counter, Transition};
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day6;
+
+fn parse(input: &Input) -> Counter<u8> {
+    input
+        .as_str()
+        .trim()
+        .split(',')
+        .map(|n| n.parse().unwrap())
+        .collect()
+}
+
+/// Each fish counts down a timer, and when it passes zero it resets to 6 and makes a new fish
+/// with a timer of 8
+fn day(timer: &u8) -> Vec<u8> {
+    match timer {
+        0 => vec![6, 8],
+        n => vec![n - 1],
+    }
+}
+
+impl AocDay for Day6 {
+    fn part1(&self, input: Input) -> Output {
+        parse(&input).simulate(80, day).total().into()
+    }
+
+    fn part2(&self, input: Input) -> Output {
+        let days = Transition::new(0..=8, day).pow(256);
+        days.apply(&parse(&input)).total().into()
+    }
+}
+
+fn main() {
+    let d = Day6;
+    runner_main(&d, 2021, 6);
+}
@@ -1,10 +1,15 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, Output};
 
-pub struct S;
+struct Day7;
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day7 {
     fn part1(&self, input: Input) -> Output {
-        let positions: Vec<i32> = input.nums_comma_separated();
+        let positions: Vec<i32> = input
+            .as_str()
+            .trim()
+            .split(',')
+            .map(|n| n.parse().unwrap())
+            .collect();
         let average: i32 = positions.iter().sum::<i32>() / positions.len() as i32;
         let min: i32 = *positions.iter().min().unwrap();
         let max: i32 = *positions.iter().max().unwrap();
@@ -40,7 +45,12 @@ impl crate::traits::AocDay for S {
     }
 
     fn part2(&self, input: Input) -> Output {
-        let positions: Vec<i32> = input.nums_comma_separated();
+        let positions: Vec<i32> = input
+            .as_str()
+            .trim()
+            .split(',')
+            .map(|n| n.parse().unwrap())
+            .collect();
         let average: i32 = positions.iter().sum::<i32>() / positions.len() as i32;
         let min: i32 = *positions.iter().min().unwrap();
         let max: i32 = *positions.iter().max().unwrap();
@@ -78,3 +88,8 @@ impl crate::traits::AocDay for S {
         best_cost.into()
     }
 }
+
+fn main() {
+    let d = Day7;
+    runner_main(&d, 2021, 7);
+}
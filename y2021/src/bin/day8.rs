@@ -1,7 +1,8 @@
-use crate::traits::*;
-use itertools::Itertools;
+#![allow(dead_code)]
 
-pub struct S;
+use util::{runner_main, AocDay, Input, Output};
+
+struct Day8;
 
 #[derive(Copy, Clone, Debug)]
 enum Digit {
@@ -114,7 +115,7 @@ impl KnownDigit {
     }
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day8 {
     fn part1(&self, input: Input) -> Output {
         let mut count = 0;
         for line in input.lines() {
@@ -148,3 +149,8 @@ mod tests {
         assert_eq!(s.0, [0, 0, 0, 0, 1, 1, 1]);
     }
 }
+
+fn main() {
+    let d = Day8;
+    runner_main(&d, 2021, 8);
+}
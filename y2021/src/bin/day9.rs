@@ -1,6 +1,6 @@
-use crate::traits::*;
+use util::{runner_main, AocDay, Input, IntoEnumeratedCells, Matrix, Output};
 
-pub struct S;
+struct Day9;
 
 fn explore(row: usize, col: usize, mat: &Matrix<u8>, visited: &mut Matrix<bool>) -> usize {
     if *visited.get(row, col) {
@@ -11,20 +11,20 @@ fn explore(row: usize, col: usize, mat: &Matrix<u8>, visited: &mut Matrix<bool>)
         return 0;
     }
     let mut size = 1;
-    for (row, col, _value) in mat.strict_enum_neighbor_iter(row, col) {
+    for (row, col, _value) in mat.adjacent_neighbor_iter(row, col).enumerate_cells() {
         size += explore(row, col, mat, visited);
     }
     size
 }
 
-impl crate::traits::AocDay for S {
+impl AocDay for Day9 {
     fn part1(&self, input: Input) -> Output {
         let mat: Matrix<u8> = Matrix::new_from_chars(input.as_str()).unwrap();
         let mat = mat.map(|c| c - b'0');
         let mut count: usize = 0;
-        for (row, col, center) in mat.enumerated_iter() {
+        for (row, col, center) in mat.iter().enumerate_cells() {
             let mut low = true;
-            for n in mat.strict_neighbor_iter(row, col) {
+            for n in mat.adjacent_neighbor_iter(row, col) {
                 if center >= n {
                     low = false;
                     break;
@@ -43,9 +43,9 @@ impl crate::traits::AocDay for S {
         let mut visited = Matrix::new_with_value(mat.rows(), mat.cols(), false);
         let mut basins = Vec::new();
         let mat = mat.map(|c| c - b'0');
-        for (row, col, center) in mat.enumerated_iter() {
+        for (row, col, center) in mat.iter().enumerate_cells() {
             let mut low = true;
-            for n in mat.strict_neighbor_iter(row, col) {
+            for n in mat.adjacent_neighbor_iter(row, col) {
                 if center >= n {
                     low = false;
                     break;
@@ -67,3 +67,8 @@ impl crate::traits::AocDay for S {
         a.into()
     }
 }
+
+fn main() {
+    let d = Day9;
+    runner_main(&d, 2021, 9);
+}
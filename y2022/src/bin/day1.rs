@@ -3,19 +3,21 @@ use util::{runner_main, AocDay, Input, Output};
 struct Day1;
 
 /// This function takes input for day 1 and returns an iterator of the sum of elf calories
-fn elves(input: &str) -> impl Iterator<Item = i32> + '_ {
+fn elves(input: &Input) -> impl Iterator<Item = i32> {
     input
-        .split("\n\n")
-        .map(|elf| elf.lines().map(|line| line.parse::<i32>().unwrap()).sum())
+        .paragraph_groups::<i32>()
+        .unwrap()
+        .into_iter()
+        .map(|elf| elf.into_iter().sum())
 }
 
 impl AocDay for Day1 {
     fn part1(&self, i: Input) -> Output {
-        elves(i.as_str()).max().unwrap().into()
+        elves(&i).max().unwrap().into()
     }
 
     fn part2(&self, i: Input) -> Output {
-        let mut elves: Vec<_> = elves(i.as_str()).collect();
+        let mut elves: Vec<_> = elves(&i).collect();
         elves.sort();
         elves[elves.len() - 3..].iter().sum::<i32>().into()
     }
@@ -1,95 +1,81 @@
+use std::ops::ControlFlow;
+
+use util::vm::{Flow, Opcode, Operand, Program, Registers, State, Vm};
 use util::{runner_main, AocDay, Input, Matrix, Output};
 
 struct Day10;
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Op {
+    Noop,
+    Addx,
+}
+
+impl Opcode for Op {
+    const ALL: &'static [Self] = &[Op::Noop, Op::Addx];
+
+    fn mnemonic(self) -> &'static str {
+        match self {
+            Op::Noop => "noop",
+            Op::Addx => "addx",
+        }
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Op::Noop => 0,
+            Op::Addx => 1,
+        }
+    }
+
+    fn cycles(self) -> u64 {
+        match self {
+            Op::Noop => 1,
+            Op::Addx => 2,
+        }
+    }
+
+    fn execute(self, args: &[Operand], state: &mut State) -> Flow {
+        if self == Op::Addx {
+            *state.registers.by_name_mut("x") += state.read(args[0]);
+        }
+        Flow::Next
+    }
+}
+
+fn cpu(i: &Input) -> Vm<Op> {
+    let program = Program::parse(&i.0, &["x"]).unwrap();
+    Vm::new(program, Registers::new(&["x"]).with("x", 1))
+}
+
 impl AocDay for Day10 {
     fn part1(&self, i: Input) -> Output {
-        let mut x = 1;
-        let mut cycle = 1;
-        let mut next_x = None;
-        let mut it = i.lines();
         let mut total = 0;
-        loop {
-            println!("t={cycle}: x: {x} total: {total}");
+        cpu(&i).run_with_hook(|cycle, state| {
+            let cycle = cycle as i64;
             if (cycle - 20) % 40 == 0 {
-                let strength = cycle * x;
-                println!("  SIGNAL: {}, strength {strength}", cycle);
-                total += strength;
-                dbg!(total, x);
-            }
-            if let Some(next_x) = next_x.take() {
-                println!("  performing add instruction {} at", next_x);
-                x += next_x;
-            } else {
-                let Some(line) = it.next() else {
-                    break;
-                };
-                println!("  executing instruction: {line}");
-                if line.starts_with("noop") {
-                    // nop
-                } else {
-                    let mut val = 0i32;
-                    scanf::sscanf!(line, "addx {}", val).unwrap();
-                    next_x = Some(val);
-                    println!("  starting add instruction {}", val);
-                }
+                total += cycle * state.registers.by_name("x");
             }
-
-            cycle += 1;
-        }
+            ControlFlow::Continue(())
+        });
         total.into()
     }
 
     fn part2(&self, i: Input) -> Output {
-        let mut x = 1;
-        let mut cycle = 1;
-        let mut next_x = None;
-        let mut it = i.lines();
         let mut mat = Matrix::new_with_value(6, 40, b'.');
-
-        loop {
-            println!();
-            println!("t={cycle}: x: {x}");
-            let pixel = cycle - 1;
-            let row = pixel / 40;
-            let col = pixel % 40;
+        cpu(&i).run_with_hook(|cycle, state| {
+            let pixel = cycle as usize - 1;
             if pixel >= 240 {
-                break;
+                return ControlFlow::Break(());
             }
-            if ((x - 1)..=(x + 1)).contains(&(pixel as i32 % 40)) {
-                dbg!(row, col);
+            let (row, col) = (pixel / 40, pixel % 40);
+            let x = state.registers.by_name("x");
+            if ((x - 1)..=(x + 1)).contains(&(col as i64)) {
                 mat.set(row, col, b'#');
-            } else {
-            }
-            let row_str: String = mat.row(row).map(|&b| b as char).take(col + 1).collect();
-            println!("CRT: {row_str}");
-
-            if let Some(next_x) = next_x.take() {
-                println!("  performing add instruction {} at", next_x);
-                x += next_x;
-            } else {
-                let Some(line) = it.next() else {
-                    break;
-                };
-                println!("  executing instruction: {line}");
-                if line.starts_with("noop") {
-                    // nop
-                } else {
-                    let mut val = 0i32;
-                    scanf::sscanf!(line, "addx {}", val).unwrap();
-                    next_x = Some(val);
-                    println!("  starting add instruction {}", val);
-                }
             }
-
-            cycle += 1;
-        }
-        mat.print_as_chars();
-        if i.1.is_test() {
-            mat.format_as_chars().into()
-        } else {
-            "FPGPHFGH".to_owned().into()
-        }
+            ControlFlow::Continue(())
+        });
+        mat.format_as_chars().into()
     }
 }
 
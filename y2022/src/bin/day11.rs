@@ -1,5 +1,5 @@
 use itertools::Itertools;
-use std::collections::HashMap;
+use util::math::lcm_all;
 use util::{runner_main, AocDay, Input, Output};
 
 struct Day11;
@@ -21,47 +21,7 @@ struct Monkey<I: std::fmt::Debug> {
 }
 
 type Monkey1 = Monkey<u32>;
-type Monkey2 = Monkey<Item>;
-
-#[derive(Debug)]
-struct Item {
-    /// Maps a modular base (N) to the current value (mod N)
-    mods: HashMap<u32, u32>,
-    initial_value: u32,
-}
-
-impl Item {
-    fn init_base(&mut self, base: u32) {
-        self.mods.insert(base, self.initial_value % base);
-    }
-
-    fn add(&mut self, rhs: u32) {
-        for (base, val) in self.mods.iter_mut() {
-            *val = (*val + rhs) % base;
-        }
-    }
-
-    fn mul(&mut self, rhs: u32) {
-        for (base, val) in self.mods.iter_mut() {
-            *val = (*val * (rhs % base)) % base;
-        }
-    }
-
-    fn square(&mut self) {
-        for (base, val) in self.mods.iter_mut() {
-            *val = (*val * *val) % base;
-        }
-    }
-}
-
-impl From<u32> for Item {
-    fn from(value: u32) -> Self {
-        Self {
-            mods: HashMap::new(),
-            initial_value: value,
-        }
-    }
-}
+type Monkey2 = Monkey<u64>;
 
 fn parse<I>(i: &str) -> Vec<Monkey<I>>
 where
@@ -177,31 +137,26 @@ impl AocDay for Day11 {
 
     fn part2(&self, i: Input) -> Output {
         let mut monkeys: Vec<Monkey2> = parse(i.as_str());
-        let bases: Vec<u32> = monkeys.iter().map(|m| m.test_divisible).collect();
-        for base in bases {
-            for monkey in &mut monkeys {
-                for item in &mut monkey.items {
-                    item.init_base(base);
-                }
-            }
-        }
+        // every test only cares about the worry level modulo its divisor, so keeping levels
+        // modulo the lcm of all divisors leaves every test result unchanged
+        let modulus = lcm_all(monkeys.iter().map(|m| m.test_divisible as i64)).unwrap() as u64;
         let mut monkey_activity: Vec<usize> = monkeys.iter().map(|_| 0).collect();
 
         for _round in 0..10000 {
             for i in 0..monkeys.len() {
-                for mut item in std::mem::take(&mut monkeys[i].items).into_iter() {
+                for old in std::mem::take(&mut monkeys[i].items).into_iter() {
                     monkey_activity[i] += 1;
-                    match monkeys[i].operation {
-                        BoredOperation::Add(v) => item.add(v),
-                        BoredOperation::Mutiply(v) => item.mul(v),
-                        BoredOperation::MutiplyOld => item.square(),
-                    };
-                    let throw_to = if item.mods[&monkeys[i].test_divisible] == 0 {
+                    let level = match monkeys[i].operation {
+                        BoredOperation::Add(v) => old + v as u64,
+                        BoredOperation::Mutiply(v) => old * v as u64,
+                        BoredOperation::MutiplyOld => old * old,
+                    } % modulus;
+                    let throw_to = if level.is_multiple_of(monkeys[i].test_divisible as u64) {
                         monkeys[i].throw_if_true
                     } else {
                         monkeys[i].throw_if_false
                     };
-                    monkeys[throw_to].items.push(item);
+                    monkeys[throw_to].items.push(level);
                 }
             }
         }
@@ -1,6 +1,6 @@
 use std::cmp::Ordering;
 
-use util::{runner_main, AocDay, Input, Output, Parser};
+use util::{runner_main, sections, AocDay, Input, Output, ParseError, Parser};
 
 struct Day13;
 
@@ -19,30 +19,31 @@ impl std::fmt::Debug for Packet {
     }
 }
 
-fn parse(parser: &mut Parser) -> Packet {
+fn parse(parser: &mut Parser) -> Result<Packet, ParseError> {
     if parser.peek_is_digit() {
-        Packet::Number(parser.next_number::<u32>())
+        Ok(Packet::Number(parser.next_number()?))
     } else {
-        parser.expect(b'[');
-        let mut nums = vec![];
-        while parser.try_consume(b']').is_err() {
-            nums.push(parse(parser));
-            let _ = parser.try_consume(b',');
-        }
-
-        Packet::List(nums)
+        parser
+            .delimited("[", "]", |p| p.sep_by(",", parse))
+            .map(Packet::List)
     }
 }
 
+fn parse_packet(line: &str) -> Packet {
+    let mut parser = Parser::new(line);
+    let packet = parse(&mut parser).and_then(|packet| {
+        parser.expect_end()?;
+        Ok(packet)
+    });
+    packet.unwrap()
+}
+
 fn parse_lines(i: &str) -> Vec<(Packet, Packet)> {
-    i.split("\n\n")
+    sections(i)
         .map(|parts| {
-            let mut lines = parts.split('\n');
-            let mut parser = Parser::new(lines.next().unwrap());
-            let a = parse(&mut parser);
-
-            let mut parser = Parser::new(lines.next().unwrap());
-            let b = parse(&mut parser);
+            let mut lines = parts.lines();
+            let a = parse_packet(lines.next().unwrap());
+            let b = parse_packet(lines.next().unwrap());
             (a, b)
         })
         .collect()
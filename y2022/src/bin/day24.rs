@@ -1,23 +1,19 @@
-use std::collections::HashSet;
-
-use itertools::Itertools;
-use util::{runner_main, AocDay, Input, IntoEnumeratedCells, Matrix, Output};
+use util::bits::BitSet;
+use util::{runner_main, AocDay, Input, Output};
 
 struct Day24;
 
 impl AocDay for Day24 {
     fn part1(&self, i: Input) -> Output {
-        let (grid, start, end) = parse_input(i.as_str());
-        let (states, cycle_start) = generate_all_states(grid.clone());
-        solve(&states, cycle_start, 0, 0, start, grid.len() - 1, end).into()
+        let valley = Valley::parse(i.as_str());
+        valley.cross(0, true).into()
     }
 
     fn part2(&self, i: Input) -> Output {
-        let (grid, start, end) = parse_input(i.as_str());
-        let (states, cycle_start) = generate_all_states(grid.clone());
-        let fwd = solve(&states, cycle_start, 0, 0, start, grid.len() - 1, end);
-        let bck = solve(&states, cycle_start, fwd, grid.len() - 1, end, 0, start);
-        solve(&states, cycle_start, bck, 0, start, grid.len() - 1, end).into()
+        let valley = Valley::parse(i.as_str());
+        let there = valley.cross(0, true);
+        let back = valley.cross(there, false);
+        valley.cross(back, true).into()
     }
 }
 
@@ -26,160 +22,92 @@ fn main() {
     runner_main(&d, 2022, 24);
 }
 
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
-
-const MASK_LEFT: u8 = 0b0000_1000;
-const MASK_RIGHT: u8 = 0b0000_0100;
-const MASK_UP: u8 = 0b0000_0010;
-const MASK_DOWN: u8 = 0b0000_0001;
-const MASK_WALL: u8 = 0b1000_0000;
-
-const STEP: &[(isize, isize)] = &[(-1, 0), (0, -1), (0, 0), (0, 1), (1, 0)];
-
-pub fn parse_input(input: impl AsRef<str>) -> (Matrix<u8>, usize, usize) {
-    let mut grid = Matrix::new_from_chars(input.as_ref()).unwrap();
-    let start = 1;
-    let end = grid.cols() - 2;
-
-    for c in grid.iter_mut() {
-        *c = match *c {
-            b'<' => MASK_LEFT,
-            b'>' => MASK_RIGHT,
-            b'^' => MASK_UP,
-            b'v' => MASK_DOWN,
-            b'#' => MASK_WALL,
-            b'.' => {
-                // TODO: parse the actual start/end positions
-                0
-            }
-            _ => panic!(),
-        }
-    }
-
-    (grid, start, end)
+/// The blizzards inside the walls, stored as one row of bits per row of the valley for each
+/// direction, so a whole row can be moved or checked at once
+struct Valley {
+    width: usize,
+    height: usize,
+    left: Vec<BitSet>,
+    right: Vec<BitSet>,
+    up: Vec<BitSet>,
+    down: Vec<BitSet>,
 }
 
-fn next_state(grid: &Matrix<u8>) -> Matrix<u8> {
-    let mut next = grid.clone();
-    for c in next.iter_mut() {
-        *c = 0;
-    }
-    let down = grid.rows() - 2;
-    let right = grid.cols() - 2;
-
-    for (r, c, &v) in grid.iter().enumerate_cells() {
-        if v & MASK_WALL != 0 {
-            *next.get_mut(r, c) |= MASK_WALL;
-            continue;
-        }
-
-        if v & MASK_LEFT != 0 {
-            let col = if c == 1 { right } else { c - 1 };
-            *next.get_mut(r, col) |= MASK_LEFT;
-        }
-
-        if v & MASK_RIGHT != 0 {
-            let col = if c == right { 1 } else { c + 1 };
-            *next.get_mut(r, col) |= MASK_RIGHT;
-        }
-
-        if v & MASK_UP != 0 {
-            let row = if r == 1 { down } else { r - 1 };
-            *next.get_mut(row, c) |= MASK_UP;
-        }
-
-        if v & MASK_DOWN != 0 {
-            let row = if r == down { 1 } else { r + 1 };
-            *next.get_mut(row, c) |= MASK_DOWN;
+impl Valley {
+    fn parse(input: &str) -> Self {
+        let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        // drop the walls around the edge
+        let rows = &lines[1..lines.len() - 1];
+        let width = rows[0].len() - 2;
+        let height = rows.len();
+        let row_of = |blizzard: u8| -> Vec<BitSet> {
+            rows.iter()
+                .map(|row| {
+                    let cols = (0..width).filter(|&c| row[c + 1] == blizzard);
+                    BitSet::from_indices(width, cols)
+                })
+                .collect()
+        };
+        Self {
+            width,
+            height,
+            left: row_of(b'<'),
+            right: row_of(b'>'),
+            up: row_of(b'^'),
+            down: row_of(b'v'),
         }
     }
 
-    next
-}
-
-fn generate_all_states(initial: Matrix<u8>) -> (Vec<Matrix<u8>>, usize) {
-    let mut states: HashMap<Matrix<u8>, usize> = HashMap::default();
-    let mut state = initial;
-
-    let cycle_start = loop {
-        let next = next_state(&state);
-        let order = states.len();
-
-        let state_id = *states.entry(state).or_insert(order);
-        if state_id != order {
-            break state_id;
-        }
-
-        state = next;
-    };
-
-    let sequence = states
-        .iter()
-        .sorted_by_key(|(_mat, idx)| *idx)
-        .map(|(mat, _)| mat.clone())
-        .collect_vec();
-
-    for i in sequence.iter().take(5) {
-        i.print_as_chars();
+    /// Returns the cells of `row` that no blizzard is in at `time`
+    fn free(&self, row: usize, time: usize) -> BitSet {
+        let (w, h) = (self.width, self.height);
+        // horizontal blizzards stay in their row, and vertical ones in their column, so the up
+        // blizzards in `row` at `time` are the ones that started `time` rows further down
+        let mut blocked = self.left[row].rotate_right(time % w);
+        blocked |= &self.right[row].rotate_left(time % w);
+        blocked |= &self.up[(row + time) % h];
+        blocked |= &self.down[(row + h - time % h) % h];
+        !&blocked
     }
 
-    (sequence, cycle_start)
-}
-
-fn manhattan(from: (usize, usize), to: (usize, usize)) -> usize {
-    from.0.abs_diff(to.0) + from.1.abs_diff(to.1)
-}
-
-pub fn solve(
-    states: &[Matrix<u8>],
-    cycle_start: usize,
-    start_time: usize,
-    rs: usize,
-    cs: usize,
-    re: usize,
-    ce: usize,
-) -> usize {
-    let cycle_len = states.len() - cycle_start;
-
-    let mut seen: HashSet<(usize, usize, usize)> = HashSet::default();
-    let mut pq = BinaryHeap::new();
-    pq.push((
-        Reverse(manhattan((rs, cs), (re, ce))),
-        Reverse(start_time),
-        (rs, cs),
-    ));
-
-    while let Some((Reverse(_cost), Reverse(time), (r, c))) = pq.pop() {
-        if r == re && c == ce {
-            return time;
-        }
-
-        let state_idx = if time < cycle_start {
-            time + 1
+    /// Returns the time the expedition reaches the far side of the valley, having left at
+    /// `start` from the top left entrance if `downwards`, or the bottom right one otherwise.
+    ///
+    /// Every reachable cell is tracked at once, one row of bits at a time, and it can wait at
+    /// the entrance for as long as it needs to
+    fn cross(&self, start: usize, downwards: bool) -> usize {
+        let (w, h) = (self.width, self.height);
+        let (entry_row, entry_col, exit_row, exit_col) = if downwards {
+            (0, 0, h - 1, w - 1)
         } else {
-            cycle_start + (time + 1 - cycle_start) % cycle_len
+            (h - 1, w - 1, 0, 0)
         };
-
-        let state = &states[state_idx];
-
-        for (dr, dc) in STEP.iter().copied() {
-            let Some(rx) = r.checked_add_signed(dr) else {
-                continue;
-            };
-            let Some(cx) = c.checked_add_signed(dc) else {
-                continue;
-            };
-            if rx >= state.rows() || cx > state.cols() {
-                continue;
+        let mut reachable = vec![BitSet::new(w); h];
+        for time in start.. {
+            if reachable[exit_row].contains(exit_col) {
+                // stepping out of the valley takes one more minute
+                return time + 1;
             }
-
-            if *state.get(rx, cx) == 0 && seen.insert((state_idx, rx, cx)) {
-                let cost = manhattan((rx, cx), (re, ce)) + time + 1;
-                pq.push((Reverse(cost), Reverse(time + 1), (rx, cx)));
+            let mut next: Vec<BitSet> = (0..h)
+                .map(|row| {
+                    let here = &reachable[row];
+                    let mut cells = here | &(here << 1);
+                    cells |= &(here >> 1);
+                    if row > 0 {
+                        cells |= &reachable[row - 1];
+                    }
+                    if row + 1 < h {
+                        cells |= &reachable[row + 1];
+                    }
+                    cells
+                })
+                .collect();
+            next[entry_row].insert(entry_col);
+            for (row, cells) in next.iter_mut().enumerate() {
+                *cells &= &self.free(row, time + 1);
             }
+            reachable = next;
         }
+        unreachable!()
     }
-
-    panic!("no solution")
 }
@@ -1,8 +1,7 @@
 #![feature(iter_array_chunks)]
 
-use std::ops::RangeInclusive;
-
-use util::{runner_main, AocDay, Input, Output};
+use util::ranges::RangeSet;
+use util::{runner_main, scan, AocDay, Input, Output};
 
 struct Day4;
 
@@ -10,17 +9,12 @@ impl AocDay for Day4 {
     fn part1(&self, i: Input) -> Output {
         i.lines()
             .map(|line| {
-                let mut a1 = 0;
-                let mut a2 = 0;
-                let mut b1 = 0;
-                let mut b2 = 0;
-                scanf::sscanf!(line, "{}-{},{}-{}", a1, a2, b1, b2).unwrap();
-                let a: RangeInclusive<usize> = a1..=a2;
-                let b: RangeInclusive<usize> = b1..=b2;
+                let (a1, a2, b1, b2): (usize, usize, usize, usize) =
+                    scan(line, "{}-{},{}-{}").unwrap();
+                let a: RangeSet<usize> = RangeSet::from_range(a1..=a2);
+                let b: RangeSet<usize> = RangeSet::from_range(b1..=b2);
 
-                if a.contains(&b.start()) && a.contains(&b.end())
-                    || b.contains(&a.start()) && b.contains(&a.end())
-                {
+                if a.contains_range(b1..=b2) || b.contains_range(a1..=a2) {
                     1
                 } else {
                     0
@@ -33,15 +27,11 @@ impl AocDay for Day4 {
     fn part2(&self, i: Input) -> Output {
         i.lines()
             .map(|line| {
-                let mut a1 = 0;
-                let mut a2 = 0;
-                let mut b1 = 0;
-                let mut b2 = 0;
-                scanf::sscanf!(line, "{}-{},{}-{}", a1, a2, b1, b2).unwrap();
-                let a: RangeInclusive<usize> = a1..=a2;
-                let b: RangeInclusive<usize> = b1..=b2;
+                let (a1, a2, b1, b2): (usize, usize, usize, usize) =
+                    scan(line, "{}-{},{}-{}").unwrap();
+                let a: RangeSet<usize> = RangeSet::from_range(a1..=a2);
 
-                if a.contains(&b.start()) || a.contains(&b.end()) {
+                if a.overlaps(b1..=b2) {
                     1
                 } else {
                     0
@@ -1,4 +1,4 @@
-use util::{runner_main, AocDay, Input, Output};
+use util::{parse_stack_diagram, runner_main, sections, AocDay, Input, LinePattern, Output};
 
 struct Day5;
 
@@ -65,32 +65,15 @@ struct Instruction {
 }
 
 fn parse(i: &str) -> (Yard, Vec<Instruction>) {
-    let mut a = i.split("\n\n");
-    let mut yard_lines = a.next().unwrap().lines().rev();
-    let width = (yard_lines.next().unwrap().len() + 1) / 4;
-    let mut yard = Yard {
-        lanes: vec![Vec::new(); width],
+    let mut a = sections(i);
+    let yard = Yard {
+        lanes: parse_stack_diagram(a.next().unwrap()).unwrap(),
     };
-    for line in yard_lines {
-        for i in 0..width {
-            let c = line.chars().nth(i * 4 + 1).unwrap();
-            if c != ' ' {
-                yard.lanes[i].push(c);
-            }
-        }
-    }
-    let instructions = a
-        .next()
+    let instructions = LinePattern::new("move {} from {} to {}")
+        .parse_lines::<(usize, usize, usize)>(a.next().unwrap())
         .unwrap()
-        .lines()
-        .map(|line| {
-            let mut count = 0;
-            let mut src = 0;
-            let mut dst = 0;
-
-            scanf::sscanf!(line, "move {} from {} to {}", count, src, dst).unwrap();
-            Instruction { count, src, dst }
-        })
+        .into_iter()
+        .map(|(count, src, dst)| Instruction { count, src, dst })
         .collect();
 
     (yard, instructions)
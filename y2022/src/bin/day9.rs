@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use util::{runner_main, AocDay, Direction, Input, Output, Point, SignedPoint};
+use util::{runner_main, scan, AocDay, Direction, Input, Output, Point, SignedPoint};
 
 struct Day9;
 
@@ -13,11 +13,8 @@ impl AocDay for Day9 {
         visited.insert(tail);
 
         for line in i.lines() {
-            let mut dir = String::new();
-            let mut count = 0u8;
-
-            scanf::sscanf!(line, "{} {}", dir, count).unwrap();
-            let direction: Direction = dir.as_bytes()[0].try_into().unwrap();
+            let (dir, count): (char, u8) = scan(line, "{} {}").unwrap();
+            let direction: Direction = (dir as u8).try_into().unwrap();
 
             for _ in 0..count {
                 head = head.offset(direction);
@@ -51,11 +48,8 @@ impl AocDay for Day9 {
         visited.insert(tail(&knots));
 
         for line in i.lines() {
-            let mut dir = String::new();
-            let mut count = 0u8;
-
-            scanf::sscanf!(line, "{} {}", dir, count).unwrap();
-            let direction: Direction = dir.as_bytes()[0].try_into().unwrap();
+            let (dir, count): (char, u8) = scan(line, "{} {}").unwrap();
+            let direction: Direction = (dir as u8).try_into().unwrap();
 
             for _ in 0..count {
                 knots[0] = knots[0].offset(direction);